@@ -2,15 +2,25 @@ use crate::config::get_api_base_url;
 use crate::error::{IggyBenchDashboardError, Result};
 use gloo::console::log;
 use gloo::net::http::Request;
+use iggy_bench_dashboard_shared::candidate::{CandidateComparison, PullRequestCandidate};
+use iggy_bench_dashboard_shared::change_grid::{ChangeGrid, GridMetric};
+use iggy_bench_dashboard_shared::commit::{CommitChange, CommitIdentity};
+use iggy_bench_dashboard_shared::coverage::BenchmarkCoverage;
+use iggy_bench_dashboard_shared::hardware::{HardwareBenchmark, HardwareEntry};
+use iggy_bench_dashboard_shared::pagination::Page;
+use iggy_bench_dashboard_shared::performance_index::PerformanceIndex;
 use iggy_bench_dashboard_shared::BenchmarkReportLight;
-use iggy_bench_report::hardware::BenchmarkHardware;
 use iggy_bench_report::report::BenchmarkReport;
+use serde::de::DeserializeOwned;
 use std::sync::atomic::{AtomicBool, Ordering};
 use uuid::Uuid;
 use web_sys::window;
 
 static HEALTH_CHECK_DONE: AtomicBool = AtomicBool::new(false);
 
+/// Page size requested from list endpoints, matches the server maximum
+const PAGE_LIMIT: usize = 1000;
+
 async fn check_server_health() -> Result<()> {
     if HEALTH_CHECK_DONE.load(Ordering::Relaxed) {
         return Ok(());
@@ -35,10 +45,44 @@ async fn check_server_health() -> Result<()> {
     Ok(())
 }
 
-pub async fn fetch_hardware_configurations() -> Result<Vec<BenchmarkHardware>> {
+/// Hardware that is not retired, with its display name and merged identifiers
+pub async fn fetch_hardware_registry() -> Result<Vec<HardwareEntry>> {
     check_server_health().await?;
 
-    let url = format!("{}/api/hardware", get_api_base_url());
+    let url = format!("{}/api/v2/hardware/registry", get_api_base_url());
+
+    fetch_all_pages(&url, "hardware").await
+}
+
+/// Commits benchmarked on the hardware, each with the git refs it is known as
+pub async fn fetch_commits_for_hardware(hardware: &str) -> Result<Vec<CommitIdentity>> {
+    check_server_health().await?;
+
+    let url = format!("{}/api/v2/commits/{}", get_api_base_url(), hardware);
+
+    fetch_all_pages(&url, "commits").await
+}
+
+pub async fn fetch_benchmarks_for_hardware_and_gitref(
+    hardware: &str,
+    gitref: &str,
+) -> Result<Vec<BenchmarkReportLight>> {
+    check_server_health().await?;
+
+    let url = format!(
+        "{}/api/v2/benchmarks/{}/{}",
+        get_api_base_url(),
+        hardware,
+        gitref
+    );
+
+    fetch_all_pages(&url, "benchmarks").await
+}
+
+pub async fn fetch_benchmark_report_full(uuid: &Uuid) -> Result<BenchmarkReport> {
+    check_server_health().await?;
+
+    let url = format!("{}/api/v2/benchmark/full/{}", get_api_base_url(), uuid);
 
     let resp = Request::get(&url)
         .send()
@@ -47,7 +91,7 @@ pub async fn fetch_hardware_configurations() -> Result<Vec<BenchmarkHardware>> {
 
     if !resp.ok() {
         return Err(IggyBenchDashboardError::Server(format!(
-            "Failed to fetch hardware configurations: {}",
+            "Failed to fetch benchmark report: {}",
             resp.status()
         )));
     }
@@ -57,10 +101,40 @@ pub async fn fetch_hardware_configurations() -> Result<Vec<BenchmarkHardware>> {
         .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))
 }
 
-pub async fn fetch_gitrefs_for_hardware(hardware: &str) -> Result<Vec<String>> {
+pub async fn fetch_benchmark_trend(
+    hardware: &str,
+    params_identifier: &str,
+) -> Result<Vec<BenchmarkReportLight>> {
+    check_server_health().await?;
+
+    let url = format!(
+        "{}/api/v2/benchmark/trend/{}/{}",
+        get_api_base_url(),
+        hardware,
+        params_identifier
+    );
+
+    fetch_all_pages(&url, "benchmark trend").await
+}
+
+pub async fn fetch_candidates() -> Result<Vec<PullRequestCandidate>> {
     check_server_health().await?;
 
-    let url = format!("{}/api/gitrefs/{}", get_api_base_url(), hardware);
+    let url = format!("{}/api/v2/candidates", get_api_base_url());
+
+    fetch_all_pages(&url, "pull request candidates").await
+}
+
+pub async fn fetch_candidate_comparison(
+    pr_number: u64,
+    source: Option<&str>,
+) -> Result<CandidateComparison> {
+    check_server_health().await?;
+
+    let mut url = format!("{}/api/v2/candidates/{}", get_api_base_url(), pr_number);
+    if let Some(source) = source {
+        url.push_str(&format!("?source={}", urlencoding::encode(source)));
+    }
 
     let resp = Request::get(&url)
         .send()
@@ -69,7 +143,8 @@ pub async fn fetch_gitrefs_for_hardware(hardware: &str) -> Result<Vec<String>> {
 
     if !resp.ok() {
         return Err(IggyBenchDashboardError::Server(format!(
-            "Failed to fetch git refs: {}",
+            "Failed to fetch pull request #{}: {}",
+            pr_number,
             resp.status()
         )));
     }
@@ -79,16 +154,46 @@ pub async fn fetch_gitrefs_for_hardware(hardware: &str) -> Result<Vec<String>> {
         .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))
 }
 
-pub async fn fetch_benchmarks_for_hardware_and_gitref(
-    hardware: &str,
+pub async fn fetch_changes(from_gitref: &str, to_gitref: &str) -> Result<Vec<CommitChange>> {
+    check_server_health().await?;
+
+    let url = format!(
+        "{}/api/v2/changes/{}/{}",
+        get_api_base_url(),
+        from_gitref,
+        to_gitref
+    );
+
+    let resp = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| IggyBenchDashboardError::Network(e.to_string()))?;
+
+    if !resp.ok() {
+        return Err(IggyBenchDashboardError::Server(format!(
+            "Failed to fetch changes from {} to {}: {}",
+            from_gitref,
+            to_gitref,
+            resp.status()
+        )));
+    }
+
+    resp.json()
+        .await
+        .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))
+}
+
+/// Latest report of the benchmark at the git ref on every hardware it ran on
+pub async fn fetch_hardware_comparison(
+    params_identifier: &str,
     gitref: &str,
-) -> Result<Vec<BenchmarkReportLight>> {
+) -> Result<Vec<HardwareBenchmark>> {
     check_server_health().await?;
 
     let url = format!(
-        "{}/api/benchmarks/{}/{}",
+        "{}/api/v2/compare/hardware/{}/{}",
         get_api_base_url(),
-        hardware,
+        params_identifier,
         gitref
     );
 
@@ -99,7 +204,7 @@ pub async fn fetch_benchmarks_for_hardware_and_gitref(
 
     if !resp.ok() {
         return Err(IggyBenchDashboardError::Server(format!(
-            "Failed to fetch benchmarks: {}",
+            "Failed to fetch hardware comparison: {}",
             resp.status()
         )));
     }
@@ -109,10 +214,18 @@ pub async fn fetch_benchmarks_for_hardware_and_gitref(
         .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))
 }
 
-pub async fn fetch_benchmark_report_full(uuid: &Uuid) -> Result<BenchmarkReport> {
+/// Performance index of every commit benchmarked on the hardware, oldest first,
+/// against `baseline` or the oldest commit
+pub async fn fetch_performance_index_trend(
+    hardware: &str,
+    baseline: Option<&str>,
+) -> Result<Vec<PerformanceIndex>> {
     check_server_health().await?;
 
-    let url = format!("{}/api/benchmark/full/{}", get_api_base_url(), uuid);
+    let mut url = format!("{}/api/v2/index/{}", get_api_base_url(), hardware);
+    if let Some(baseline) = baseline {
+        url.push_str(&format!("?baseline={}", urlencoding::encode(baseline)));
+    }
 
     let resp = Request::get(&url)
         .send()
@@ -121,7 +234,7 @@ pub async fn fetch_benchmark_report_full(uuid: &Uuid) -> Result<BenchmarkReport>
 
     if !resp.ok() {
         return Err(IggyBenchDashboardError::Server(format!(
-            "Failed to fetch benchmark report: {}",
+            "Failed to fetch performance index: {}",
             resp.status()
         )));
     }
@@ -131,17 +244,41 @@ pub async fn fetch_benchmark_report_full(uuid: &Uuid) -> Result<BenchmarkReport>
         .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))
 }
 
-pub async fn fetch_benchmark_trend(
+pub async fn fetch_coverage(hardware: &str) -> Result<BenchmarkCoverage> {
+    check_server_health().await?;
+
+    let url = format!("{}/api/v2/coverage/{}", get_api_base_url(), hardware);
+
+    let resp = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| IggyBenchDashboardError::Network(e.to_string()))?;
+
+    if !resp.ok() {
+        return Err(IggyBenchDashboardError::Server(format!(
+            "Failed to fetch coverage: {}",
+            resp.status()
+        )));
+    }
+
+    resp.json()
+        .await
+        .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))
+}
+
+pub async fn fetch_change_grid(
     hardware: &str,
-    params_identifier: &str,
-) -> Result<Vec<BenchmarkReportLight>> {
+    metric: GridMetric,
+    gitrefs: usize,
+) -> Result<ChangeGrid> {
     check_server_health().await?;
 
     let url = format!(
-        "{}/api/benchmark/trend/{}/{}",
+        "{}/api/v2/change-grid/{}?metric={}&gitrefs={}",
         get_api_base_url(),
         hardware,
-        params_identifier
+        metric.as_str(),
+        gitrefs
     );
 
     let resp = Request::get(&url)
@@ -151,7 +288,7 @@ pub async fn fetch_benchmark_trend(
 
     if !resp.ok() {
         return Err(IggyBenchDashboardError::Server(format!(
-            "Failed to fetch benchmark trend: {}",
+            "Failed to fetch change grid: {}",
             resp.status()
         )));
     }
@@ -161,9 +298,53 @@ pub async fn fetch_benchmark_trend(
         .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))
 }
 
+/// Follows `next_cursor` until the last page and returns all items of a list endpoint
+async fn fetch_all_pages<T: DeserializeOwned>(url: &str, what: &str) -> Result<Vec<T>> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page_url = match &cursor {
+            Some(cursor) => format!(
+                "{}?limit={}&cursor={}",
+                url,
+                PAGE_LIMIT,
+                urlencoding::encode(cursor)
+            ),
+            None => format!("{}?limit={}", url, PAGE_LIMIT),
+        };
+
+        let resp = Request::get(&page_url)
+            .send()
+            .await
+            .map_err(|e| IggyBenchDashboardError::Network(e.to_string()))?;
+
+        if !resp.ok() {
+            return Err(IggyBenchDashboardError::Server(format!(
+                "Failed to fetch {}: {}",
+                what,
+                resp.status()
+            )));
+        }
+
+        let page: Page<T> = resp
+            .json()
+            .await
+            .map_err(|e| IggyBenchDashboardError::Parse(e.to_string()))?;
+
+        items.extend(page.items);
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    Ok(items)
+}
+
 pub fn download_test_artifacts(uuid: &Uuid) {
     // Create the download URL
-    let url = format!("{}/api/artifacts/{}", get_api_base_url(), uuid);
+    let url = format!("{}/api/v2/artifacts/{}", get_api_base_url(), uuid);
 
     // Use browser's native download functionality
     if let Some(window) = window() {
@@ -26,18 +26,22 @@ fn use_init_hardware(hardware_ctx: HardwareContext) {
         let dispatch = hardware_ctx.dispatch.clone();
         let already_selected = hardware_ctx.state.selected_hardware.clone();
         yew::platform::spawn_local(async move {
-            match api::fetch_hardware_configurations().await {
-                Ok(mut hw_list) => {
+            match api::fetch_hardware_registry().await {
+                Ok(hw_list) => {
                     if !hw_list.is_empty() {
-                        hw_list.sort_by(|a, b| a.identifier.cmp(&b.identifier));
                         dispatch.emit(HardwareAction::SetHardwareList(hw_list.clone()));
 
-                        if already_selected.is_none() {
-                            if let Some(first_hw) = hw_list.first() {
-                                dispatch.emit(HardwareAction::SelectHardware(Some(
-                                    first_hw.identifier.clone().unwrap(),
-                                )));
-                            }
+                        // Hardware from the URL may be an identifier merged into another
+                        let selected = match already_selected.clone() {
+                            Some(selected) => hw_list
+                                .iter()
+                                .find(|entry| entry.aliases.contains(&selected))
+                                .map(|entry| entry.identifier.clone())
+                                .unwrap_or(selected),
+                            None => hw_list[0].identifier.clone(),
+                        };
+                        if already_selected.as_ref() != Some(&selected) {
+                            dispatch.emit(HardwareAction::SelectHardware(Some(selected)));
                         }
                     }
                 }
@@ -63,19 +67,26 @@ fn use_load_gitrefs(
 
         if let Some(hardware) = hardware {
             yew::platform::spawn_local(async move {
-                match api::fetch_gitrefs_for_hardware(&hardware).await {
-                    Ok(vers) => {
+                match api::fetch_commits_for_hardware(&hardware).await {
+                    Ok(commits) => {
                         gitref_ctx
                             .dispatch
-                            .emit(GitrefAction::SetGitrefs(vers.clone()));
-                        if !vers.is_empty() {
+                            .emit(GitrefAction::SetCommits(commits.clone()));
+                        if !commits.is_empty() {
                             let current_selected = gitref_ctx.state.selected_gitref.clone();
 
-                            let final_gitref = match current_selected {
-                                Some(ref existing) if vers.contains(existing) => existing.clone(),
-                                _ => {
+                            // A link may name the commit by any of its aliases
+                            let existing = current_selected.and_then(|existing| {
+                                commits
+                                    .iter()
+                                    .find(|commit| commit.aliases.contains(&existing))
+                                    .map(|commit| commit.name.clone())
+                            });
+                            let final_gitref = match existing {
+                                Some(existing) => existing,
+                                None => {
                                     log!("Using first available version for new hardware");
-                                    vers[0].clone()
+                                    commits[0].name.clone()
                                 }
                             };
 
@@ -0,0 +1,165 @@
+use crate::api;
+use crate::components::changes::change_list::ChangeList;
+use crate::router::{AppRoute, PullRequestQuery};
+use iggy_bench_dashboard_shared::candidate::{
+    BenchmarkComparison, CandidateComparison, HardwareComparison,
+};
+use yew::prelude::*;
+use yew_router::prelude::{use_location, Link};
+
+#[derive(Properties, PartialEq)]
+pub struct CandidateComparisonProps {
+    pub pr_number: u64,
+}
+
+/// Benchmarks of a pull request next to the closest mainline results on each hardware
+#[function_component(CandidateComparisonView)]
+pub fn candidate_comparison(props: &CandidateComparisonProps) -> Html {
+    let comparison = use_state(|| None::<Result<CandidateComparison, String>>);
+    let source = use_location()
+        .and_then(|location| location.query::<PullRequestQuery>().ok())
+        .and_then(|query| query.source);
+
+    {
+        let comparison = comparison.clone();
+        use_effect_with((props.pr_number, source), move |(pr_number, source)| {
+            let pr_number = *pr_number;
+            let source = source.clone();
+            comparison.set(None);
+            yew::platform::spawn_local(async move {
+                let result = api::fetch_candidate_comparison(pr_number, source.as_deref())
+                    .await
+                    .map_err(|e| e.to_string());
+                comparison.set(Some(result));
+            });
+            || ()
+        });
+    }
+
+    let content = match &*comparison {
+        None => html! { <p class="candidates-status">{"Loading comparison..."}</p> },
+        Some(Err(e)) => html! { <p class="candidates-status">{format!("Error: {}", e)}</p> },
+        Some(Ok(comparison)) => {
+            let candidate = &comparison.candidate;
+            let mut baselines: Vec<&String> = comparison
+                .hardware
+                .iter()
+                .filter_map(|hardware| hardware.baseline_gitref.as_ref())
+                .collect();
+            baselines.sort();
+            baselines.dedup();
+            html! {
+                <>
+                    <p class="candidates-summary">
+                        {format!(
+                            "{} at {} against {} ({})",
+                            candidate.head_ref,
+                            short_sha(&candidate.head_sha),
+                            candidate.base_ref,
+                            candidate.base_gitref
+                        )}
+                    </p>
+                    if comparison.hardware.is_empty() {
+                        <p class="candidates-status">{"The run contained no benchmarks."}</p>
+                    }
+                    { for comparison.hardware.iter().map(hardware_table) }
+                    {
+                        for baselines.into_iter().map(|baseline| html! {
+                            <ChangeList
+                                from_gitref={baseline.clone()}
+                                to_gitref={candidate.head_sha.clone()}
+                            />
+                        })
+                    }
+                </>
+            }
+        }
+    };
+
+    let title = match &*comparison {
+        Some(Ok(comparison)) => match &comparison.candidate.title {
+            Some(title) => format!("#{} {}", props.pr_number, title),
+            None => format!("#{}", props.pr_number),
+        },
+        _ => format!("#{}", props.pr_number),
+    };
+
+    html! {
+        <div class="candidates-page">
+            <div class="candidates-header">
+                <h2>{title}</h2>
+                <Link<AppRoute> to={AppRoute::PullRequests}>{"All pull requests"}</Link<AppRoute>>
+            </div>
+            { content }
+        </div>
+    }
+}
+
+fn hardware_table(hardware: &HardwareComparison) -> Html {
+    let baseline = hardware
+        .baseline_gitref
+        .clone()
+        .unwrap_or_else(|| "no mainline results".to_string());
+
+    html! {
+        <div class="candidates-hardware">
+            <h3>{format!("{} vs {}", hardware.hardware, baseline)}</h3>
+            <table class="candidates-table">
+                <thead>
+                    <tr>
+                        <th>{"Benchmark"}</th>
+                        <th>{"Throughput (msg/s)"}</th>
+                        <th>{"Baseline"}</th>
+                        <th>{"Change"}</th>
+                        <th>{"P99 (ms)"}</th>
+                        <th>{"Baseline"}</th>
+                        <th>{"Change"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for hardware.benchmarks.iter().map(benchmark_row) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+fn benchmark_row(comparison: &BenchmarkComparison) -> Html {
+    let baseline = comparison.baseline.as_ref();
+    html! {
+        <tr>
+            <td>{&comparison.candidate.params.pretty_name}</td>
+            <td>{format_metric(comparison.candidate.throughput_messages_per_second(), 0)}</td>
+            <td>{format_metric(baseline.and_then(|b| b.throughput_messages_per_second()), 0)}</td>
+            { change_cell(comparison.throughput_change_percent, true) }
+            <td>{format_metric(comparison.candidate.p99_latency_ms(), 2)}</td>
+            <td>{format_metric(baseline.and_then(|b| b.p99_latency_ms()), 2)}</td>
+            { change_cell(comparison.p99_change_percent, false) }
+        </tr>
+    }
+}
+
+fn change_cell(change: Option<f64>, higher_is_better: bool) -> Html {
+    let Some(change) = change else {
+        return html! { <td>{"-"}</td> };
+    };
+    let class = if change == 0.0 {
+        None
+    } else if (change > 0.0) == higher_is_better {
+        Some("change-better")
+    } else {
+        Some("change-worse")
+    };
+
+    html! { <td class={classes!(class)}>{format!("{:+.2}%", change)}</td> }
+}
+
+fn format_metric(value: Option<f64>, precision: usize) -> String {
+    value
+        .map(|value| format!("{:.*}", precision, value))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..8).unwrap_or(sha)
+}
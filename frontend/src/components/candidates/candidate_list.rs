@@ -0,0 +1,70 @@
+use crate::api;
+use crate::router::{AppRoute, PullRequestQuery};
+use iggy_bench_dashboard_shared::candidate::PullRequestCandidate;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+/// Open pull requests with benchmarked runs
+#[function_component(CandidateList)]
+pub fn candidate_list() -> Html {
+    let candidates = use_state(|| None::<Result<Vec<PullRequestCandidate>, String>>);
+
+    {
+        let candidates = candidates.clone();
+        use_effect_with((), move |_| {
+            yew::platform::spawn_local(async move {
+                let result = api::fetch_candidates().await.map_err(|e| e.to_string());
+                candidates.set(Some(result));
+            });
+            || ()
+        });
+    }
+
+    let content = match &*candidates {
+        None => html! { <p class="candidates-status">{"Loading pull requests..."}</p> },
+        Some(Err(e)) => html! { <p class="candidates-status">{format!("Error: {}", e)}</p> },
+        Some(Ok(candidates)) if candidates.is_empty() => html! {
+            <p class="candidates-status">{"No open pull request has been benchmarked yet."}</p>
+        },
+        Some(Ok(candidates)) => html! {
+            <table class="candidates-table">
+                <thead>
+                    <tr>
+                        <th>{"Pull request"}</th>
+                        <th>{"Branch"}</th>
+                        <th>{"Base"}</th>
+                        <th>{"Ingested"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for candidates.iter().map(|candidate| html! {
+                        <tr>
+                            <td>
+                                <Link<AppRoute, PullRequestQuery>
+                                    to={AppRoute::PullRequest { pr_number: candidate.pr_number }}
+                                    query={Some(PullRequestQuery { source: Some(candidate.source.clone()) })}
+                                >
+                                    {format!("#{}", candidate.pr_number)}
+                                    { for candidate.title.as_ref().map(|title| format!(" {}", title)) }
+                                </Link<AppRoute, PullRequestQuery>>
+                            </td>
+                            <td>{&candidate.head_ref}</td>
+                            <td>{format!("{} ({})", candidate.base_ref, candidate.base_gitref)}</td>
+                            <td>{&candidate.ingested_at}</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        },
+    };
+
+    html! {
+        <div class="candidates-page">
+            <div class="candidates-header">
+                <h2>{"Pull requests"}</h2>
+                <Link<AppRoute> to={AppRoute::Single}>{"Back to dashboard"}</Link<AppRoute>>
+            </div>
+            { content }
+        </div>
+    }
+}
@@ -0,0 +1,2 @@
+pub mod candidate_comparison;
+pub mod candidate_list;
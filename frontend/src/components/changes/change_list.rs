@@ -0,0 +1,77 @@
+use crate::api;
+use iggy_bench_dashboard_shared::commit::CommitChange;
+use yew::prelude::*;
+
+/// Pull requests of the benchmarked repository, linked from the changes
+const PULL_REQUEST_URL: &str = "https://github.com/apache/iggy/pull";
+
+#[derive(Properties, PartialEq)]
+pub struct ChangeListProps {
+    pub from_gitref: String,
+    pub to_gitref: String,
+}
+
+/// Commits between two git refs, newest first
+#[function_component(ChangeList)]
+pub fn change_list(props: &ChangeListProps) -> Html {
+    let changes = use_state(|| None::<Result<Vec<CommitChange>, String>>);
+
+    {
+        let changes = changes.clone();
+        use_effect_with(
+            (props.from_gitref.clone(), props.to_gitref.clone()),
+            move |(from_gitref, to_gitref)| {
+                let from_gitref = from_gitref.clone();
+                let to_gitref = to_gitref.clone();
+                changes.set(None);
+                yew::platform::spawn_local(async move {
+                    let result = api::fetch_changes(&from_gitref, &to_gitref)
+                        .await
+                        .map_err(|e| e.to_string());
+                    changes.set(Some(result));
+                });
+                || ()
+            },
+        );
+    }
+
+    let content = match &*changes {
+        None => html! { <p class="changes-status">{"Loading changes..."}</p> },
+        Some(Err(e)) => {
+            html! { <p class="changes-status">{format!("Changes unavailable: {}", e)}</p> }
+        }
+        Some(Ok(changes)) if changes.is_empty() => {
+            html! { <p class="changes-status">{"No commits in between."}</p> }
+        }
+        Some(Ok(changes)) => html! {
+            <ul class="changes-list">
+                { for changes.iter().map(change_item) }
+            </ul>
+        },
+    };
+
+    html! {
+        <div class="changes">
+            <h3>{format!("Changes from {} to {}", props.from_gitref, props.to_gitref)}</h3>
+            { content }
+        </div>
+    }
+}
+
+fn change_item(change: &CommitChange) -> Html {
+    let date = change.date.get(..10).unwrap_or(&change.date);
+    html! {
+        <li>
+            <code>{change.sha.get(..8).unwrap_or(&change.sha)}</code>
+            {" "}
+            if let Some(pr_number) = change.pr_number {
+                <a href={format!("{}/{}", PULL_REQUEST_URL, pr_number)} target="_blank" rel="noopener noreferrer">
+                    {format!("#{}", pr_number)}
+                </a>
+                {" "}
+            }
+            {&change.subject}
+            <span class="changes-meta">{format!(" - {}, {}", change.author, date)}</span>
+        </li>
+    }
+}
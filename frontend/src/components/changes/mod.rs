@@ -0,0 +1 @@
+pub mod change_list;
@@ -0,0 +1,154 @@
+use crate::api::fetch_changes;
+use iggy_bench_dashboard_shared::commit::CommitChange;
+use js_sys::{Array, Function, Reflect};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use yew::platform::spawn_local;
+
+/// Most commits listed in a tooltip
+const MAX_TOOLTIP_CHANGES: usize = 10;
+
+/// Longest commit subject shown in a tooltip, in characters
+const MAX_SUBJECT_LEN: usize = 72;
+
+pub enum Changes {
+    Loading,
+    Loaded(Vec<CommitChange>),
+    Unavailable,
+}
+
+/// Changes fetched so far, by the git refs they lie between
+pub type ChangesCache = HashMap<(String, String), Changes>;
+
+pub type TooltipFormatter = Closure<dyn Fn(JsValue, JsValue, JsValue) -> JsValue>;
+
+/// Axis tooltip listing the series values and the commits since the previous
+/// git ref of the trend. Changes are fetched on first hover, the tooltip is
+/// updated through the echarts callback once they arrive.
+pub fn changes_tooltip_formatter(
+    gitrefs: Vec<Option<String>>,
+    cache: Rc<RefCell<ChangesCache>>,
+) -> TooltipFormatter {
+    Closure::new(move |params: JsValue, ticket: JsValue, callback: JsValue| {
+        let params = Array::from(&params);
+        let values = values_html(&params);
+        let range = Reflect::get(&params.get(0), &"dataIndex".into())
+            .ok()
+            .and_then(|index| index.as_f64())
+            .map(|index| index as usize)
+            .filter(|index| *index > 0)
+            .and_then(|index| {
+                Some((
+                    gitrefs.get(index - 1)?.clone()?,
+                    gitrefs.get(index)?.clone()?,
+                ))
+            });
+        let Some(range) = range else {
+            return values.into();
+        };
+
+        let cached = match cache.borrow().get(&range) {
+            Some(Changes::Loaded(changes)) => Some(changes_html(&range.0, changes)),
+            Some(Changes::Loading) => Some(loading_html(&range.0)),
+            Some(Changes::Unavailable) => Some(String::new()),
+            None => None,
+        };
+        let changes = cached.unwrap_or_else(|| {
+            cache.borrow_mut().insert(range.clone(), Changes::Loading);
+            spawn_local(load_changes(
+                range.clone(),
+                Rc::clone(&cache),
+                values.clone(),
+                ticket,
+                callback,
+            ));
+            loading_html(&range.0)
+        });
+        let html = format!("{}{}", values, changes);
+        html.into()
+    })
+}
+
+async fn load_changes(
+    range: (String, String),
+    cache: Rc<RefCell<ChangesCache>>,
+    values: String,
+    ticket: JsValue,
+    callback: JsValue,
+) {
+    let (html, changes) = match fetch_changes(&range.0, &range.1).await {
+        Ok(changes) => (
+            format!("{}{}", values, changes_html(&range.0, &changes)),
+            Changes::Loaded(changes),
+        ),
+        Err(_) => (values, Changes::Unavailable),
+    };
+    cache.borrow_mut().insert(range, changes);
+
+    if let Ok(callback) = callback.dyn_into::<Function>() {
+        let _ = callback.call2(&JsValue::NULL, &ticket, &html.into());
+    }
+}
+
+/// The default axis tooltip: the git ref, then each series with its value
+fn values_html(params: &Array) -> String {
+    let get = |param: &JsValue, key: &str| Reflect::get(param, &key.into()).unwrap_or_default();
+    let Some(first) = params.iter().next() else {
+        return String::new();
+    };
+
+    let mut html = escape_html(&get(&first, "name").as_string().unwrap_or_default());
+    for param in params.iter() {
+        let value = get(&param, "value")
+            .as_f64()
+            .filter(|value| !value.is_nan())
+            .map(|value| format!("{:.2}", value))
+            .unwrap_or_else(|| "-".to_string());
+        html.push_str(&format!(
+            "<br/>{}{}: <b>{}</b>",
+            get(&param, "marker").as_string().unwrap_or_default(),
+            escape_html(&get(&param, "seriesName").as_string().unwrap_or_default()),
+            value
+        ));
+    }
+    html
+}
+
+fn changes_html(from_gitref: &str, changes: &[CommitChange]) -> String {
+    let mut html = format!(
+        "<hr/><b>{} commits since {}</b>",
+        changes.len(),
+        escape_html(from_gitref)
+    );
+    for change in changes.iter().take(MAX_TOOLTIP_CHANGES) {
+        let reference = match change.pr_number {
+            Some(pr_number) => format!("#{}", pr_number),
+            None => change.sha.get(..8).unwrap_or(&change.sha).to_string(),
+        };
+        let subject: String = change.subject.chars().take(MAX_SUBJECT_LEN).collect();
+        html.push_str(&format!(
+            "<br/>{} {} ({})",
+            reference,
+            escape_html(&subject),
+            escape_html(&change.author)
+        ));
+    }
+    if changes.len() > MAX_TOOLTIP_CHANGES {
+        html.push_str(&format!(
+            "<br/>and {} more",
+            changes.len() - MAX_TOOLTIP_CHANGES
+        ));
+    }
+    html
+}
+
+fn loading_html(from_gitref: &str) -> String {
+    format!("<hr/>Loading commits since {}...", escape_html(from_gitref))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
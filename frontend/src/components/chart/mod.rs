@@ -1,8 +1,10 @@
+mod changes_tooltip;
 mod plot_trend;
 pub mod single_chart;
 pub mod trend_chart;
 
-use wasm_bindgen::prelude::wasm_bindgen;
+use js_sys::{Function, Object, Reflect};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 use web_sys::Element;
 
 #[derive(Debug, Clone)]
@@ -28,6 +30,9 @@ extern "C" {
 
     #[wasm_bindgen(method)]
     fn dispose(this: &EChartsInstance);
+
+    #[wasm_bindgen(method, js_name = setOption)]
+    fn set_option(this: &EChartsInstance, option: &JsValue);
 }
 
 pub fn dispose_chart(element_id: &str) {
@@ -41,3 +46,22 @@ pub fn dispose_chart(element_id: &str) {
         }
     }
 }
+
+/// Formats the tooltip of a rendered chart with a JS function, which charming cannot serialize
+pub fn set_tooltip_formatter(element_id: &str, formatter: &Function) {
+    let Some(element) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(element_id))
+    else {
+        return;
+    };
+    let Some(instance) = getInstanceByDom(&element) else {
+        return;
+    };
+
+    let tooltip = Object::new();
+    let option = Object::new();
+    let _ = Reflect::set(&tooltip, &"formatter".into(), formatter);
+    let _ = Reflect::set(&option, &"tooltip".into(), &tooltip);
+    instance.set_option(&option);
+}
@@ -11,6 +11,7 @@ use iggy_bench_report::{
     params::BenchmarkParams,
     plotting::{chart::IggyChart, chart_kind::ChartKind},
 };
+use std::collections::HashMap;
 
 fn trend_chart_title(params: &BenchmarkParams, kind: ChartKind) -> String {
     if let Some(remark) = &params.remark {
@@ -23,6 +24,21 @@ fn trend_chart_title(params: &BenchmarkParams, kind: ChartKind) -> String {
     }
 }
 
+/// Colors of the hardware series of an overlay chart, repeated if there are more
+const OVERLAY_COLORS: [&str; 8] = [
+    "#5470c6", "#91cc75", "#fac858", "#ee6666", "#73c0de", "#3ba272", "#fc8452", "#9a60b4",
+];
+
+/// Trend of a benchmark on one hardware, oldest commit first
+#[derive(Debug, Clone, PartialEq)]
+pub struct HardwareTrend {
+    /// Display name of the hardware
+    pub hardware: String,
+    pub reports: Vec<BenchmarkReportLight>,
+    /// Commit rank of the server by commit name, empty if the commits couldn't be fetched
+    pub commit_ranks: HashMap<String, usize>,
+}
+
 pub fn create_chart(
     config: &PlotConfig,
     plot_data: &[BenchmarkReportLight],
@@ -33,6 +49,113 @@ pub fn create_chart(
         PlotType::Throughput => create_throughput_trend_chart(plot_data, config.is_dark),
     };
 
+    render(config, &chart)
+}
+
+/// One P99 latency or throughput series per hardware over the git refs of all
+/// of them, with gaps where a hardware has no result
+pub fn create_overlay_chart(
+    config: &PlotConfig,
+    trends: &[HardwareTrend],
+    gitrefs: &[String],
+    plot_type: &PlotType,
+) -> Result<Echarts, String> {
+    let Some(first) = trends.iter().find_map(|trend| trend.reports.first()) else {
+        return Err("No trend data".to_string());
+    };
+    let subtext = first.format_params();
+    let (kind, y_axis) = match plot_type {
+        PlotType::Latency => (ChartKind::Latency, "P99 Latency [ms]"),
+        PlotType::Throughput => (ChartKind::Throughput, "Throughput [msg/s]"),
+    };
+    let title = trend_chart_title(&first.params, kind);
+
+    let mut chart = IggyChart::new(&title, &subtext, config.is_dark, true)
+        .with_category_x_axis("Version", gitrefs.to_vec())
+        .with_y_axis(y_axis);
+    for (index, trend) in trends.iter().enumerate() {
+        // NaN leaves a gap in the line
+        let values = gitrefs
+            .iter()
+            .map(|gitref| {
+                trend
+                    .reports
+                    .iter()
+                    .find(|report| report.params.gitref.as_ref() == Some(gitref))
+                    .and_then(|report| match plot_type {
+                        PlotType::Latency => report.p99_latency_ms(),
+                        PlotType::Throughput => report.throughput_messages_per_second(),
+                    })
+                    .unwrap_or(f64::NAN)
+            })
+            .collect();
+        chart = chart.add_series(
+            &trend.hardware,
+            values,
+            Symbol::Circle,
+            OVERLAY_COLORS[index % OVERLAY_COLORS.len()],
+        );
+    }
+
+    render(
+        config,
+        &chart.inner.tooltip(Tooltip::new().trigger(Trigger::Axis)),
+    )
+}
+
+/// Git refs of all trends, oldest first by the commit rank of the server. A git
+/// ref without a rank stays right after the git ref preceding it in its own trend.
+pub fn shared_gitrefs(trends: &[HardwareTrend]) -> Vec<String> {
+    let ranks: HashMap<&String, usize> = trends
+        .iter()
+        .flat_map(|trend| &trend.commit_ranks)
+        .map(|(name, rank)| (name, *rank))
+        .collect();
+
+    let mut rank = None;
+    let mut ranked: Vec<(Option<usize>, String)> = merged_gitrefs(trends)
+        .into_iter()
+        .map(|gitref| {
+            rank = ranks.get(&gitref).copied().or(rank);
+            (rank, gitref)
+        })
+        .collect();
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked.into_iter().map(|(_, gitref)| gitref).collect()
+}
+
+/// Git refs of all trends, each trend's in its order. A git ref missing from
+/// the trends before is placed after the git ref preceding it in its own trend.
+fn merged_gitrefs(trends: &[HardwareTrend]) -> Vec<String> {
+    let mut gitrefs: Vec<String> = Vec::new();
+    for trend in trends {
+        let trend_gitrefs: Vec<&String> = trend
+            .reports
+            .iter()
+            .filter_map(|report| report.params.gitref.as_ref())
+            .collect();
+        let mut previous: Option<usize> = None;
+        for (index, gitref) in trend_gitrefs.iter().enumerate() {
+            if let Some(position) = gitrefs.iter().position(|known| known == *gitref) {
+                previous = Some(position);
+                continue;
+            }
+            let position = match previous {
+                Some(previous) => previous + 1,
+                // Before the first later git ref of the trend that is already placed
+                None => trend_gitrefs[index + 1..]
+                    .iter()
+                    .find_map(|later| gitrefs.iter().position(|known| known == *later))
+                    .unwrap_or(gitrefs.len()),
+            };
+            gitrefs.insert(position, (*gitref).clone());
+            previous = Some(position);
+        }
+    }
+    gitrefs
+}
+
+fn render(config: &PlotConfig, chart: &Chart) -> Result<Echarts, String> {
     let renderer = if config.is_dark {
         WasmRenderer::new(config.width, config.height).theme(Theme::Dark)
     } else {
@@ -40,7 +163,7 @@ pub fn create_chart(
     };
 
     renderer
-        .render(&config.element_id, &chart)
+        .render(&config.element_id, chart)
         .map_err(|e| e.to_string())
 }
 
@@ -1,10 +1,17 @@
-use crate::api::fetch_benchmark_trend;
-use crate::components::chart::plot_trend::create_chart;
-use crate::components::chart::{dispose_chart, PlotConfig, PlotType};
+use crate::api::{fetch_benchmark_trend, fetch_commits_for_hardware};
+use crate::components::chart::changes_tooltip::{
+    changes_tooltip_formatter, ChangesCache, TooltipFormatter,
+};
+use crate::components::chart::plot_trend::{
+    create_chart, create_overlay_chart, shared_gitrefs, HardwareTrend,
+};
+use crate::components::chart::{dispose_chart, set_tooltip_formatter, PlotConfig, PlotType};
 use crate::components::selectors::measurement_type_selector::MeasurementType;
 use crate::state::hardware::use_hardware;
 use charming::Echarts;
 use gloo::console::log;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
 use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew_hooks::use_size;
@@ -21,32 +28,63 @@ pub struct TrendChartProps {
 #[function_component(TrendChart)]
 pub fn trend_chart(props: &TrendChartProps) -> Html {
     let hardware_ctx = use_hardware();
-    let chart_data = use_state(Vec::new);
+    let chart_data = use_state(Vec::<HardwareTrend>::new);
     let chart_node = use_node_ref();
     let chart_size = use_size(chart_node.clone());
     let echarts = use_state(|| None::<Echarts>);
+    let changes_cache = use_mut_ref(ChangesCache::new);
+    // Kept alive while the chart may call it
+    let tooltip_formatter = use_mut_ref(|| None::<TooltipFormatter>);
 
     {
         let params_identifier = props.params_identifier.clone();
-        let hardware = hardware_ctx.state.selected_hardware.clone();
+        let state = hardware_ctx.state.clone();
         let chart_data = chart_data.clone();
 
         use_effect_with(
-            (params_identifier, hardware),
-            move |(params_identifier, hardware)| {
+            (
+                params_identifier,
+                state.selected_hardware.clone(),
+                state.overlay_hardware.clone(),
+            ),
+            move |(params_identifier, hardware, overlay_hardware)| {
                 let params_identifier = params_identifier.clone();
-                let hardware = hardware.clone();
+                let hardware: Vec<String> = hardware
+                    .iter()
+                    .chain(overlay_hardware.iter())
+                    .cloned()
+                    .collect();
                 spawn_local(async move {
-                    if let Some(hardware) = hardware {
-                        match fetch_benchmark_trend(&hardware, &params_identifier).await {
-                            Ok(data) => {
-                                chart_data.set(data);
+                    // Overlaid trends are ordered by the commit rank of the server
+                    let overlay = hardware.len() > 1;
+                    let mut trends = Vec::new();
+                    for hardware in hardware {
+                        let commit_ranks = if overlay {
+                            match fetch_commits_for_hardware(&hardware).await {
+                                Ok(commits) => commits
+                                    .into_iter()
+                                    .filter_map(|commit| Some((commit.name, commit.rank?)))
+                                    .collect(),
+                                Err(e) => {
+                                    log!(format!("Error fetching commits of {}: {}", hardware, e));
+                                    HashMap::new()
+                                }
                             }
+                        } else {
+                            HashMap::new()
+                        };
+                        match fetch_benchmark_trend(&hardware, &params_identifier).await {
+                            Ok(reports) => trends.push(HardwareTrend {
+                                hardware: state.display_name(&hardware),
+                                reports,
+                                commit_ranks,
+                            }),
                             Err(e) => {
-                                log!(format!("Error fetching trend data: {}", e));
+                                log!(format!("Error fetching trend data of {}: {}", hardware, e));
                             }
                         }
                     }
+                    chart_data.set(trends);
                 });
                 Box::new(|| ()) as CleanupFn
             },
@@ -58,6 +96,8 @@ pub fn trend_chart(props: &TrendChartProps) -> Html {
         let measurement_type = props.measurement_type.clone();
         let is_dark = props.is_dark;
         let echarts = echarts.clone();
+        let changes_cache = changes_cache.clone();
+        let tooltip_formatter = tooltip_formatter.clone();
 
         use_effect_with(
             (data, measurement_type, is_dark, chart_size),
@@ -85,9 +125,34 @@ pub fn trend_chart(props: &TrendChartProps) -> Html {
                     dispose_chart("trend-chart");
                 }
 
-                // Render new chart
-                match create_chart(&config, data, &plot_type) {
-                    Ok(new_e) => echarts.set(Some(new_e)),
+                // Render new chart, overlaid if several hardware are selected
+                let (chart, gitrefs) = match data.as_slice() {
+                    [trend] => (
+                        create_chart(&config, &trend.reports, &plot_type),
+                        trend
+                            .reports
+                            .iter()
+                            .map(|d| d.params.gitref.clone())
+                            .collect(),
+                    ),
+                    trends => {
+                        let gitrefs = shared_gitrefs(trends);
+                        (
+                            create_overlay_chart(&config, trends, &gitrefs, &plot_type),
+                            gitrefs.into_iter().map(Some).collect(),
+                        )
+                    }
+                };
+                match chart {
+                    Ok(new_e) => {
+                        let formatter = changes_tooltip_formatter(gitrefs, changes_cache.clone());
+                        set_tooltip_formatter(
+                            &config.element_id,
+                            formatter.as_ref().unchecked_ref(),
+                        );
+                        *tooltip_formatter.borrow_mut() = Some(formatter);
+                        echarts.set(Some(new_e));
+                    }
                     Err(e) => log!(format!("Error rendering chart: {}", e)),
                 }
 
@@ -0,0 +1,186 @@
+use super::SingleViewQuery;
+use crate::api;
+use crate::router::AppRoute;
+use iggy_bench_dashboard_shared::change_grid::{ChangeGrid, ChangeGridRow, GridMetric};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+/// Number of git refs the grid can be asked for
+const GITREF_COUNTS: [usize; 4] = [10, 20, 50, 100];
+
+/// Change in percent at which a cell gets its strongest color
+const FULL_COLOR_CHANGE: f64 = 20.0;
+
+/// Changes smaller than this, in percent, are treated as noise and left uncolored
+const NOISE_CHANGE: f64 = 1.0;
+
+#[derive(Properties, PartialEq)]
+pub struct ChangeGridProps {
+    pub hardware: String,
+}
+
+/// Percent change of a metric of every benchmark over the last git refs of the hardware
+#[function_component(ChangeGridView)]
+pub fn change_grid(props: &ChangeGridProps) -> Html {
+    let metric = use_state(GridMetric::default);
+    let gitref_count = use_state(|| GITREF_COUNTS[0]);
+    let grid = use_state(|| None::<Result<ChangeGrid, String>>);
+
+    {
+        let grid = grid.clone();
+        use_effect_with(
+            (props.hardware.clone(), *metric, *gitref_count),
+            move |(hardware, metric, gitref_count)| {
+                let hardware = hardware.clone();
+                let metric = *metric;
+                let gitref_count = *gitref_count;
+                grid.set(None);
+                yew::platform::spawn_local(async move {
+                    let result = api::fetch_change_grid(&hardware, metric, gitref_count)
+                        .await
+                        .map_err(|e| e.to_string());
+                    grid.set(Some(result));
+                });
+                || ()
+            },
+        );
+    }
+
+    let on_metric_change = {
+        let metric = metric.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                metric.set(match select.value().as_str() {
+                    "throughput" => GridMetric::Throughput,
+                    _ => GridMetric::P99,
+                });
+            }
+        })
+    };
+
+    let on_count_change = {
+        let gitref_count = gitref_count.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                if let Ok(count) = select.value().parse() {
+                    gitref_count.set(count);
+                }
+            }
+        })
+    };
+
+    let content = match &*grid {
+        None => html! { <p class="compare-status">{"Loading change grid..."}</p> },
+        Some(Err(e)) => html! { <p class="compare-status">{format!("Error: {}", e)}</p> },
+        Some(Ok(grid)) => html! {
+            <div class="heat-grid">
+                <table class="compare-table heat-table">
+                    <thead>
+                        <tr>
+                            <th>{"Benchmark"}</th>
+                            { for grid.gitrefs.iter().map(|gitref| html! {
+                                <th class="heat-gitref" title={gitref.clone()}>{gitref}</th>
+                            }) }
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for grid.benchmarks.iter().map(|row| grid_row(grid, row)) }
+                    </tbody>
+                </table>
+            </div>
+        },
+    };
+
+    html! {
+        <div class="compare-page">
+            <div class="compare-header">
+                <h2>{format!("Changes on {}", props.hardware)}</h2>
+                <Link<AppRoute> to={AppRoute::Single}>{"Back to dashboard"}</Link<AppRoute>>
+            </div>
+            <p class="compare-summary">
+                {"Change of each benchmark against the previous version it ran at, green is faster, \
+                  red is slower."}
+            </p>
+            <div class="compare-baseline">
+                <label>{"Metric "}
+                    <select onchange={on_metric_change}>
+                        <option value="p99" selected={*metric == GridMetric::P99}>{"P99 latency"}</option>
+                        <option value="throughput" selected={*metric == GridMetric::Throughput}>
+                            {"Throughput"}
+                        </option>
+                    </select>
+                </label>
+                <label>{" Versions "}
+                    <select onchange={on_count_change}>
+                        { for GITREF_COUNTS.iter().map(|count| html! {
+                            <option value={count.to_string()} selected={*gitref_count == *count}>
+                                {count}
+                            </option>
+                        }) }
+                    </select>
+                </label>
+            </div>
+            { content }
+        </div>
+    }
+}
+
+fn grid_row(grid: &ChangeGrid, row: &ChangeGridRow) -> Html {
+    html! {
+        <tr>
+            <td title={row.params_identifier.clone()}>{&row.pretty_name}</td>
+            { for grid.gitrefs.iter().enumerate().map(|(column, gitref)| {
+                let value = row.values[column];
+                let change = row.change_percent[column];
+                let title = match (value, change) {
+                    (Some(value), Some(change)) => format!("{:.2} ({:+.1}%)", value, change),
+                    (Some(value), None) => format!("{:.2}", value),
+                    _ => "Did not run".to_string(),
+                };
+                let query = SingleViewQuery {
+                    hardware: grid.hardware.clone(),
+                    gitref: gitref.clone(),
+                    params_identifier: row.params_identifier.clone(),
+                };
+                html! {
+                    <td
+                        class="heat-cell"
+                        style={change.and_then(|change| change_color(grid.metric, change))}
+                        {title}
+                    >
+                        <Link<AppRoute, SingleViewQuery> to={AppRoute::Single} query={Some(query)}>
+                            { change.map(|change| format!("{:+.1}", change)).unwrap_or_default() }
+                        </Link<AppRoute, SingleViewQuery>>
+                    </td>
+                }
+            }) }
+        </tr>
+    }
+}
+
+/// Background of a cell, green for improvements and red for regressions,
+/// stronger the larger the change
+fn change_color(metric: GridMetric, change: f64) -> Option<String> {
+    if change.abs() < NOISE_CHANGE {
+        return None;
+    }
+    let improved = (change > 0.0) == metric.higher_is_better();
+    let alpha = 0.15 + 0.65 * (change.abs() / FULL_COLOR_CHANGE).min(1.0);
+    let (red, green, blue) = if improved {
+        (46, 125, 50)
+    } else {
+        (198, 40, 40)
+    };
+    Some(format!(
+        "background-color: rgba({}, {}, {}, {:.2});",
+        red, green, blue, alpha
+    ))
+}
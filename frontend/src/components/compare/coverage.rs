@@ -0,0 +1,128 @@
+use super::SingleViewQuery;
+use crate::api;
+use crate::router::AppRoute;
+use iggy_bench_dashboard_shared::coverage::{BenchmarkCoverage, CoverageRow};
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+#[derive(Properties, PartialEq)]
+pub struct CoverageProps {
+    pub hardware: String,
+}
+
+/// Heatmap of which benchmarks ran at which git refs on the hardware
+#[function_component(CoverageView)]
+pub fn coverage(props: &CoverageProps) -> Html {
+    let coverage = use_state(|| None::<Result<BenchmarkCoverage, String>>);
+
+    {
+        let coverage = coverage.clone();
+        use_effect_with(props.hardware.clone(), move |hardware| {
+            let hardware = hardware.clone();
+            coverage.set(None);
+            yew::platform::spawn_local(async move {
+                let result = api::fetch_coverage(&hardware)
+                    .await
+                    .map_err(|e| e.to_string());
+                coverage.set(Some(result));
+            });
+            || ()
+        });
+    }
+
+    let content = match &*coverage {
+        None => html! { <p class="compare-status">{"Loading coverage..."}</p> },
+        Some(Err(e)) => html! { <p class="compare-status">{format!("Error: {}", e)}</p> },
+        Some(Ok(coverage)) => {
+            let missing = coverage
+                .benchmarks
+                .iter()
+                .map(|row| {
+                    row.counts
+                        .iter()
+                        .skip(first_run(row))
+                        .filter(|count| **count == 0)
+                        .count()
+                })
+                .sum::<usize>();
+            html! {
+                <>
+                    <p class="compare-summary">
+                        {format!(
+                            "{} benchmarks over {} versions, {} runs missing since the benchmark first ran.",
+                            coverage.benchmarks.len(),
+                            coverage.gitrefs.len(),
+                            missing
+                        )}
+                    </p>
+                    <div class="heat-grid">
+                        <table class="compare-table heat-table">
+                            <thead>
+                                <tr>
+                                    <th>{"Benchmark"}</th>
+                                    { for coverage.gitrefs.iter().map(|gitref| html! {
+                                        <th class="heat-gitref" title={gitref.clone()}>{gitref}</th>
+                                    }) }
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { for coverage.benchmarks.iter().map(|row| coverage_row(coverage, row)) }
+                            </tbody>
+                        </table>
+                    </div>
+                </>
+            }
+        }
+    };
+
+    html! {
+        <div class="compare-page">
+            <div class="compare-header">
+                <h2>{format!("Benchmark coverage on {}", props.hardware)}</h2>
+                <Link<AppRoute> to={AppRoute::Single}>{"Back to dashboard"}</Link<AppRoute>>
+            </div>
+            { content }
+        </div>
+    }
+}
+
+fn coverage_row(coverage: &BenchmarkCoverage, row: &CoverageRow) -> Html {
+    let first_run = first_run(row);
+    html! {
+        <tr>
+            <td title={row.params_identifier.clone()}>{&row.pretty_name}</td>
+            { for row.counts.iter().zip(&coverage.gitrefs).enumerate().map(|(column, (count, gitref))| {
+                let class = if *count > 0 {
+                    "coverage-present"
+                } else if column > first_run {
+                    "coverage-missing"
+                } else {
+                    "coverage-absent"
+                };
+                let query = SingleViewQuery {
+                    hardware: coverage.hardware.clone(),
+                    gitref: gitref.clone(),
+                    params_identifier: row.params_identifier.clone(),
+                };
+                html! {
+                    <td class={classes!("heat-cell", class)}>
+                        <Link<AppRoute, SingleViewQuery>
+                            to={AppRoute::Single}
+                            query={Some(query)}
+                        >
+                            {count}
+                        </Link<AppRoute, SingleViewQuery>>
+                    </td>
+                }
+            }) }
+        </tr>
+    }
+}
+
+/// Column of the first git ref the benchmark ran at, later empty cells are missing runs
+fn first_run(row: &CoverageRow) -> usize {
+    row.counts
+        .iter()
+        .position(|count| *count > 0)
+        .unwrap_or(row.counts.len())
+}
@@ -0,0 +1,189 @@
+use crate::api;
+use crate::components::chart::dispose_chart;
+use crate::router::AppRoute;
+use charming::{
+    component::{Axis, Legend, Title},
+    element::{AxisType, Tooltip, Trigger},
+    series::Bar,
+    theme::Theme,
+    Chart, WasmRenderer,
+};
+use gloo::console::log;
+use iggy_bench_dashboard_shared::hardware::HardwareBenchmark;
+use yew::prelude::*;
+use yew_hooks::use_size;
+use yew_router::prelude::Link;
+
+const CHART_ID: &str = "hardware-comparison-chart";
+
+#[derive(Properties, PartialEq)]
+pub struct HardwareComparisonProps {
+    pub params_identifier: String,
+    pub gitref: String,
+}
+
+/// One benchmark of a git ref on every hardware it ran on, side by side
+#[function_component(HardwareComparisonView)]
+pub fn hardware_comparison(props: &HardwareComparisonProps) -> Html {
+    let (is_dark, _) = use_context::<(bool, Callback<()>)>().expect("Theme context not found");
+    let comparison = use_state(|| None::<Result<Vec<HardwareBenchmark>, String>>);
+    let chart_node = use_node_ref();
+    let chart_size = use_size(chart_node.clone());
+
+    {
+        let comparison = comparison.clone();
+        use_effect_with(
+            (props.params_identifier.clone(), props.gitref.clone()),
+            move |(params_identifier, gitref)| {
+                let params_identifier = params_identifier.clone();
+                let gitref = gitref.clone();
+                comparison.set(None);
+                yew::platform::spawn_local(async move {
+                    let result = api::fetch_hardware_comparison(&params_identifier, &gitref)
+                        .await
+                        .map_err(|e| e.to_string());
+                    comparison.set(Some(result));
+                });
+                || ()
+            },
+        );
+    }
+
+    {
+        let benchmarks = match &*comparison {
+            Some(Ok(benchmarks)) => benchmarks.clone(),
+            _ => Vec::new(),
+        };
+        use_effect_with(
+            (benchmarks, is_dark, chart_size),
+            move |(benchmarks, is_dark, (width, height))| {
+                dispose_chart(CHART_ID);
+                if !benchmarks.is_empty() {
+                    let renderer = WasmRenderer::new(*width, *height).theme(if *is_dark {
+                        Theme::Dark
+                    } else {
+                        Theme::Default
+                    });
+                    if let Err(e) = renderer.render(CHART_ID, &comparison_chart(benchmarks)) {
+                        log!(format!("Error rendering chart: {}", e));
+                    }
+                }
+                || ()
+            },
+        );
+    }
+
+    let content = match &*comparison {
+        None => html! { <p class="compare-status">{"Loading comparison..."}</p> },
+        Some(Err(e)) => html! { <p class="compare-status">{format!("Error: {}", e)}</p> },
+        Some(Ok(benchmarks)) => html! {
+            <>
+                if let Some(first) = benchmarks.first() {
+                    <p class="compare-summary">{first.report.format_params()}</p>
+                }
+                <table class="compare-table">
+                    <thead>
+                        <tr>
+                            <th>{"Hardware"}</th>
+                            <th>{"CPU"}</th>
+                            <th>{"Cores"}</th>
+                            <th>{"Memory (GB)"}</th>
+                            <th>{"OS"}</th>
+                            <th>{"Throughput (msg/s)"}</th>
+                            <th>{"P99 (ms)"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for benchmarks.iter().map(hardware_row) }
+                    </tbody>
+                </table>
+            </>
+        },
+    };
+
+    let title = match &*comparison {
+        Some(Ok(benchmarks)) if !benchmarks.is_empty() => format!(
+            "{} at {}",
+            benchmarks[0].report.params.pretty_name, props.gitref
+        ),
+        _ => format!("{} at {}", props.params_identifier, props.gitref),
+    };
+
+    html! {
+        <div class="compare-page">
+            <div class="compare-header">
+                <h2>{title}</h2>
+                <Link<AppRoute> to={AppRoute::Single}>{"Back to dashboard"}</Link<AppRoute>>
+            </div>
+            <div ref={chart_node} class="compare-chart">
+                <div id={CHART_ID} style="width: 100%; height: 100%;"></div>
+            </div>
+            { content }
+        </div>
+    }
+}
+
+/// Throughput and P99 latency bars per hardware, on separate axes
+fn comparison_chart(benchmarks: &[HardwareBenchmark]) -> Chart {
+    let names: Vec<String> = benchmarks.iter().map(hardware_name).collect();
+    let throughput: Vec<f64> = benchmarks
+        .iter()
+        .map(|benchmark| {
+            benchmark
+                .report
+                .throughput_messages_per_second()
+                .unwrap_or_default()
+        })
+        .collect();
+    let p99: Vec<f64> = benchmarks
+        .iter()
+        .map(|benchmark| benchmark.report.p99_latency_ms().unwrap_or_default())
+        .collect();
+
+    Chart::new()
+        .title(Title::new().text("Throughput and P99 latency per hardware"))
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+        .legend(Legend::new().bottom("0"))
+        .x_axis(Axis::new().type_(AxisType::Category).data(names))
+        .y_axis(
+            Axis::new()
+                .type_(AxisType::Value)
+                .name("Throughput [msg/s]"),
+        )
+        .y_axis(Axis::new().type_(AxisType::Value).name("P99 Latency [ms]"))
+        .series(Bar::new().name("Throughput [msg/s]").data(throughput))
+        .series(
+            Bar::new()
+                .name("P99 Latency [ms]")
+                .y_axis_index(1.0)
+                .data(p99),
+        )
+}
+
+fn hardware_row(benchmark: &HardwareBenchmark) -> Html {
+    let hardware = &benchmark.report.hardware;
+    html! {
+        <tr>
+            <td title={benchmark.identifier.clone()}>{hardware_name(benchmark)}</td>
+            <td>{&hardware.cpu_name}</td>
+            <td>{hardware.cpu_cores}</td>
+            <td>{format!("{:.1}", hardware.total_memory_mb as f64 / 1024.0)}</td>
+            <td>{format!("{} {}", hardware.os_name, hardware.os_version)}</td>
+            <td>{format_metric(benchmark.report.throughput_messages_per_second(), 0)}</td>
+            <td>{format_metric(benchmark.report.p99_latency_ms(), 2)}</td>
+        </tr>
+    }
+}
+
+fn hardware_name(benchmark: &HardwareBenchmark) -> String {
+    benchmark
+        .display_name
+        .clone()
+        .unwrap_or_else(|| benchmark.identifier.clone())
+}
+
+fn format_metric(value: Option<f64>, precision: usize) -> String {
+    value
+        .map(|value| format!("{:.*}", precision, value))
+        .unwrap_or_else(|| "-".to_string())
+}
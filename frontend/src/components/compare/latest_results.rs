@@ -0,0 +1,356 @@
+use super::SingleViewQuery;
+use crate::api;
+use crate::router::AppRoute;
+use iggy_bench_dashboard_shared::change_grid::{ChangeGridRow, GridMetric};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+use yew_router::prelude::{use_navigator, Link};
+
+/// Number of git refs drawn in the sparklines
+const SPARKLINE_GITREFS: usize = 20;
+
+const SPARKLINE_WIDTH: f64 = 100.0;
+const SPARKLINE_HEIGHT: f64 = 24.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortKey {
+    Regression,
+    P99Change,
+    ThroughputChange,
+    Name,
+}
+
+/// One benchmark of the newest git ref with its recent history
+#[derive(Clone, PartialEq)]
+struct LatestResult {
+    params_identifier: String,
+    pretty_name: String,
+    throughput: Option<f64>,
+    p99: Option<f64>,
+    throughput_change: Option<f64>,
+    p99_change: Option<f64>,
+    throughput_history: Vec<Option<f64>>,
+    p99_history: Vec<Option<f64>>,
+}
+
+impl LatestResult {
+    /// How much slower the benchmark got in percent, the worse of its
+    /// throughput drop and P99 latency increase, negative if it got faster
+    fn regression(&self) -> Option<f64> {
+        let throughput = self.throughput_change.map(|change| -change);
+        match (throughput, self.p99_change) {
+            (Some(throughput), Some(p99)) => Some(throughput.max(p99)),
+            (throughput, p99) => throughput.or(p99),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LatestResultsProps {
+    pub hardware: String,
+}
+
+/// Every benchmark of the newest git ref on the hardware, with its change
+/// against the previous git ref and its recent trend
+#[function_component(LatestResultsView)]
+pub fn latest_results(props: &LatestResultsProps) -> Html {
+    let results = use_state(|| None::<Result<(String, Vec<LatestResult>), String>>);
+    let sort = use_state(|| SortKey::Regression);
+
+    {
+        let results = results.clone();
+        use_effect_with(props.hardware.clone(), move |hardware| {
+            let hardware = hardware.clone();
+            results.set(None);
+            yew::platform::spawn_local(async move {
+                let result = fetch_latest_results(&hardware)
+                    .await
+                    .map_err(|e| e.to_string());
+                results.set(Some(result));
+            });
+            || ()
+        });
+    }
+
+    let on_sort_change = {
+        let sort = sort.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                sort.set(match select.value().as_str() {
+                    "p99" => SortKey::P99Change,
+                    "throughput" => SortKey::ThroughputChange,
+                    "name" => SortKey::Name,
+                    _ => SortKey::Regression,
+                });
+            }
+        })
+    };
+
+    let content = match &*results {
+        None => html! { <p class="compare-status">{"Loading latest results..."}</p> },
+        Some(Err(e)) => html! { <p class="compare-status">{format!("Error: {}", e)}</p> },
+        Some(Ok((_, rows))) if rows.is_empty() => html! {
+            <p class="compare-status">{"No benchmarks on this hardware."}</p>
+        },
+        Some(Ok((gitref, rows))) => {
+            let mut rows = rows.clone();
+            sort_results(&mut rows, *sort);
+            html! {
+                <table class="compare-table">
+                    <thead>
+                        <tr>
+                            <th>{"Benchmark"}</th>
+                            <th>{"Throughput (msg/s)"}</th>
+                            <th>{"Change"}</th>
+                            <th>{format!("Last {}", SPARKLINE_GITREFS)}</th>
+                            <th>{"P99 (ms)"}</th>
+                            <th>{"Change"}</th>
+                            <th>{format!("Last {}", SPARKLINE_GITREFS)}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for rows.iter().map(|row| result_row(&props.hardware, gitref, row)) }
+                    </tbody>
+                </table>
+            }
+        }
+    };
+
+    let title = match &*results {
+        Some(Ok((gitref, _))) => format!("Latest results on {} at {}", props.hardware, gitref),
+        _ => format!("Latest results on {}", props.hardware),
+    };
+
+    html! {
+        <div class="compare-page">
+            <div class="compare-header">
+                <h2>{title}</h2>
+                <Link<AppRoute> to={AppRoute::Single}>{"Back to dashboard"}</Link<AppRoute>>
+            </div>
+            <p class="compare-summary">
+                {"Every benchmark of the newest version, changes against the previous version it ran at."}
+            </p>
+            <div class="compare-baseline">
+                <label>{"Sort by "}
+                    <select onchange={on_sort_change}>
+                        <option value="regression" selected={*sort == SortKey::Regression}>
+                            {"Largest regression"}
+                        </option>
+                        <option value="p99" selected={*sort == SortKey::P99Change}>
+                            {"P99 latency regression"}
+                        </option>
+                        <option value="throughput" selected={*sort == SortKey::ThroughputChange}>
+                            {"Throughput regression"}
+                        </option>
+                        <option value="name" selected={*sort == SortKey::Name}>{"Name"}</option>
+                    </select>
+                </label>
+            </div>
+            { content }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LatestResultsRedirectProps {
+    /// Selected hardware, an identifier or alias, the first hardware if `None`
+    pub hardware: Option<String>,
+}
+
+/// Replaces the current route with the latest results of the selected hardware
+#[function_component(LatestResultsRedirect)]
+pub fn latest_results_redirect(props: &LatestResultsRedirectProps) -> Html {
+    let error = use_state(|| None::<String>);
+    let navigator = use_navigator();
+
+    {
+        let error = error.clone();
+        use_effect_with(props.hardware.clone(), move |selected| {
+            let selected = selected.clone();
+            yew::platform::spawn_local(async move {
+                let hardware = match api::fetch_hardware_registry().await {
+                    Ok(hardware_list) => match selected {
+                        // The selected hardware may be an identifier merged into another
+                        Some(selected) => Some(
+                            hardware_list
+                                .iter()
+                                .find(|entry| entry.aliases.contains(&selected))
+                                .map(|entry| entry.identifier.clone())
+                                .unwrap_or(selected),
+                        ),
+                        None => hardware_list.first().map(|entry| entry.identifier.clone()),
+                    },
+                    Err(e) => {
+                        error.set(Some(e.to_string()));
+                        return;
+                    }
+                };
+                match (hardware, navigator) {
+                    (Some(hardware), Some(navigator)) => {
+                        navigator.replace(&AppRoute::LatestResults { hardware })
+                    }
+                    (None, _) => error.set(Some("No hardware has been benchmarked yet.".into())),
+                    (_, None) => error.set(Some("Navigation is not available.".into())),
+                }
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <div class="compare-page">
+            if let Some(error) = &*error {
+                <p class="compare-status">{error}</p>
+                <Link<AppRoute> to={AppRoute::Single}>{"Open the dashboard"}</Link<AppRoute>>
+            } else {
+                <p class="compare-status">{"Loading latest results..."}</p>
+            }
+        </div>
+    }
+}
+
+/// Newest git ref of the hardware and the benchmarks that ran at it, built
+/// from the throughput and P99 change grids of the last git refs
+async fn fetch_latest_results(hardware: &str) -> crate::error::Result<(String, Vec<LatestResult>)> {
+    let throughput =
+        api::fetch_change_grid(hardware, GridMetric::Throughput, SPARKLINE_GITREFS).await?;
+    let p99 = api::fetch_change_grid(hardware, GridMetric::P99, SPARKLINE_GITREFS).await?;
+
+    let gitref = throughput.gitrefs.last().cloned().unwrap_or_default();
+    let p99_rows: HashMap<&str, &ChangeGridRow> = p99
+        .benchmarks
+        .iter()
+        .map(|row| (row.params_identifier.as_str(), row))
+        .collect();
+
+    let results = throughput
+        .benchmarks
+        .iter()
+        .filter_map(|row| {
+            let p99_row = p99_rows.get(row.params_identifier.as_str())?;
+            let ran_at_newest = newest(&row.values).is_some() || newest(&p99_row.values).is_some();
+            ran_at_newest.then(|| LatestResult {
+                params_identifier: row.params_identifier.clone(),
+                pretty_name: row.pretty_name.clone(),
+                throughput: newest(&row.values),
+                p99: newest(&p99_row.values),
+                throughput_change: newest(&row.change_percent),
+                p99_change: newest(&p99_row.change_percent),
+                throughput_history: row.values.clone(),
+                p99_history: p99_row.values.clone(),
+            })
+        })
+        .collect();
+    Ok((gitref, results))
+}
+
+/// Cell of the newest git ref
+fn newest(cells: &[Option<f64>]) -> Option<f64> {
+    cells.last().copied().flatten()
+}
+
+/// Sorts the largest regressions first, benchmarks without change last
+fn sort_results(rows: &mut [LatestResult], sort: SortKey) {
+    let key = |row: &LatestResult| match sort {
+        SortKey::Regression => row.regression(),
+        SortKey::P99Change => row.p99_change,
+        SortKey::ThroughputChange => row.throughput_change.map(|change| -change),
+        SortKey::Name => None,
+    };
+    rows.sort_by(|a, b| {
+        let by_change = match (key(a), key(b)) {
+            (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        by_change.then_with(|| a.pretty_name.cmp(&b.pretty_name))
+    });
+}
+
+fn result_row(hardware: &str, gitref: &str, row: &LatestResult) -> Html {
+    let query = SingleViewQuery {
+        hardware: hardware.to_string(),
+        gitref: gitref.to_string(),
+        params_identifier: row.params_identifier.clone(),
+    };
+    html! {
+        <tr>
+            <td title={row.params_identifier.clone()}>
+                <Link<AppRoute, SingleViewQuery> to={AppRoute::Single} query={Some(query)}>
+                    {&row.pretty_name}
+                </Link<AppRoute, SingleViewQuery>>
+            </td>
+            <td>{format_metric(row.throughput, 0)}</td>
+            { change_cell(row.throughput_change, true) }
+            <td>{ sparkline(&row.throughput_history) }</td>
+            <td>{format_metric(row.p99, 2)}</td>
+            { change_cell(row.p99_change, false) }
+            <td>{ sparkline(&row.p99_history) }</td>
+        </tr>
+    }
+}
+
+fn change_cell(change: Option<f64>, higher_is_better: bool) -> Html {
+    let Some(change) = change else {
+        return html! { <td>{"-"}</td> };
+    };
+    let class = match change.partial_cmp(&0.0) {
+        Some(Ordering::Greater) if higher_is_better => Some("change-better"),
+        Some(Ordering::Greater) => Some("change-worse"),
+        Some(Ordering::Less) if higher_is_better => Some("change-worse"),
+        Some(Ordering::Less) => Some("change-better"),
+        _ => None,
+    };
+    html! { <td class={classes!(class)}>{format!("{:+.1}%", change)}</td> }
+}
+
+/// Line through the values that are set, scaled to their range
+fn sparkline(values: &[Option<f64>]) -> Html {
+    let present: Vec<(usize, f64)> = values
+        .iter()
+        .enumerate()
+        .filter_map(|(column, value)| value.map(|value| (column, value)))
+        .collect();
+    if present.len() < 2 {
+        return html! {};
+    }
+
+    let (min, max) = present.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), (_, value)| (min.min(*value), max.max(*value)),
+    );
+    let range = if max > min { max - min } else { 1.0 };
+    let step = SPARKLINE_WIDTH / (values.len() - 1) as f64;
+    let points = present
+        .iter()
+        .map(|(column, value)| {
+            let y = SPARKLINE_HEIGHT - (value - min) / range * (SPARKLINE_HEIGHT - 2.0) - 1.0;
+            format!("{:.1},{:.1}", *column as f64 * step, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    html! {
+        <svg
+            class="sparkline"
+            width={SPARKLINE_WIDTH.to_string()}
+            height={SPARKLINE_HEIGHT.to_string()}
+            viewBox={format!("0 0 {} {}", SPARKLINE_WIDTH, SPARKLINE_HEIGHT)}
+        >
+            <polyline {points} />
+        </svg>
+    }
+}
+
+fn format_metric(value: Option<f64>, precision: usize) -> String {
+    value
+        .map(|value| format!("{:.*}", precision, value))
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -0,0 +1,15 @@
+pub mod change_grid;
+pub mod coverage;
+pub mod hardware_comparison;
+pub mod latest_results;
+pub mod performance_index;
+
+use serde::Serialize;
+
+/// Query selecting a benchmark of a git ref in the single view
+#[derive(Clone, PartialEq, Serialize)]
+pub struct SingleViewQuery {
+    pub hardware: String,
+    pub gitref: String,
+    pub params_identifier: String,
+}
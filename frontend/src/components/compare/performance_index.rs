@@ -0,0 +1,217 @@
+use crate::api;
+use crate::components::chart::dispose_chart;
+use crate::router::AppRoute;
+use charming::{
+    element::{Symbol, Tooltip, Trigger},
+    theme::Theme,
+    Chart, WasmRenderer,
+};
+use gloo::console::log;
+use iggy_bench_dashboard_shared::{commit::CommitIdentity, performance_index::PerformanceIndex};
+use iggy_bench_report::plotting::chart::IggyChart;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+use yew_hooks::use_size;
+use yew_router::prelude::Link;
+
+const CHART_ID: &str = "performance-index-chart";
+
+#[derive(Properties, PartialEq)]
+pub struct PerformanceIndexProps {
+    pub hardware: String,
+}
+
+/// Composite performance index of every commit on the hardware against a chosen baseline
+#[function_component(PerformanceIndexView)]
+pub fn performance_index(props: &PerformanceIndexProps) -> Html {
+    let (is_dark, _) = use_context::<(bool, Callback<()>)>().expect("Theme context not found");
+    let commits = use_state(Vec::<CommitIdentity>::new);
+    let baseline = use_state(|| None::<String>);
+    let trend = use_state(|| None::<Result<Vec<PerformanceIndex>, String>>);
+    let chart_node = use_node_ref();
+    let chart_size = use_size(chart_node.clone());
+
+    {
+        let commits = commits.clone();
+        use_effect_with(props.hardware.clone(), move |hardware| {
+            let hardware = hardware.clone();
+            yew::platform::spawn_local(async move {
+                match api::fetch_commits_for_hardware(&hardware).await {
+                    Ok(list) => commits.set(list),
+                    Err(e) => log!(format!("Error fetching commits: {}", e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    {
+        let trend = trend.clone();
+        use_effect_with(
+            (props.hardware.clone(), (*baseline).clone()),
+            move |(hardware, baseline)| {
+                let hardware = hardware.clone();
+                let baseline = baseline.clone();
+                trend.set(None);
+                yew::platform::spawn_local(async move {
+                    let result = api::fetch_performance_index_trend(&hardware, baseline.as_deref())
+                        .await
+                        .map_err(|e| e.to_string());
+                    trend.set(Some(result));
+                });
+                || ()
+            },
+        );
+    }
+
+    {
+        let indexes = match &*trend {
+            Some(Ok(indexes)) => indexes.clone(),
+            _ => Vec::new(),
+        };
+        use_effect_with(
+            (indexes, is_dark, chart_size),
+            move |(indexes, is_dark, (width, height))| {
+                dispose_chart(CHART_ID);
+                if !indexes.is_empty() {
+                    let renderer = WasmRenderer::new(*width, *height).theme(if *is_dark {
+                        Theme::Dark
+                    } else {
+                        Theme::Default
+                    });
+                    if let Err(e) = renderer.render(CHART_ID, &index_chart(indexes, *is_dark)) {
+                        log!(format!("Error rendering chart: {}", e));
+                    }
+                }
+                || ()
+            },
+        );
+    }
+
+    let on_baseline_change = {
+        let baseline = baseline.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())
+            {
+                let value = select.value();
+                baseline.set((!value.is_empty()).then_some(value));
+            }
+        })
+    };
+
+    let selected_baseline = match &*trend {
+        Some(Ok(indexes)) => indexes.first().map(|index| index.baseline_gitref.clone()),
+        _ => (*baseline).clone(),
+    };
+
+    let content = match &*trend {
+        None => html! { <p class="compare-status">{"Loading performance index..."}</p> },
+        Some(Err(e)) => html! { <p class="compare-status">{format!("Error: {}", e)}</p> },
+        Some(Ok(indexes)) if indexes.is_empty() => html! {
+            <p class="compare-status">{"No commit has benchmarks in common with the baseline."}</p>
+        },
+        Some(Ok(indexes)) => html! {
+            <table class="compare-table">
+                <thead>
+                    <tr>
+                        <th>{"Version"}</th>
+                        <th>{"Index"}</th>
+                        <th>{"Throughput"}</th>
+                        <th>{"P99 latency"}</th>
+                        <th>{"Benchmarks"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for indexes.iter().rev().map(index_row) }
+                </tbody>
+            </table>
+        },
+    };
+
+    html! {
+        <div class="compare-page">
+            <div class="compare-header">
+                <h2>{format!("Performance index on {}", props.hardware)}</h2>
+                <Link<AppRoute> to={AppRoute::Single}>{"Back to dashboard"}</Link<AppRoute>>
+            </div>
+            <p class="compare-summary">
+                {"Weighted geometric mean of throughput and inverse P99 latency over the benchmarks \
+                  each version shares with the baseline, 1.00 being as fast as the baseline."}
+            </p>
+            <div class="compare-baseline">
+                <label>{"Baseline "}
+                    <select onchange={on_baseline_change}>
+                        <option value="" selected={baseline.is_none()}>{"Oldest version"}</option>
+                        { for commits.iter().map(|commit| html! {
+                            <option
+                                value={commit.name.clone()}
+                                selected={baseline.is_some() && selected_baseline.as_ref() == Some(&commit.name)}
+                            >
+                                {commit.name.clone()}
+                            </option>
+                        }) }
+                    </select>
+                </label>
+            </div>
+            <div ref={chart_node} class="compare-chart">
+                <div id={CHART_ID} style="width: 100%; height: 100%;"></div>
+            </div>
+            { content }
+        </div>
+    }
+}
+
+fn index_chart(indexes: &[PerformanceIndex], is_dark: bool) -> Chart {
+    let gitrefs: Vec<String> = indexes.iter().map(|index| index.gitref.clone()).collect();
+    let subtext = format!("Baseline {}", indexes[0].baseline_gitref);
+
+    IggyChart::new("Performance Index", &subtext, is_dark, true)
+        .with_category_x_axis("Version", gitrefs)
+        .with_y_axis("Relative to baseline")
+        .add_series(
+            "Index",
+            indexes.iter().map(|index| index.index).collect(),
+            Symbol::Circle,
+            "#5470c6",
+        )
+        .add_series(
+            "Throughput",
+            indexes.iter().map(|index| index.throughput_index).collect(),
+            Symbol::Triangle,
+            "#91cc75",
+        )
+        .add_series(
+            "P99 Latency",
+            indexes.iter().map(|index| index.latency_index).collect(),
+            Symbol::Diamond,
+            "#fac858",
+        )
+        .inner
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+}
+
+fn index_row(index: &PerformanceIndex) -> Html {
+    html! {
+        <tr>
+            <td>{&index.gitref}</td>
+            { index_cell(index.index) }
+            { index_cell(index.throughput_index) }
+            { index_cell(index.latency_index) }
+            <td>{index.benchmark_count}</td>
+        </tr>
+    }
+}
+
+fn index_cell(value: f64) -> Html {
+    let class = if value > 1.0 {
+        Some("change-better")
+    } else if value < 1.0 {
+        Some("change-worse")
+    } else {
+        None
+    };
+    html! { <td class={classes!(class)}>{format!("{:.3}", value)}</td> }
+}
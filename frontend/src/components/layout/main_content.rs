@@ -1,9 +1,11 @@
 use crate::components::chart::single_chart::SingleChart;
 use crate::components::chart::trend_chart::TrendChart;
 use crate::components::layout::topbar::TopBar;
+use crate::router::AppRoute;
 use crate::state::benchmark::use_benchmark;
 use crate::state::ui::{use_ui, ViewMode};
 use yew::prelude::*;
+use yew_router::prelude::Link;
 
 #[derive(Properties, PartialEq)]
 pub struct MainContentProps {
@@ -31,6 +33,15 @@ pub fn main_content(props: &MainContentProps) -> Html {
                             <div class="chart-title-sub">
                                 { selected_benchmark.subtext() }
                             </div>
+                            <Link<AppRoute>
+                                classes="chart-title-link"
+                                to={AppRoute::HardwareComparison {
+                                    params_identifier: selected_benchmark.params.params_identifier.clone(),
+                                    gitref: props.selected_gitref.clone(),
+                                }}
+                            >
+                                {"Compare across hardware"}
+                            </Link<AppRoute>>
                         </div>
                         <div class="single-view">
                             <SingleChart
@@ -1,13 +1,17 @@
 use super::logo::Logo;
 use crate::components::selectors::benchmark_selector::BenchmarkSelector;
 use crate::components::selectors::gitref_selector::GitrefSelector;
+use crate::components::selectors::hardware_overlay_selector::HardwareOverlaySelector;
 use crate::components::selectors::hardware_selector::HardwareSelector;
 use crate::components::selectors::view_mode_selector::ViewModeSelector;
+use crate::router::AppRoute;
 use crate::state::benchmark::{use_benchmark, BenchmarkAction};
 use crate::state::gitref::use_gitref;
+use crate::state::hardware::use_hardware;
 use crate::state::ui::{use_ui, ViewMode};
 use iggy_bench_report::benchmark_kind::BenchmarkKind;
 use yew::prelude::*;
+use yew_router::prelude::Link;
 
 #[derive(Clone, PartialEq)]
 pub enum BenchmarkTab {
@@ -26,6 +30,7 @@ pub fn sidebar(props: &SidebarProps) -> Html {
     let gitref_ctx = use_gitref();
     let ui_state = use_ui();
     let benchmark_ctx = use_benchmark();
+    let hardware_ctx = use_hardware();
     let is_trend_view = matches!(ui_state.view_mode, ViewMode::GitrefTrend);
 
     let active_tab = match benchmark_ctx.state.selected_kind {
@@ -116,9 +121,11 @@ pub fn sidebar(props: &SidebarProps) -> Html {
             <HardwareSelector />
             <ViewModeSelector />
 
-            if !is_trend_view {
+            if is_trend_view {
+                <HardwareOverlaySelector />
+            } else {
                 <GitrefSelector
-                    gitrefs={gitref_ctx.state.gitrefs.clone()}
+                    commits={gitref_ctx.state.commits.clone()}
                     selected_gitref={gitref_ctx.state.selected_gitref.clone().unwrap_or_default()}
                     on_gitref_select={props.on_gitref_select.clone()}
                 />
@@ -190,6 +197,24 @@ pub fn sidebar(props: &SidebarProps) -> Html {
                     <BenchmarkSelector kind={get_default_kind_for_tab(&BenchmarkTab::EndToEnd)} />
                 </div>
             </div>
+
+            if let Some(hardware) = hardware_ctx.state.selected_hardware.clone() {
+                <Link<AppRoute> classes="sidebar-link" to={AppRoute::LatestResults { hardware: hardware.clone() }}>
+                    {"Latest results"}
+                </Link<AppRoute>>
+                <Link<AppRoute> classes="sidebar-link" to={AppRoute::PerformanceIndex { hardware: hardware.clone() }}>
+                    {"Performance index"}
+                </Link<AppRoute>>
+                <Link<AppRoute> classes="sidebar-link" to={AppRoute::Coverage { hardware: hardware.clone() }}>
+                    {"Benchmark coverage"}
+                </Link<AppRoute>>
+                <Link<AppRoute> classes="sidebar-link" to={AppRoute::ChangeGrid { hardware }}>
+                    {"Changes across benchmarks"}
+                </Link<AppRoute>>
+            }
+            <Link<AppRoute> classes="sidebar-link" to={AppRoute::PullRequests}>
+                {"Pull request benchmarks"}
+            </Link<AppRoute>>
         </div>
     }
 }
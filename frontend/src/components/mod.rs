@@ -1,5 +1,8 @@
 pub mod app_content;
+pub mod candidates;
+pub mod changes;
 pub mod chart;
+pub mod compare;
 pub mod footer;
 pub mod layout;
 pub mod selectors;
@@ -1,10 +1,11 @@
+use iggy_bench_dashboard_shared::commit::CommitIdentity;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct GitrefSelectorProps {
-    pub gitrefs: Vec<String>,
+    pub commits: Vec<CommitIdentity>,
     pub selected_gitref: String,
     pub on_gitref_select: Callback<String>,
 }
@@ -29,13 +30,17 @@ pub fn gitref_selector(props: &GitrefSelectorProps) -> Html {
             <h3>{"Version"}</h3>
             <select {onchange} value={props.selected_gitref.clone()}>
                 {
-                    props.gitrefs.iter().map(|gitref| {
+                    props.commits.iter().map(|commit| {
                         html! {
                             <option
-                                value={gitref.clone()}
-                                selected={gitref == &props.selected_gitref}
+                                value={commit.name.clone()}
+                                title={commit.aliases.join(", ")}
+                                selected={commit.name == props.selected_gitref}
                             >
-                                {gitref}
+                                {commit.name.clone()}
+                                if commit.off_mainline {
+                                    {" (off mainline)"}
+                                }
                             </option>
                         }
                     }).collect::<Html>()
@@ -0,0 +1,44 @@
+use crate::state::hardware::{use_hardware, HardwareAction};
+use yew::prelude::*;
+
+/// Other hardware to draw on the trend chart next to the selected one
+#[function_component(HardwareOverlaySelector)]
+pub fn hardware_overlay_selector() -> Html {
+    let hardware_ctx = use_hardware();
+    let state = &hardware_ctx.state;
+    let others: Vec<_> = state
+        .hardware_list
+        .iter()
+        .filter(|entry| state.selected_hardware.as_ref() != Some(&entry.identifier))
+        .collect();
+
+    if others.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="hardware-overlay-select">
+            <h3>{"Compare with"}</h3>
+            { for others.into_iter().map(|entry| {
+                let identifier = entry.identifier.clone();
+                let onchange = {
+                    let dispatch = hardware_ctx.dispatch.clone();
+                    let identifier = identifier.clone();
+                    Callback::from(move |_: Event| {
+                        dispatch.emit(HardwareAction::ToggleOverlayHardware(identifier.clone()));
+                    })
+                };
+                html! {
+                    <label title={entry.description.clone()}>
+                        <input
+                            type="checkbox"
+                            checked={state.overlay_hardware.contains(&identifier)}
+                            {onchange}
+                        />
+                        {state.display_name(&identifier)}
+                    </label>
+                }
+            }) }
+        </div>
+    }
+}
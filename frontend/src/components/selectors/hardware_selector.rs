@@ -22,13 +22,15 @@ pub fn hardware_selector(_props: &HardwareSelectorProps) -> Html {
         <div class="hardware-select">
             <h3>{"Hardware"}</h3>
             <select onchange={onchange}>
-                {hardware_ctx.state.hardware_list.iter().map(|hardware| {
+                {hardware_ctx.state.hardware_list.iter().map(|entry| {
+                    let name = entry.display_name.as_deref().unwrap_or(&entry.identifier);
                     html! {
                         <option
-                            value={hardware.identifier.clone().unwrap_or_else(|| "Unknown".to_string())}
-                            selected={hardware_ctx.state.selected_hardware == Some(hardware.identifier.clone().unwrap_or_else(|| "Unknown".to_string()))}
+                            value={entry.identifier.clone()}
+                            title={entry.description.clone()}
+                            selected={hardware_ctx.state.selected_hardware.as_ref() == Some(&entry.identifier)}
                         >
-                            {format!("{} @ {}", hardware.identifier.clone().unwrap_or_else(|| "Unknown".to_string()), &hardware.cpu_name)}
+                            {format!("{} @ {}", name, &entry.hardware.cpu_name)}
                         </option>
                     }
                 }).collect::<Html>()}
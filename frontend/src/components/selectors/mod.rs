@@ -1,6 +1,7 @@
 pub mod benchmark_kind_selector;
 pub mod benchmark_selector;
 pub mod gitref_selector;
+pub mod hardware_overlay_selector;
 pub mod hardware_selector;
 pub mod measurement_type_selector;
 pub mod view_mode_selector;
@@ -6,14 +6,25 @@ mod router;
 mod state;
 
 use crate::{
-    components::{app_content::AppContent, footer::Footer},
+    components::{
+        app_content::AppContent,
+        candidates::{
+            candidate_comparison::CandidateComparisonView, candidate_list::CandidateList,
+        },
+        compare::change_grid::ChangeGridView,
+        compare::coverage::CoverageView,
+        compare::hardware_comparison::HardwareComparisonView,
+        compare::latest_results::{LatestResultsRedirect, LatestResultsView},
+        compare::performance_index::PerformanceIndexView,
+        footer::Footer,
+    },
     state::hardware::HardwareProvider,
 };
 use components::theme::theme_provider::ThemeProvider;
-use router::AppRoute;
+use router::{AppRoute, HomeQuery};
 use state::{benchmark::BenchmarkProvider, gitref::GitrefProvider, ui::UiProvider};
 use yew::prelude::*;
-use yew_router::{BrowserRouter, Switch};
+use yew_router::{hooks::use_location, BrowserRouter, Switch};
 
 #[function_component(App)]
 pub fn app() -> Html {
@@ -24,22 +35,106 @@ pub fn app() -> Html {
     }
 }
 
+/// Latest results of the selected hardware, or the single view for links into it
+#[function_component(Home)]
+fn home() -> Html {
+    let query = use_location()
+        .and_then(|location| location.query::<HomeQuery>().ok())
+        .unwrap_or_default();
+    if query.is_single_view() {
+        return single_view();
+    }
+
+    html! {
+        <ThemeProvider>
+            <div class="app-container">
+                <LatestResultsRedirect hardware={query.hardware} />
+                <Footer />
+            </div>
+        </ThemeProvider>
+    }
+}
+
+fn single_view() -> Html {
+    html! {
+        <ThemeProvider>
+            <UiProvider>
+                <div class="app-container">
+                    <HardwareProvider>
+                        <GitrefProvider>
+                            <BenchmarkProvider>
+                                <AppContent />
+                            </BenchmarkProvider>
+                        </GitrefProvider>
+                    </HardwareProvider>
+                    <Footer />
+                </div>
+            </UiProvider>
+        </ThemeProvider>
+    }
+}
+
 fn switch(routes: AppRoute) -> Html {
     match routes {
-        AppRoute::Single | AppRoute::Home => html! {
+        AppRoute::Home => html! { <Home /> },
+        AppRoute::Single => single_view(),
+        AppRoute::PullRequests => html! {
+            <ThemeProvider>
+                <div class="app-container">
+                    <CandidateList />
+                    <Footer />
+                </div>
+            </ThemeProvider>
+        },
+        AppRoute::PullRequest { pr_number } => html! {
+            <ThemeProvider>
+                <div class="app-container">
+                    <CandidateComparisonView {pr_number} />
+                    <Footer />
+                </div>
+            </ThemeProvider>
+        },
+        AppRoute::HardwareComparison {
+            params_identifier,
+            gitref,
+        } => html! {
+            <ThemeProvider>
+                <div class="app-container">
+                    <HardwareComparisonView {params_identifier} {gitref} />
+                    <Footer />
+                </div>
+            </ThemeProvider>
+        },
+        AppRoute::LatestResults { hardware } => html! {
+            <ThemeProvider>
+                <div class="app-container">
+                    <LatestResultsView {hardware} />
+                    <Footer />
+                </div>
+            </ThemeProvider>
+        },
+        AppRoute::PerformanceIndex { hardware } => html! {
+            <ThemeProvider>
+                <div class="app-container">
+                    <PerformanceIndexView {hardware} />
+                    <Footer />
+                </div>
+            </ThemeProvider>
+        },
+        AppRoute::Coverage { hardware } => html! {
+            <ThemeProvider>
+                <div class="app-container">
+                    <CoverageView {hardware} />
+                    <Footer />
+                </div>
+            </ThemeProvider>
+        },
+        AppRoute::ChangeGrid { hardware } => html! {
             <ThemeProvider>
-                <UiProvider>
-                    <div class="app-container">
-                        <HardwareProvider>
-                            <GitrefProvider>
-                                <BenchmarkProvider>
-                                    <AppContent />
-                                </BenchmarkProvider>
-                            </GitrefProvider>
-                        </HardwareProvider>
-                        <Footer />
-                    </div>
-                </UiProvider>
+                <div class="app-container">
+                    <ChangeGridView {hardware} />
+                    <Footer />
+                </div>
             </ThemeProvider>
         },
         AppRoute::NotFound => html! { "404 Not Found" },
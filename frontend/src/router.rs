@@ -1,12 +1,55 @@
+use serde::{Deserialize, Serialize};
 use yew_router::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Routable)]
 pub enum AppRoute {
+    /// Latest results of the hardware in the query, or of the first hardware.
+    /// Links into the single view are served here too, see [`HomeQuery`].
     #[at("/")]
     Home,
     #[at("/single")]
     Single,
+    #[at("/pulls")]
+    PullRequests,
+    #[at("/pulls/:pr_number")]
+    PullRequest { pr_number: u64 },
+    #[at("/compare/hardware/:params_identifier/:gitref")]
+    HardwareComparison {
+        params_identifier: String,
+        gitref: String,
+    },
+    #[at("/latest/:hardware")]
+    LatestResults { hardware: String },
+    #[at("/index/:hardware")]
+    PerformanceIndex { hardware: String },
+    #[at("/coverage/:hardware")]
+    Coverage { hardware: String },
+    #[at("/change-grid/:hardware")]
+    ChangeGrid { hardware: String },
     #[not_found]
     #[at("/404")]
     NotFound,
 }
+
+/// Query of [`AppRoute::PullRequest`], telling apart pull requests of several
+/// sources that have the same number
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PullRequestQuery {
+    pub source: Option<String>,
+}
+
+/// Query of [`AppRoute::Home`]. Links to a git ref or benchmark, e.g. from pull
+/// request comments, open the single view, any other the latest results.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct HomeQuery {
+    pub hardware: Option<String>,
+    pub gitref: Option<String>,
+    pub measurement: Option<String>,
+    pub params_identifier: Option<String>,
+}
+
+impl HomeQuery {
+    pub fn is_single_view(&self) -> bool {
+        self.gitref.is_some() || self.measurement.is_some() || self.params_identifier.is_some()
+    }
+}
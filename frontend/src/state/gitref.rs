@@ -1,15 +1,17 @@
 use gloo::console::log;
+use iggy_bench_dashboard_shared::commit::CommitIdentity;
 use std::rc::Rc;
 use yew::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct GitrefState {
-    pub gitrefs: Vec<String>,
+    /// One entry per commit, selected by name
+    pub commits: Vec<CommitIdentity>,
     pub selected_gitref: Option<String>,
 }
 
 pub enum GitrefAction {
-    SetGitrefs(Vec<String>),
+    SetCommits(Vec<CommitIdentity>),
     SetSelectedGitref(Option<String>),
 }
 
@@ -30,17 +32,18 @@ impl Reducible for GitrefState {
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
         let next_state = match action {
-            GitrefAction::SetGitrefs(gitrefs) => {
-                log!("Available gitrefs updated:", format!("{:?}", &gitrefs));
+            GitrefAction::SetCommits(commits) => {
+                let names: Vec<&str> = commits.iter().map(|commit| commit.name.as_str()).collect();
+                log!("Available gitrefs updated:", format!("{:?}", names));
                 GitrefState {
-                    gitrefs,
+                    commits,
                     selected_gitref: self.selected_gitref.clone(),
                 }
             }
             GitrefAction::SetSelectedGitref(gitref) => {
                 log!("Gitref state updated to:", format!("{:?}", &gitref));
                 GitrefState {
-                    gitrefs: self.gitrefs.clone(),
+                    commits: self.commits.clone(),
                     selected_gitref: gitref,
                 }
             }
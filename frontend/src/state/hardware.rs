@@ -1,16 +1,19 @@
-use iggy_bench_report::hardware::BenchmarkHardware;
+use iggy_bench_dashboard_shared::hardware::HardwareEntry;
 use std::rc::Rc;
 use yew::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct HardwareState {
-    pub hardware_list: Vec<BenchmarkHardware>,
+    pub hardware_list: Vec<HardwareEntry>,
     pub selected_hardware: Option<String>,
+    /// Other hardware whose trends are drawn over the selected hardware's
+    pub overlay_hardware: Vec<String>,
 }
 
 pub enum HardwareAction {
-    SetHardwareList(Vec<BenchmarkHardware>),
+    SetHardwareList(Vec<HardwareEntry>),
     SelectHardware(Option<String>),
+    ToggleOverlayHardware(String),
 }
 
 #[derive(Clone, PartialEq)]
@@ -33,11 +36,35 @@ impl Reducible for HardwareState {
             HardwareAction::SetHardwareList(hardware_list) => HardwareState {
                 hardware_list,
                 selected_hardware: self.selected_hardware.clone(),
+                overlay_hardware: self.overlay_hardware.clone(),
             },
             HardwareAction::SelectHardware(hardware) => HardwareState {
                 hardware_list: self.hardware_list.clone(),
+                overlay_hardware: self
+                    .overlay_hardware
+                    .iter()
+                    .filter(|overlay| hardware.as_ref() != Some(*overlay))
+                    .cloned()
+                    .collect(),
                 selected_hardware: hardware,
             },
+            HardwareAction::ToggleOverlayHardware(hardware) => {
+                let mut overlay_hardware = self.overlay_hardware.clone();
+                match overlay_hardware
+                    .iter()
+                    .position(|overlay| *overlay == hardware)
+                {
+                    Some(position) => {
+                        overlay_hardware.remove(position);
+                    }
+                    None => overlay_hardware.push(hardware),
+                }
+                HardwareState {
+                    hardware_list: self.hardware_list.clone(),
+                    selected_hardware: self.selected_hardware.clone(),
+                    overlay_hardware,
+                }
+            }
         };
 
         next_state.into()
@@ -70,3 +97,14 @@ pub fn hardware_provider(props: &HardwareProviderProps) -> Html {
 pub fn use_hardware() -> HardwareContext {
     use_context::<HardwareContext>().expect("Hardware context not found")
 }
+
+impl HardwareState {
+    /// Display name of the hardware, its identifier if it has none
+    pub fn display_name(&self, identifier: &str) -> String {
+        self.hardware_list
+            .iter()
+            .find(|entry| entry.identifier == identifier)
+            .and_then(|entry| entry.display_name.clone())
+            .unwrap_or_else(|| identifier.to_string())
+    }
+}
@@ -1,45 +1,89 @@
-use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
 use serde::Deserialize;
 use std::path::PathBuf;
 
-#[derive(Debug, Subcommand, Deserialize)]
+#[derive(Debug, Clone, Subcommand, Deserialize)]
 pub enum PollGithub {
     PollGithub(PollGithubArgs),
 }
 
-#[derive(Debug, Parser, Deserialize)]
+#[derive(Debug, Clone, Parser, Deserialize)]
 pub struct PollGithubArgs {
-    /// How often to poll GitHub for new artifacts, in seconds
-    #[arg(short, long, default_value = "60")]
-    pub interval_seconds: u64,
+    /// How often to poll GitHub for new artifacts, in seconds [default: 60]
+    #[arg(short, long)]
+    pub interval_seconds: Option<u64>,
 
-    /// Branch to filter artifacts by
-    #[arg(short, long, default_value = "master")]
-    pub branch: String,
+    /// Branch to filter artifacts by [default: master]
+    #[arg(short, long)]
+    pub branch: Option<String>,
+
+    /// Import past workflow runs missing locally before polling starts
+    #[arg(long)]
+    pub backfill: bool,
+
+    /// Only backfill runs created on or after this date (YYYY-MM-DD), implies --backfill
+    #[arg(long)]
+    pub backfill_since: Option<NaiveDate>,
+
+    /// Backfill at most this many of the most recent runs per branch, implies --backfill
+    #[arg(long)]
+    pub backfill_max_runs: Option<usize>,
 }
 
-#[derive(Debug, Deserialize, Parser)]
+/// Command line arguments. Every setting is optional so that values missing
+/// here can be taken from the environment or the `--config` file, see
+/// [`crate::config::ServerConfig::load`].
+#[derive(Debug, Clone, Deserialize, Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct IggyBenchDashboardServerArgs {
-    /// Server host address
-    #[arg(long, default_value = "127.0.0.1")]
-    pub host: String,
+    /// Path to a TOML configuration file
+    #[arg(long, env = "IGGY_DASHBOARD_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Server host address [default: 127.0.0.1]
+    #[arg(long, env = "IGGY_DASHBOARD_HOST")]
+    pub host: Option<String>,
+
+    /// Server port [default: 8061]
+    #[arg(long, env = "IGGY_DASHBOARD_PORT")]
+    pub port: Option<u16>,
+
+    /// Directory containing performance results [default: ./performance_results]
+    #[arg(long, env = "IGGY_DASHBOARD_RESULTS_DIR")]
+    pub results_dir: Option<PathBuf>,
+
+    /// Log level (trace, debug, info, warn, error) [default: info]
+    #[arg(long, env = "IGGY_DASHBOARD_LOG_LEVEL")]
+    pub log_level: Option<String>,
 
-    /// Server port
-    #[arg(long, default_value_t = 8061)]
-    pub port: u16,
+    /// Allowed CORS origins (comma-separated) [default: *]
+    #[arg(long, env = "IGGY_DASHBOARD_CORS_ORIGINS")]
+    pub cors_origins: Option<String>,
 
-    /// Directory containing performance results
-    #[arg(long, default_value = "./performance_results")]
-    pub results_dir: PathBuf,
+    /// Seconds to wait for requests, the GitHub poller and the file watcher on shutdown [default: 30]
+    #[arg(long, env = "IGGY_DASHBOARD_SHUTDOWN_TIMEOUT_SECONDS")]
+    pub shutdown_timeout_seconds: Option<u64>,
 
-    /// Log level (trace, debug, info, warn, error)
-    #[arg(long, default_value = "info")]
-    pub log_level: String,
+    /// PEM certificate chain, enables HTTPS together with --tls-key
+    #[arg(long, env = "IGGY_DASHBOARD_TLS_CERT", requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
 
-    /// Allowed CORS origins (comma-separated)
-    #[arg(long, default_value = "*")]
-    pub cors_origins: String,
+    /// PEM private key, enables HTTPS together with --tls-cert
+    #[arg(long, env = "IGGY_DASHBOARD_TLS_KEY", requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+
+    /// Port serving plain HTTP redirects to HTTPS
+    #[arg(long, env = "IGGY_DASHBOARD_TLS_REDIRECT_PORT")]
+    pub tls_redirect_port: Option<u16>,
+
+    /// Local clone of iggy, orders git refs by the first-parent history of the mainline
+    #[arg(long, env = "IGGY_DASHBOARD_GIT_REPO")]
+    pub git_repo: Option<PathBuf>,
+
+    /// Mainline branch of the --git-repo clone [default: master]
+    #[arg(long, env = "IGGY_DASHBOARD_GIT_MAINLINE")]
+    pub git_mainline: Option<String>,
 
     /// Poll GitHub for new artifacts
     #[command(subcommand)]
@@ -51,46 +95,9 @@ impl IggyBenchDashboardServerArgs {
         Self::parse_from(std::env::args())
     }
 
-    pub fn validate(&self) {
-        let mut cmd = IggyBenchDashboardServerArgs::command();
-        if !self.results_dir.exists() {
-            cmd.error(
-                ErrorKind::InvalidValue,
-                format!(
-                    "Results directory does not exist: {}",
-                    self.results_dir.display()
-                ),
-            )
-            .exit();
-        }
-        if !self.results_dir.is_dir() {
-            cmd.error(
-                ErrorKind::InvalidValue,
-                format!(
-                    "Results path is not a directory: {}",
-                    self.results_dir.display()
-                ),
-            )
-            .exit();
-        }
-
-        if self.github.is_some() && std::env::var("GITHUB_TOKEN").is_err() {
-            cmd.error(
-                ErrorKind::InvalidValue,
-                "GITHUB_TOKEN env variable not set, but GitHub polling enabled",
-            )
-            .exit();
-        }
-    }
-
-    pub fn server_addr(&self) -> String {
-        format!("{}:{}", self.host, self.port)
-    }
-
-    pub fn cors_origins_list(&self) -> Vec<String> {
-        self.cors_origins
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect()
+    pub fn poll_github_args(&self) -> Option<&PollGithubArgs> {
+        self.github
+            .as_ref()
+            .map(|PollGithub::PollGithub(args)| args)
     }
 }
@@ -0,0 +1,350 @@
+use super::{BenchmarkCache, Result};
+use crate::error::IggyBenchDashboardServerError;
+use iggy_bench_dashboard_shared::{
+    candidate::{
+        BenchmarkComparison, CandidateComparison, HardwareComparison, PullRequestCandidate,
+    },
+    BenchmarkReportLight,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+use url::form_urlencoded::byte_serialize;
+use uuid::Uuid;
+
+/// Directory inside the results directory holding pull request benchmarks
+pub const CANDIDATES_DIR: &str = "candidates";
+
+/// Metadata file of a candidate directory
+pub const CANDIDATE_FILE: &str = "candidate.json";
+
+/// Benchmarks of a pull request run
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub info: PullRequestCandidate,
+    pub benchmarks: Vec<Uuid>,
+}
+
+/// Directory of the candidate of a pull request of `source`. Pull request
+/// numbers are only unique within a repository, so the name holds both.
+pub fn candidate_dir(results_dir: &Path, source: &str, pr_number: u64) -> PathBuf {
+    let source: String = byte_serialize(source.as_bytes()).collect();
+    results_dir
+        .join(CANDIDATES_DIR)
+        .join(format!("{}-pr-{}", source, pr_number))
+}
+
+impl BenchmarkCache {
+    /// Loads `candidates/*-pr-*/` directories, each holding a `candidate.json`
+    /// next to the benchmark directories of the pull request run
+    pub(super) fn load_candidates(&self) -> Result<()> {
+        let candidates_dir = self.results_dir.join(CANDIDATES_DIR);
+        if !candidates_dir.exists() {
+            return Ok(());
+        }
+
+        for entry in std::fs::read_dir(&candidates_dir)?.filter_map(|r| r.ok()) {
+            let path = entry.path();
+            // Hidden directories are candidates still being written
+            if !path.is_dir() || entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            if let Err(e) = self.load_candidate(&path) {
+                error!("Failed to load candidate {}: {}", path.display(), e);
+            }
+        }
+
+        info!("Loaded {} pull request candidates", self.candidates.len());
+        Ok(())
+    }
+
+    fn load_candidate(&self, dir: &Path) -> Result<()> {
+        let data = std::fs::read_to_string(dir.join(CANDIDATE_FILE))?;
+        let info: PullRequestCandidate = serde_json::from_str(&data)
+            .map_err(|e| IggyBenchDashboardServerError::InvalidJson(e.to_string()))?;
+
+        let mut benchmarks = Vec::new();
+        for entry in std::fs::read_dir(dir)?.filter_map(|r| r.ok()) {
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let path = entry.path().join("report.json");
+            match self.load_light_report(&path) {
+                Ok(report) => {
+                    benchmarks.push(report.uuid);
+                    self.candidate_benchmarks
+                        .insert(report.uuid, (report, path));
+                }
+                Err(e) => error!(
+                    "Failed to load light report for {}: {}",
+                    entry.path().display(),
+                    e
+                ),
+            }
+        }
+
+        self.candidates.insert(
+            (info.source.clone(), info.pr_number),
+            Candidate { info, benchmarks },
+        );
+        Ok(())
+    }
+
+    /// Replaces the cached candidate of a pull request with its directory on disk,
+    /// without waiting for the results directory watcher
+    pub fn reload_candidate(&self, source: &str, pr_number: u64) -> Result<()> {
+        self.remove_candidate(source, pr_number);
+        self.load_candidate(&candidate_dir(&self.results_dir, source, pr_number))
+    }
+
+    /// Candidates, most recently ingested first
+    pub fn get_candidates(&self) -> Vec<PullRequestCandidate> {
+        let mut candidates: Vec<PullRequestCandidate> = self
+            .candidates
+            .iter()
+            .map(|entry| entry.value().info.clone())
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.ingested_at
+                .cmp(&a.ingested_at)
+                .then_with(|| b.pr_number.cmp(&a.pr_number))
+                .then_with(|| a.source.cmp(&b.source))
+        });
+        candidates
+    }
+
+    /// Source of the candidate of pull request `pr_number`. `source` is only
+    /// needed when pull requests of several sources have that number.
+    pub fn candidate_source(&self, pr_number: u64, source: Option<&str>) -> Result<String> {
+        let mut sources: Vec<String> = self
+            .candidates
+            .iter()
+            .filter(|entry| {
+                let (candidate_source, candidate_pr_number) = entry.key();
+                *candidate_pr_number == pr_number
+                    && source.is_none_or(|source| candidate_source == source)
+            })
+            .map(|entry| entry.key().0.clone())
+            .collect();
+        if sources.len() > 1 {
+            return Err(IggyBenchDashboardServerError::Conflict(format!(
+                "Pull requests #{} of several sources have candidates, pick one with the source parameter",
+                pr_number
+            )));
+        }
+        sources.pop().ok_or_else(|| {
+            IggyBenchDashboardServerError::NotFound(format!(
+                "No candidate for pull request #{}",
+                pr_number
+            ))
+        })
+    }
+
+    pub fn remove_candidate(&self, source: &str, pr_number: u64) {
+        if let Some((_, candidate)) = self.candidates.remove(&(source.to_owned(), pr_number)) {
+            for uuid in candidate.benchmarks {
+                self.candidate_benchmarks.remove(&uuid);
+            }
+        }
+    }
+
+    /// Compares every benchmark of a pull request run with the same benchmark
+    /// on the closest mainline git ref of the same hardware
+    pub fn compare_candidate(&self, source: &str, pr_number: u64) -> Option<CandidateComparison> {
+        let candidate = self
+            .candidates
+            .get(&(source.to_owned(), pr_number))?
+            .value()
+            .clone();
+
+        let mut by_hardware: BTreeMap<String, Vec<BenchmarkReportLight>> = BTreeMap::new();
+        for uuid in &candidate.benchmarks {
+            if let Some(entry) = self.candidate_benchmarks.get(uuid) {
+                let report = entry.value().0.clone();
+                let hardware = report.hardware.identifier.as_deref().unwrap_or_default();
+                let hardware = self.canonical_hardware(hardware);
+                by_hardware.entry(hardware).or_default().push(report);
+            }
+        }
+
+        let hardware = by_hardware
+            .into_iter()
+            .map(|(hardware, mut reports)| {
+                reports.sort_by(|a, b| a.params.pretty_name.cmp(&b.params.pretty_name));
+                let baseline_gitref = self.closest_mainline_gitref(&candidate.info, &hardware);
+                let baseline = baseline_gitref
+                    .as_deref()
+                    .map(|gitref| self.get_benchmarks_for_hardware_and_gitref(&hardware, gitref))
+                    .unwrap_or_default();
+
+                let benchmarks = reports
+                    .into_iter()
+                    .map(|report| {
+                        let baseline = baseline
+                            .iter()
+                            .find(|baseline| {
+                                baseline.params.params_identifier == report.params.params_identifier
+                            })
+                            .cloned();
+                        BenchmarkComparison::new(report, baseline)
+                    })
+                    .collect();
+
+                HardwareComparison {
+                    hardware,
+                    baseline_gitref,
+                    benchmarks,
+                }
+            })
+            .collect();
+
+        Some(CandidateComparison {
+            candidate: candidate.info,
+            hardware,
+        })
+    }
+
+    /// The base commit itself if it was benchmarked on `hardware`, otherwise the
+    /// newest mainline git ref preceding the base commit, otherwise the newest
+    /// mainline git ref. With a git clone "preceding" means in the history of
+    /// the base commit and "newest" follows the commit order, so rebased or
+    /// back-dated commits are placed right. Without one both go by date.
+    fn closest_mainline_gitref(
+        &self,
+        candidate: &PullRequestCandidate,
+        hardware: &str,
+    ) -> Option<String> {
+        let gitrefs: HashSet<String> = self.get_gitrefs_for_hardware(hardware);
+        let base = self
+            .resolve_gitref(&candidate.base_sha)
+            .or_else(|| self.resolve_gitref(&candidate.base_gitref));
+        if let Some(base) = base.filter(|base| gitrefs.contains(base)) {
+            return Some(base);
+        }
+
+        if let Some(git) = &self.git {
+            if let Some(base_sha) = git.resolve(&candidate.base_sha) {
+                let preceding = gitrefs.iter().filter(|gitref| {
+                    self.get_commit(gitref)
+                        .and_then(|commit| commit.sha)
+                        .or_else(|| git.resolve(gitref))
+                        .is_some_and(|sha| git.is_ancestor(&sha, &base_sha))
+                });
+                return self
+                    .newest_by_rank(preceding)
+                    .or_else(|| self.newest_by_rank(gitrefs.iter()));
+            }
+        }
+
+        let base_date = candidate.base_committed_at.as_deref().map(Self::parse_date);
+        let mut dated: Vec<_> = gitrefs
+            .into_iter()
+            .map(|gitref| {
+                let date = self
+                    .gitref_date(hardware, &gitref)
+                    .as_deref()
+                    .map(Self::parse_date);
+                (date, gitref)
+            })
+            .collect();
+        dated.sort();
+
+        dated
+            .iter()
+            .rev()
+            .find(|(date, _)| match (date, base_date) {
+                (Some(date), Some(base_date)) => *date <= base_date,
+                _ => false,
+            })
+            .or_else(|| dated.last())
+            .map(|(_, gitref)| gitref.clone())
+    }
+
+    /// Git ref latest in the commit order, commits without rank coming first
+    fn newest_by_rank<'a>(&self, gitrefs: impl Iterator<Item = &'a String>) -> Option<String> {
+        gitrefs
+            .max_by_key(|gitref| {
+                let rank = self.commit_rank(gitref);
+                (rank.is_some(), rank, (*gitref).clone())
+            })
+            .cloned()
+    }
+
+    fn gitref_date(&self, hardware: &str, gitref: &str) -> Option<String> {
+        self.get_benchmarks_for_hardware_and_gitref(hardware, gitref)
+            .into_iter()
+            .find_map(|report| report.params.gitref_date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::LEGACY_SOURCE;
+
+    fn candidate(source: &str, pr_number: u64) -> Candidate {
+        Candidate {
+            info: PullRequestCandidate {
+                pr_number,
+                title: None,
+                source: source.to_owned(),
+                run_id: 1,
+                head_sha: "head".to_owned(),
+                head_ref: "feature".to_owned(),
+                base_sha: "base".to_owned(),
+                base_ref: "master".to_owned(),
+                base_gitref: "base".to_owned(),
+                base_committed_at: None,
+                ingested_at: "2025-01-01T00:00:00Z".to_owned(),
+            },
+            benchmarks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn candidate_dirs_of_sources_differ() {
+        let results_dir = Path::new("/results");
+        let apache = candidate_dir(results_dir, "apache/iggy/performance.yml", 7);
+        let fork = candidate_dir(results_dir, "fork/iggy/performance.yml", 7);
+
+        assert_ne!(apache, fork);
+        assert_eq!(apache.parent().unwrap(), results_dir.join(CANDIDATES_DIR));
+        assert!(!apache.file_name().unwrap().to_string_lossy().contains('/'));
+    }
+
+    #[tokio::test]
+    async fn candidate_source_needs_the_source_only_when_ambiguous() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let cache = BenchmarkCache::new(results_dir.path().to_path_buf(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+        for (source, pr_number) in [
+            ("a/iggy/performance.yml", 7),
+            ("b/iggy/performance.yml", 7),
+            ("a/iggy/performance.yml", 8),
+        ] {
+            cache
+                .candidates
+                .insert((source.to_owned(), pr_number), candidate(source, pr_number));
+        }
+
+        assert_eq!(
+            cache.candidate_source(8, None).unwrap(),
+            "a/iggy/performance.yml"
+        );
+        assert!(matches!(
+            cache.candidate_source(7, None),
+            Err(IggyBenchDashboardServerError::Conflict(_))
+        ));
+        assert_eq!(
+            cache
+                .candidate_source(7, Some("b/iggy/performance.yml"))
+                .unwrap(),
+            "b/iggy/performance.yml"
+        );
+        assert!(matches!(
+            cache.candidate_source(9, None),
+            Err(IggyBenchDashboardServerError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+use super::BenchmarkCache;
+use iggy_bench_dashboard_shared::change_grid::{ChangeGrid, ChangeGridRow, GridMetric};
+use std::collections::{BTreeMap, HashMap};
+
+/// Pretty name and metric value of every benchmark of one commit, by params identifier
+type CommitValues = BTreeMap<String, (String, Option<f64>)>;
+
+impl BenchmarkCache {
+    /// Change of `metric` of every benchmark at the last `gitref_count` commits
+    /// benchmarked on `hardware`, each against the previous commit it ran at
+    pub fn get_change_grid(
+        &self,
+        hardware: &str,
+        metric: GridMetric,
+        gitref_count: usize,
+    ) -> ChangeGrid {
+        let gitrefs = self.gitrefs_oldest_first(hardware);
+        let first_shown = gitrefs.len().saturating_sub(gitref_count);
+        let values: Vec<CommitValues> = gitrefs
+            .iter()
+            .map(|gitref| {
+                self.latest_by_params(hardware, gitref)
+                    .into_iter()
+                    .map(|(params_identifier, report)| {
+                        let value = metric.value(&report);
+                        (params_identifier, (report.params.pretty_name, value))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        ChangeGrid {
+            hardware: self.canonical_hardware(hardware),
+            metric,
+            gitrefs: gitrefs[first_shown..].to_vec(),
+            benchmarks: change_rows(&values, first_shown),
+        }
+    }
+}
+
+/// Rows of the commits from `first_shown` on, given the values of every commit
+/// oldest first. Earlier commits are only compared against. Non-finite values
+/// count as not run.
+fn change_rows(values: &[CommitValues], first_shown: usize) -> Vec<ChangeGridRow> {
+    let shown = values.len().saturating_sub(first_shown);
+    let mut previous: HashMap<&str, f64> = HashMap::new();
+    let mut rows: BTreeMap<&str, ChangeGridRow> = BTreeMap::new();
+    for (position, commit) in values.iter().enumerate() {
+        for (params_identifier, (pretty_name, value)) in commit {
+            let value = value.filter(|value| value.is_finite());
+            let before = previous.get(params_identifier.as_str()).copied();
+            if let Some(value) = value {
+                previous.insert(params_identifier, value);
+            }
+            let Some(column) = position.checked_sub(first_shown) else {
+                continue;
+            };
+
+            let row = rows
+                .entry(params_identifier)
+                .or_insert_with(|| ChangeGridRow {
+                    params_identifier: params_identifier.clone(),
+                    pretty_name: pretty_name.clone(),
+                    values: vec![None; shown],
+                    change_percent: vec![None; shown],
+                });
+            row.pretty_name = pretty_name.clone();
+            row.values[column] = value;
+            row.change_percent[column] = match (before, value) {
+                (Some(before), Some(value)) if before != 0.0 => {
+                    Some((value - before) / before * 100.0)
+                }
+                _ => None,
+            };
+        }
+    }
+
+    let mut benchmarks: Vec<ChangeGridRow> = rows.into_values().collect();
+    benchmarks.sort_by(|a, b| {
+        a.pretty_name
+            .cmp(&b.pretty_name)
+            .then_with(|| a.params_identifier.cmp(&b.params_identifier))
+    });
+    benchmarks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(values: &[(&str, Option<f64>)]) -> CommitValues {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), (name.to_string(), *value)))
+            .collect()
+    }
+
+    #[test]
+    fn first_shown_commit_is_compared_with_the_one_before() {
+        let values = [
+            commit(&[("poll", Some(100.0))]),
+            commit(&[("poll", Some(110.0)), ("send", Some(50.0))]),
+        ];
+
+        let rows = change_rows(&values, 1);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].params_identifier, "poll");
+        assert_eq!(rows[0].values, [Some(110.0)]);
+        assert_eq!(rows[0].change_percent, [Some(10.0)]);
+        // Nothing to compare against before its first run
+        assert_eq!(rows[1].values, [Some(50.0)]);
+        assert_eq!(rows[1].change_percent, [None]);
+    }
+
+    #[test]
+    fn change_is_against_the_last_commit_the_benchmark_ran_at() {
+        let values = [
+            commit(&[("poll", Some(100.0))]),
+            commit(&[("send", Some(1.0))]),
+            commit(&[("poll", Some(50.0))]),
+        ];
+
+        let rows = change_rows(&values, 0);
+        assert_eq!(rows[0].values, [Some(100.0), None, Some(50.0)]);
+        assert_eq!(rows[0].change_percent, [None, None, Some(-50.0)]);
+    }
+
+    #[test]
+    fn non_finite_values_are_skipped() {
+        let values = [
+            commit(&[("poll", Some(100.0))]),
+            commit(&[("poll", Some(f64::NAN))]),
+            commit(&[("poll", Some(f64::INFINITY))]),
+            commit(&[("poll", Some(120.0))]),
+        ];
+
+        let rows = change_rows(&values, 1);
+        assert_eq!(rows[0].values, [None, None, Some(120.0)]);
+        assert_eq!(rows[0].change_percent, [None, None, Some(20.0)]);
+    }
+
+    #[test]
+    fn zero_baseline_has_no_change() {
+        let values = [
+            commit(&[("poll", Some(0.0))]),
+            commit(&[("poll", Some(5.0))]),
+            commit(&[("poll", Some(10.0))]),
+        ];
+
+        let rows = change_rows(&values, 0);
+        assert_eq!(rows[0].change_percent, [None, None, Some(100.0)]);
+    }
+
+    #[test]
+    fn window_wider_than_the_history_shows_every_commit() {
+        let values = [commit(&[("poll", Some(1.0))])];
+
+        assert_eq!(change_rows(&values, 0)[0].values, [Some(1.0)]);
+        assert!(change_rows(&[], 0).is_empty());
+    }
+}
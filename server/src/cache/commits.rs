@@ -0,0 +1,579 @@
+use super::BenchmarkCache;
+use crate::{
+    error::IggyBenchDashboardServerError,
+    git::{GitRepository, MainlineCommit},
+    ingest::IngestStatus,
+};
+use chrono::{DateTime, FixedOffset};
+use iggy_bench_dashboard_shared::commit::{CommitChange, CommitIdentity};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Length of the short SHA the poller names untagged commits by
+const SHORT_SHA_LEN: usize = 8;
+
+/// Shortest SHA prefix resolved to a commit
+const MIN_SHA_PREFIX_LEN: usize = 7;
+
+impl BenchmarkCache {
+    /// Resolves the loaded benchmarks to commits, and indexes them by hardware
+    /// and commit name. A benchmark's commit is the head SHA of the run it was
+    /// ingested from, its git ref if that is a full SHA, or the commit its git
+    /// ref is a tag or SHA prefix of. Benchmarks of an unknown commit form one
+    /// commit per git ref.
+    pub(super) async fn index_commits(&self) {
+        let mut report_shas: HashMap<Uuid, String> = HashMap::new();
+        for record in self.manifest.history() {
+            if record.status != IngestStatus::Imported {
+                continue;
+            }
+            if let Some(sha) = record.head_sha.filter(|sha| is_full_sha(sha)) {
+                for uuid in record.benchmark_uuids {
+                    report_shas.insert(uuid, sha.to_ascii_lowercase());
+                }
+            }
+        }
+
+        let reports: Vec<(Uuid, String)> = self
+            .benchmarks
+            .iter()
+            .filter_map(|entry| Some((*entry.key(), entry.value().0.params.gitref.clone()?)))
+            .collect();
+        for (uuid, gitref) in &reports {
+            if !report_shas.contains_key(uuid) && is_full_sha(gitref) {
+                report_shas.insert(*uuid, gitref.to_ascii_lowercase());
+            }
+        }
+
+        let mut gitref_shas: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for (uuid, gitref) in &reports {
+            if let Some(sha) = report_shas.get(uuid) {
+                gitref_shas.entry(gitref.as_str()).or_default().insert(sha);
+            }
+        }
+        let known_shas: BTreeSet<&str> = report_shas.values().map(String::as_str).collect();
+
+        // Keyed by full SHA, or by git ref if the commit is unknown
+        let mut commits: BTreeMap<String, (BTreeSet<String>, Vec<Uuid>)> = BTreeMap::new();
+        for (uuid, gitref) in &reports {
+            let sha = report_shas.get(uuid).map(String::as_str).or_else(|| {
+                match gitref_shas.get(gitref.as_str()) {
+                    Some(shas) if shas.len() == 1 => shas.first().copied(),
+                    Some(_) => None,
+                    None => unique_sha_with_prefix(&known_shas, gitref),
+                }
+            });
+            let (aliases, uuids) = commits
+                .entry(sha.unwrap_or(gitref.as_str()).to_owned())
+                .or_default();
+            aliases.insert(gitref.clone());
+            if let Some(sha) = sha {
+                aliases.insert(sha.to_owned());
+                aliases.insert(sha[..SHORT_SHA_LEN].to_owned());
+            }
+            uuids.push(*uuid);
+        }
+
+        let report_dates: HashMap<Uuid, DateTime<FixedOffset>> = self
+            .benchmarks
+            .iter()
+            .filter_map(|entry| {
+                let date = entry.value().0.params.gitref_date.as_deref()?;
+                Some((*entry.key(), DateTime::parse_from_rfc3339(date).ok()?))
+            })
+            .collect();
+        let mut dates = HashMap::new();
+
+        // A git ref used for several commits, e.g. a branch name, names none of them
+        let mut alias_counts: HashMap<String, usize> = HashMap::new();
+        for (aliases, _) in commits.values() {
+            for alias in aliases {
+                *alias_counts.entry(alias.clone()).or_default() += 1;
+            }
+        }
+
+        for (key, (aliases, uuids)) in commits {
+            let sha = is_full_sha(&key).then(|| key.clone());
+            let (mut aliases, ambiguous): (Vec<String>, Vec<String>) = aliases
+                .into_iter()
+                .partition(|alias| alias_counts[alias] == 1);
+            if !ambiguous.is_empty() {
+                warn!(
+                    "Git refs {:?} name several commits, not resolving them to {}",
+                    ambiguous,
+                    sha.as_deref().unwrap_or("a commit")
+                );
+            }
+            let name = match &sha {
+                Some(sha) => commit_name(sha, &aliases),
+                // Even if ambiguous, the git ref still names the benchmarks reported with it
+                None => {
+                    if !aliases.contains(&key) {
+                        aliases.push(key.clone());
+                        aliases.sort();
+                    }
+                    key
+                }
+            };
+
+            for alias in &aliases {
+                self.gitref_aliases.insert(alias.clone(), name.clone());
+            }
+            let date = uuids
+                .iter()
+                .filter_map(|uuid| report_dates.get(uuid))
+                .min()
+                .copied();
+            dates.insert(name.clone(), date);
+            for uuid in uuids {
+                let Some(mut entry) = self.benchmarks.get_mut(&uuid) else {
+                    continue;
+                };
+                let (report, _) = entry.value_mut();
+                report.params.gitref = Some(name.clone());
+                if let Some(identifier) = &report.hardware.identifier {
+                    self.hardware_to_gitref
+                        .entry(identifier.clone())
+                        .or_default()
+                        .insert(name.clone());
+                }
+                self.gitref_to_benchmarks
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(uuid);
+            }
+            self.commits.insert(
+                name.clone(),
+                CommitIdentity {
+                    name,
+                    sha,
+                    aliases,
+                    mainline_position: None,
+                    off_mainline: false,
+                    rank: None,
+                },
+            );
+        }
+        self.rank_commits(dates).await;
+
+        info!(
+            "Indexed {} benchmarks under {} commits",
+            reports.len(),
+            self.commits.len()
+        );
+    }
+
+    /// Ranks the commits oldest first, by the first-parent history of the
+    /// mainline if a git clone is configured. Commits off the first-parent
+    /// history are placed by date between the mainline commits, or only by
+    /// date without a clone. Commits without any date get no rank. The clone
+    /// is queried on a blocking thread, one `git` call per commit at worst.
+    async fn rank_commits(&self, dates: HashMap<String, Option<DateTime<FixedOffset>>>) {
+        let (history, mut placements) = match self.git.clone() {
+            Some(git) => {
+                let commits: Vec<(String, Option<String>)> = self
+                    .commits
+                    .iter()
+                    .map(|commit| (commit.name.clone(), commit.sha.clone()))
+                    .collect();
+                tokio::task::spawn_blocking(move || place_commits(&git, commits))
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!(
+                            "Failed to place the commits in git, ordering by date: {}",
+                            e
+                        );
+                        Default::default()
+                    })
+            }
+            None => Default::default(),
+        };
+
+        let mut commits = Vec::new();
+        for mut commit in self.commits.iter_mut() {
+            let placement = placements.remove(&commit.name).unwrap_or_default();
+            commit.mainline_position = placement.position;
+            commit.off_mainline = placement.off_mainline;
+            let date = placement
+                .date
+                .or_else(|| dates.get(&commit.name).copied().flatten());
+            commits.push((commit.name.clone(), placement.position, date));
+        }
+
+        let mainline_dates: Vec<_> = history.iter().map(|commit| commit.committed_at).collect();
+        for (rank, name) in order_by_slot(&mainline_dates, commits)
+            .into_iter()
+            .enumerate()
+        {
+            if let Some(mut commit) = self.commits.get_mut(&name) {
+                commit.rank = Some(rank);
+            }
+            self.commit_ranks.insert(name, rank);
+        }
+    }
+
+    /// Position of the commit of `gitref` in history, oldest first
+    pub fn commit_rank(&self, gitref: &str) -> Option<usize> {
+        self.commit_ranks
+            .get(&self.canonical_gitref(gitref))
+            .map(|rank| *rank)
+    }
+
+    /// Name of the commit `gitref` is an alias or SHA prefix of
+    pub fn resolve_gitref(&self, gitref: &str) -> Option<String> {
+        if let Some(name) = self.gitref_aliases.get(gitref) {
+            return Some(name.clone());
+        }
+        if gitref.len() < MIN_SHA_PREFIX_LEN || !is_hex(gitref) {
+            return None;
+        }
+
+        let prefix = gitref.to_ascii_lowercase();
+        let mut matches = self.commits.iter().filter(|commit| {
+            commit
+                .sha
+                .as_deref()
+                .is_some_and(|sha| sha.starts_with(&prefix))
+        });
+        let name = matches.next()?.name.clone();
+        matches.next().is_none().then_some(name)
+    }
+
+    pub fn get_commit(&self, gitref: &str) -> Option<CommitIdentity> {
+        let name = self.resolve_gitref(gitref)?;
+        self.commits.get(&name).map(|commit| commit.clone())
+    }
+
+    pub fn get_commits_for_hardware(&self, hardware: &str) -> Vec<CommitIdentity> {
+        self.get_gitrefs_for_hardware(hardware)
+            .iter()
+            .filter_map(|name| self.commits.get(name).map(|commit| commit.clone()))
+            .collect()
+    }
+
+    /// Commits between two git refs, newest first, read from the git clone.
+    /// Git refs resolve through the known commits first, then through the clone.
+    pub fn get_changes(
+        &self,
+        from_gitref: &str,
+        to_gitref: &str,
+    ) -> Result<Vec<CommitChange>, IggyBenchDashboardServerError> {
+        let git = self.git.as_ref().ok_or_else(|| {
+            IggyBenchDashboardServerError::Unavailable(
+                "No git clone is configured, changes are not available".into(),
+            )
+        })?;
+        let resolve = |gitref: &str| {
+            self.get_commit(gitref)
+                .and_then(|commit| commit.sha)
+                .or_else(|| git.resolve(gitref))
+                .ok_or_else(|| {
+                    IggyBenchDashboardServerError::NotFound(format!(
+                        "Commit '{}' is not in the git clone",
+                        gitref
+                    ))
+                })
+        };
+        git.changes(&resolve(from_gitref)?, &resolve(to_gitref)?)
+    }
+
+    /// Name of the commit of `gitref`, or `gitref` itself if it names none
+    pub(super) fn canonical_gitref(&self, gitref: &str) -> String {
+        self.resolve_gitref(gitref)
+            .unwrap_or_else(|| gitref.to_owned())
+    }
+}
+
+/// Where the git clone places a commit
+#[derive(Debug, Default)]
+struct Placement {
+    /// Position in the first-parent history of the mainline
+    position: Option<usize>,
+    /// Commit date in the clone, read only for commits off the first-parent history
+    date: Option<DateTime<FixedOffset>>,
+    off_mainline: bool,
+}
+
+/// Places the commits, given by name and SHA if known, in the first-parent
+/// history of the mainline. Blocks on `git` subprocesses.
+fn place_commits(
+    git: &GitRepository,
+    commits: Vec<(String, Option<String>)>,
+) -> (Vec<MainlineCommit>, HashMap<String, Placement>) {
+    let history = git.mainline_history().unwrap_or_else(|e| {
+        warn!(
+            "Failed to read the mainline history, ordering by date: {}",
+            e
+        );
+        Vec::new()
+    });
+    let positions: HashMap<&str, usize> = history
+        .iter()
+        .enumerate()
+        .map(|(position, commit)| (commit.sha.as_str(), position))
+        .collect();
+
+    let placements = commits
+        .into_iter()
+        .map(|(name, sha)| {
+            let sha = sha.or_else(|| git.resolve(&name));
+            let position = sha.as_deref().and_then(|sha| positions.get(sha).copied());
+            let mut placement = Placement {
+                position,
+                ..Default::default()
+            };
+            if position.is_none() {
+                placement.date = sha.as_deref().and_then(|sha| git.commit_date(sha));
+                placement.off_mainline = !sha.as_deref().is_some_and(|sha| git.is_on_mainline(sha));
+                if placement.off_mainline {
+                    warn!("Commit {} is not on the mainline", name);
+                }
+            }
+            (name, placement)
+        })
+        .collect();
+    (history, placements)
+}
+
+/// Names of the commits, given with their mainline position and date, oldest
+/// first. Odd slots are mainline commits, even slots lie between them by date,
+/// compared with the commit dates of the mainline. Commits without either are
+/// left out.
+fn order_by_slot(
+    mainline_dates: &[Option<DateTime<FixedOffset>>],
+    commits: Vec<(String, Option<usize>, Option<DateTime<FixedOffset>>)>,
+) -> Vec<String> {
+    let mut slots: Vec<_> = commits
+        .into_iter()
+        .filter_map(|(name, position, date)| {
+            let slot = match (position, date) {
+                (Some(position), _) => 2 * position + 1,
+                (None, Some(date)) => {
+                    2 * mainline_dates.partition_point(|committed_at| {
+                        committed_at.is_some_and(|committed_at| committed_at <= date)
+                    })
+                }
+                (None, None) => return None,
+            };
+            Some((slot, date, name))
+        })
+        .collect();
+    slots.sort();
+    slots.into_iter().map(|(_, _, name)| name).collect()
+}
+
+/// Tag or other non-SHA alias if the commit has one, its short SHA otherwise,
+/// or the full SHA if the short one is shared with another commit
+fn commit_name(sha: &str, aliases: &[String]) -> String {
+    let short_sha = &sha[..SHORT_SHA_LEN];
+    aliases
+        .iter()
+        .find(|alias| !is_hex(alias))
+        .cloned()
+        .unwrap_or_else(|| {
+            if aliases.iter().any(|alias| alias == short_sha) {
+                short_sha.to_owned()
+            } else {
+                sha.to_owned()
+            }
+        })
+}
+
+fn unique_sha_with_prefix<'a>(shas: &BTreeSet<&'a str>, gitref: &str) -> Option<&'a str> {
+    if gitref.len() < MIN_SHA_PREFIX_LEN || !is_hex(gitref) {
+        return None;
+    }
+    let prefix = gitref.to_ascii_lowercase();
+    let mut matches = shas.iter().filter(|sha| sha.starts_with(&prefix));
+    let sha = matches.next()?;
+    matches.next().is_none().then_some(*sha)
+}
+
+fn is_full_sha(gitref: &str) -> bool {
+    gitref.len() == 40 && is_hex(gitref)
+}
+
+fn is_hex(gitref: &str) -> bool {
+    gitref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::{IngestRecord, LEGACY_SOURCE};
+    use iggy_bench_dashboard_shared::BenchmarkReportLight;
+    use std::path::PathBuf;
+
+    const SHA_A: &str = "abcdef0123456789abcdef0123456789abcdef01";
+    const SHA_B: &str = "abcdef0223456789abcdef0123456789abcdef02";
+    // Shares its short SHA with `SHA_A`
+    const SHA_C: &str = "abcdef01ffffffffffffffffffffffffffffffff";
+
+    async fn cache() -> (tempfile::TempDir, BenchmarkCache) {
+        let results_dir = tempfile::tempdir().unwrap();
+        let cache = BenchmarkCache::new(results_dir.path().to_path_buf(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+        (results_dir, cache)
+    }
+
+    /// Adds a benchmark reported with `gitref`, ingested from a run of `head_sha` if given
+    async fn add_benchmark(cache: &BenchmarkCache, gitref: &str, head_sha: Option<&str>) {
+        let uuid = Uuid::from_u128(cache.benchmarks.len() as u128 + 1);
+        let mut report = BenchmarkReportLight {
+            uuid,
+            ..Default::default()
+        };
+        report.params.gitref = Some(gitref.to_owned());
+        cache.benchmarks.insert(uuid, (report, PathBuf::new()));
+
+        if let Some(head_sha) = head_sha {
+            let run_id = cache.manifest.history().len() as u64 + 1;
+            cache
+                .manifest
+                .record(IngestRecord {
+                    source: LEGACY_SOURCE.to_owned(),
+                    run_id,
+                    key: None,
+                    pull_request: None,
+                    head_sha: Some(head_sha.to_owned()),
+                    gitref: Some(gitref.to_owned()),
+                    branch: None,
+                    artifacts: Vec::new(),
+                    benchmark_uuids: vec![uuid],
+                    run_created_at: None,
+                    started_at: None,
+                    finished_at: None,
+                    status: IngestStatus::Imported,
+                    error: None,
+                    failed_attempts: 0,
+                })
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn branch_name_of_several_commits_names_none() {
+        let (_results_dir, cache) = cache().await;
+        add_benchmark(&cache, "master", Some(SHA_A)).await;
+        add_benchmark(&cache, "master", Some(SHA_B)).await;
+        cache.index_commits().await;
+
+        assert_eq!(cache.commits.len(), 2);
+        assert_eq!(cache.resolve_gitref("master"), None);
+        assert_eq!(cache.resolve_gitref(SHA_A).as_deref(), Some(&SHA_A[..8]));
+        assert_eq!(cache.resolve_gitref(SHA_B).as_deref(), Some(&SHA_B[..8]));
+        assert!(!cache
+            .get_commit(SHA_A)
+            .unwrap()
+            .aliases
+            .contains(&"master".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn tag_names_its_commit() {
+        let (_results_dir, cache) = cache().await;
+        add_benchmark(&cache, "0.4.300", Some(SHA_A)).await;
+        add_benchmark(&cache, SHA_A, None).await;
+        cache.index_commits().await;
+
+        assert_eq!(cache.commits.len(), 1);
+        let commit = cache.get_commit(SHA_A).unwrap();
+        assert_eq!(commit.name, "0.4.300");
+        assert_eq!(commit.sha.as_deref(), Some(SHA_A));
+        assert_eq!(cache.gitref_to_benchmarks.get("0.4.300").unwrap().len(), 2);
+        assert_eq!(
+            cache.resolve_gitref(&SHA_A[..8]).as_deref(),
+            Some("0.4.300")
+        );
+    }
+
+    #[tokio::test]
+    async fn sha_prefix_resolves_only_if_unique() {
+        let (_results_dir, cache) = cache().await;
+        add_benchmark(&cache, SHA_A, None).await;
+        add_benchmark(&cache, SHA_B, None).await;
+        // A benchmark reported with a unique prefix joins the commit
+        add_benchmark(&cache, &SHA_B[..10], None).await;
+        cache.index_commits().await;
+
+        assert_eq!(cache.commits.len(), 2);
+        assert_eq!(
+            cache.resolve_gitref(&SHA_B[..10]).as_deref(),
+            Some(&SHA_B[..8])
+        );
+        assert_eq!(cache.resolve_gitref("abcdef0"), None);
+        assert_eq!(
+            cache.resolve_gitref("abcdef02").as_deref(),
+            Some(&SHA_B[..8])
+        );
+        assert_eq!(
+            cache.resolve_gitref("ABCDEF022").as_deref(),
+            Some(&SHA_B[..8])
+        );
+        // Shorter than the minimum prefix
+        assert_eq!(cache.resolve_gitref("abcdef"), None);
+    }
+
+    #[tokio::test]
+    async fn shared_short_sha_names_the_commits_by_full_sha() {
+        let (_results_dir, cache) = cache().await;
+        add_benchmark(&cache, SHA_A, None).await;
+        add_benchmark(&cache, SHA_C, None).await;
+        cache.index_commits().await;
+
+        assert_eq!(cache.commits.len(), 2);
+        assert_eq!(cache.resolve_gitref(&SHA_A[..8]), None);
+        assert_eq!(cache.resolve_gitref(SHA_A).as_deref(), Some(SHA_A));
+        assert_eq!(cache.resolve_gitref(SHA_C).as_deref(), Some(SHA_C));
+    }
+
+    fn date(day: u32) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&format!("2025-01-{:02}T00:00:00Z", day)).ok()
+    }
+
+    #[test]
+    fn commits_off_the_mainline_lie_between_it_by_date() {
+        let mainline_dates = [date(10), date(20)];
+        let commits = vec![
+            ("after".to_owned(), None, date(25)),
+            ("second".to_owned(), Some(1), date(1)),
+            ("between".to_owned(), None, date(15)),
+            ("undated".to_owned(), None, None),
+            ("first".to_owned(), Some(0), None),
+            ("before".to_owned(), None, date(5)),
+            // Committed with the first mainline commit, placed after it
+            ("same_day".to_owned(), None, date(10)),
+        ];
+
+        assert_eq!(
+            order_by_slot(&mainline_dates, commits),
+            ["before", "first", "same_day", "between", "second", "after"]
+        );
+    }
+
+    #[test]
+    fn commits_without_a_mainline_are_ordered_by_date_then_name() {
+        let commits = vec![
+            ("b".to_owned(), None, date(2)),
+            ("c".to_owned(), None, date(1)),
+            ("a".to_owned(), None, date(2)),
+        ];
+
+        assert_eq!(order_by_slot(&[], commits), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn commit_name_prefers_a_non_sha_alias() {
+        let short_sha = SHA_A[..8].to_owned();
+        let tag = "0.4.300".to_owned();
+
+        assert_eq!(commit_name(SHA_A, &[short_sha.clone(), tag]), "0.4.300");
+        assert_eq!(
+            commit_name(SHA_A, &[SHA_A.to_owned(), short_sha]),
+            &SHA_A[..8]
+        );
+        assert_eq!(commit_name(SHA_A, &[SHA_A.to_owned()]), SHA_A);
+    }
+}
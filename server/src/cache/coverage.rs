@@ -0,0 +1,130 @@
+use super::BenchmarkCache;
+use iggy_bench_dashboard_shared::coverage::{BenchmarkCoverage, CoverageRow};
+use std::collections::BTreeMap;
+
+impl BenchmarkCache {
+    /// Number of runs of every benchmark at every commit benchmarked on `hardware`
+    pub fn get_coverage(&self, hardware: &str) -> BenchmarkCoverage {
+        let gitrefs = self.gitrefs_oldest_first(hardware);
+        let mut rows: BTreeMap<String, CoverageRow> = BTreeMap::new();
+
+        for (column, gitref) in gitrefs.iter().enumerate() {
+            let mut reports = self.get_benchmarks_for_hardware_and_gitref(hardware, gitref);
+            reports.sort_by_cached_key(|report| Self::parse_date(&report.timestamp));
+            for report in reports {
+                let row = rows
+                    .entry(report.params.params_identifier.clone())
+                    .or_insert_with(|| CoverageRow {
+                        params_identifier: report.params.params_identifier.clone(),
+                        pretty_name: report.params.pretty_name.clone(),
+                        counts: vec![0; gitrefs.len()],
+                    });
+                // Newer runs win, so renamed benchmarks show their current name
+                row.pretty_name = report.params.pretty_name.clone();
+                row.counts[column] += 1;
+            }
+        }
+
+        let mut benchmarks: Vec<CoverageRow> = rows.into_values().collect();
+        benchmarks.sort_by(|a, b| {
+            a.pretty_name
+                .cmp(&b.pretty_name)
+                .then_with(|| a.params_identifier.cmp(&b.params_identifier))
+        });
+
+        BenchmarkCoverage {
+            hardware: self.canonical_hardware(hardware),
+            gitrefs,
+            benchmarks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::LEGACY_SOURCE;
+    use iggy_bench_dashboard_shared::BenchmarkReportLight;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    const SHA_A: &str = "aaaaaaaa23456789abcdef0123456789abcdef01";
+    const SHA_B: &str = "bbbbbbbb23456789abcdef0123456789abcdef02";
+
+    fn add_benchmark(
+        cache: &BenchmarkCache,
+        hardware: &str,
+        gitref: &str,
+        gitref_date: &str,
+        (params_identifier, pretty_name): (&str, &str),
+        timestamp: &str,
+    ) {
+        let uuid = Uuid::from_u128(cache.benchmarks.len() as u128 + 1);
+        let mut report = BenchmarkReportLight {
+            uuid,
+            timestamp: timestamp.to_owned(),
+            ..Default::default()
+        };
+        report.hardware.identifier = Some(hardware.to_owned());
+        report.params.gitref = Some(gitref.to_owned());
+        report.params.gitref_date = Some(gitref_date.to_owned());
+        report.params.params_identifier = params_identifier.to_owned();
+        report.params.pretty_name = pretty_name.to_owned();
+        cache.benchmarks.insert(uuid, (report, PathBuf::new()));
+    }
+
+    #[tokio::test]
+    async fn runs_are_counted_per_benchmark_and_commit() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let cache = BenchmarkCache::new(results_dir.path().to_path_buf(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+        let old = "2025-01-01T00:00:00Z";
+        let new = "2025-02-01T00:00:00Z";
+        add_benchmark(&cache, "atlas", SHA_B, new, ("poll", "Poll"), new);
+        add_benchmark(&cache, "atlas", SHA_A, old, ("poll", "Poll"), old);
+        add_benchmark(&cache, "atlas", SHA_A, old, ("poll", "Poll"), old);
+        add_benchmark(&cache, "atlas", SHA_A, old, ("send", "Send"), old);
+        // Renamed, the newest run's name is shown
+        add_benchmark(&cache, "atlas", SHA_B, new, ("send", "B Send"), new);
+        add_benchmark(&cache, "atlas", SHA_B, new, ("send", "A Send"), old);
+        // Other hardware is not counted
+        add_benchmark(&cache, "zeus", SHA_A, old, ("poll", "Poll"), old);
+        cache.index_hardware();
+        cache.index_commits().await;
+
+        let coverage = cache.get_coverage("atlas");
+        assert_eq!(coverage.hardware, "atlas");
+        assert_eq!(coverage.gitrefs, [&SHA_A[..8], &SHA_B[..8]]);
+        let rows: Vec<(&str, &str, &[u32])> = coverage
+            .benchmarks
+            .iter()
+            .map(|row| {
+                (
+                    row.params_identifier.as_str(),
+                    row.pretty_name.as_str(),
+                    row.counts.as_slice(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("send", "B Send", &[1, 2][..]),
+                ("poll", "Poll", &[2, 1][..])
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_hardware_has_no_coverage() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let cache = BenchmarkCache::new(results_dir.path().to_path_buf(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+
+        let coverage = cache.get_coverage("atlas");
+        assert!(coverage.gitrefs.is_empty());
+        assert!(coverage.benchmarks.is_empty());
+    }
+}
@@ -0,0 +1,420 @@
+use super::BenchmarkCache;
+use iggy_bench_dashboard_shared::hardware::{
+    HardwareEntry, HardwareSpecDifference, HardwareSpecValue,
+};
+use iggy_bench_report::hardware::BenchmarkHardware;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::ErrorKind;
+use std::path::Path;
+use tracing::{error, info, warn};
+
+/// Display names, merged identifiers and retired hardware, read from the results directory
+const HARDWARE_REGISTRY_FILE: &str = "hardware_registry.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HardwareRegistry {
+    #[serde(default)]
+    hardware: Vec<RegisteredHardware>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegisteredHardware {
+    identifier: String,
+    display_name: Option<String>,
+    description: Option<String>,
+    /// Identifiers whose benchmarks are listed under `identifier`
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    retired: bool,
+}
+
+impl HardwareRegistry {
+    /// The registry, empty if the file is missing or invalid
+    fn load(results_dir: &Path) -> Self {
+        let path = results_dir.join(HARDWARE_REGISTRY_FILE);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                error!("Failed to read hardware registry {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        toml::from_str(&content).unwrap_or_else(|e| {
+            error!(
+                "Ignoring invalid hardware registry {}: {}",
+                path.display(),
+                e
+            );
+            Self::default()
+        })
+    }
+}
+
+/// Hardware reported by a benchmark: reported identifier, specification and timestamp
+type ReportedHardware = (String, BenchmarkHardware, String);
+
+impl BenchmarkCache {
+    /// Lists the benchmarks of identifiers registered as aliases under the
+    /// identifier they are an alias of, and indexes the hardware with its
+    /// registry entry and the specifications its benchmarks reported
+    pub(super) fn index_hardware(&self) {
+        let mut registered: HashMap<String, RegisteredHardware> = HashMap::new();
+        for entry in HardwareRegistry::load(&self.results_dir).hardware {
+            if registered.contains_key(&entry.identifier) {
+                warn!(
+                    "Hardware {} is registered twice, ignoring the second entry",
+                    entry.identifier
+                );
+                continue;
+            }
+            registered.insert(entry.identifier.clone(), entry);
+        }
+
+        let mut alias_owners: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for entry in registered.values() {
+            for alias in &entry.aliases {
+                if registered.contains_key(alias) {
+                    warn!(
+                        "Hardware alias {} of {} is registered itself, not merging it",
+                        alias, entry.identifier
+                    );
+                    continue;
+                }
+                alias_owners
+                    .entry(alias)
+                    .or_default()
+                    .insert(&entry.identifier);
+            }
+        }
+        let mut aliases: HashMap<String, String> = HashMap::new();
+        for (alias, owners) in alias_owners {
+            match owners.first() {
+                Some(owner) if owners.len() == 1 => {
+                    aliases.insert(alias.to_owned(), owner.to_string());
+                }
+                _ => warn!(
+                    "Hardware alias {} is registered for {:?}, not merging it",
+                    alias, owners
+                ),
+            }
+        }
+
+        let mut reports: BTreeMap<String, Vec<ReportedHardware>> = BTreeMap::new();
+        for mut entry in self.benchmarks.iter_mut() {
+            let (report, _) = entry.value_mut();
+            let Some(reported) = report.hardware.identifier.clone() else {
+                continue;
+            };
+            let identifier = aliases
+                .get(&reported)
+                .cloned()
+                .unwrap_or_else(|| reported.clone());
+            report.hardware.identifier = Some(identifier.clone());
+            reports.entry(identifier).or_default().push((
+                reported,
+                report.hardware.clone(),
+                report.timestamp.clone(),
+            ));
+        }
+
+        for (identifier, mut reports) in reports {
+            reports.sort_by_cached_key(|(_, _, timestamp)| Self::parse_date(timestamp));
+            let Some((_, hardware, _)) = reports.last() else {
+                continue;
+            };
+            let registered = registered.get(&identifier);
+            let mut merged: Vec<String> = aliases
+                .iter()
+                .filter(|(_, owner)| **owner == identifier)
+                .map(|(alias, _)| alias.clone())
+                .collect();
+            merged.sort();
+
+            let entry = HardwareEntry {
+                identifier: identifier.clone(),
+                display_name: registered.and_then(|entry| entry.display_name.clone()),
+                description: registered.and_then(|entry| entry.description.clone()),
+                aliases: merged,
+                retired: registered.is_some_and(|entry| entry.retired),
+                hardware: hardware.clone(),
+                spec_differences: spec_differences(&reports),
+            };
+            if !entry.spec_differences.is_empty() {
+                warn!(
+                    "Benchmarks of hardware {} report different {}",
+                    identifier,
+                    entry
+                        .spec_differences
+                        .iter()
+                        .map(|difference| difference.field.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            for alias in &entry.aliases {
+                self.hardware_aliases
+                    .insert(alias.clone(), identifier.clone());
+            }
+            self.hardware.insert(identifier, entry);
+        }
+
+        info!(
+            "Indexed {} hardware, {} registered",
+            self.hardware.len(),
+            registered.len()
+        );
+    }
+
+    /// Hardware sorted by identifier, retired hardware only if `include_retired`
+    pub fn get_hardware_entries(&self, include_retired: bool) -> Vec<HardwareEntry> {
+        let mut entries: Vec<HardwareEntry> = self
+            .hardware
+            .iter()
+            .filter(|entry| include_retired || !entry.retired)
+            .map(|entry| entry.clone())
+            .collect();
+        entries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        entries
+    }
+
+    /// Hardware `hardware` is the identifier or an alias of
+    pub fn get_hardware_entry(&self, hardware: &str) -> Option<HardwareEntry> {
+        self.hardware
+            .get(&self.canonical_hardware(hardware))
+            .map(|entry| entry.clone())
+    }
+
+    /// Identifier `hardware` is merged into, or `hardware` itself
+    pub(super) fn canonical_hardware(&self, hardware: &str) -> String {
+        self.hardware_aliases
+            .get(hardware)
+            .map(|identifier| identifier.clone())
+            .unwrap_or_else(|| hardware.to_owned())
+    }
+}
+
+/// Fields of the specification reported with several values, the values
+/// ordered by when they were first reported. `reports` must be oldest first.
+fn spec_differences(reports: &[ReportedHardware]) -> Vec<HardwareSpecDifference> {
+    let specs: Vec<[(&str, String); 5]> = reports
+        .iter()
+        .map(|(_, hardware, _)| spec_fields(hardware))
+        .collect();
+
+    let mut differences = Vec::new();
+    for field in 0..5 {
+        let mut values: Vec<HardwareSpecValue> = Vec::new();
+        for ((reported, _, timestamp), spec) in reports.iter().zip(&specs) {
+            let value = &spec[field].1;
+            match values.iter_mut().find(|known| known.value == *value) {
+                Some(known) => {
+                    known.benchmark_count += 1;
+                    known.last_seen = timestamp.clone();
+                    if !known.identifiers.contains(reported) {
+                        known.identifiers.push(reported.clone());
+                    }
+                }
+                None => values.push(HardwareSpecValue {
+                    value: value.clone(),
+                    identifiers: vec![reported.clone()],
+                    benchmark_count: 1,
+                    first_seen: timestamp.clone(),
+                    last_seen: timestamp.clone(),
+                }),
+            }
+        }
+        if values.len() > 1 {
+            differences.push(HardwareSpecDifference {
+                field: specs[0][field].0.to_owned(),
+                values,
+            });
+        }
+    }
+    differences
+}
+
+fn spec_fields(hardware: &BenchmarkHardware) -> [(&'static str, String); 5] {
+    [
+        ("cpu_name", hardware.cpu_name.clone()),
+        ("cpu_cores", hardware.cpu_cores.to_string()),
+        ("total_memory_mb", hardware.total_memory_mb.to_string()),
+        ("os_name", hardware.os_name.clone()),
+        ("os_version", hardware.os_version.clone()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::LEGACY_SOURCE;
+    use iggy_bench_dashboard_shared::BenchmarkReportLight;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    async fn cache(registry: &str) -> (tempfile::TempDir, BenchmarkCache) {
+        let results_dir = tempfile::tempdir().unwrap();
+        std::fs::write(results_dir.path().join(HARDWARE_REGISTRY_FILE), registry).unwrap();
+        let cache = BenchmarkCache::new(results_dir.path().to_path_buf(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+        (results_dir, cache)
+    }
+
+    fn add_benchmark(cache: &BenchmarkCache, identifier: &str, cpu_name: &str, timestamp: &str) {
+        let uuid = Uuid::from_u128(cache.benchmarks.len() as u128 + 1);
+        let mut report = BenchmarkReportLight {
+            uuid,
+            timestamp: timestamp.to_owned(),
+            ..Default::default()
+        };
+        report.hardware.identifier = Some(identifier.to_owned());
+        report.hardware.cpu_name = cpu_name.to_owned();
+        cache.benchmarks.insert(uuid, (report, PathBuf::new()));
+    }
+
+    #[tokio::test]
+    async fn alias_is_merged_into_its_identifier() {
+        let (_results_dir, cache) = cache(
+            r#"
+            [[hardware]]
+            identifier = "atlas"
+            display_name = "Atlas"
+            aliases = ["atlas-old"]
+            "#,
+        )
+        .await;
+        add_benchmark(&cache, "atlas", "EPYC", "2025-01-02T00:00:00Z");
+        add_benchmark(&cache, "atlas-old", "EPYC", "2025-01-01T00:00:00Z");
+        cache.index_hardware();
+
+        let entry = cache.get_hardware_entry("atlas-old").unwrap();
+        assert_eq!(entry.identifier, "atlas");
+        assert_eq!(entry.display_name.as_deref(), Some("Atlas"));
+        assert_eq!(entry.aliases, ["atlas-old"]);
+        assert!(entry.spec_differences.is_empty());
+        assert_eq!(cache.get_hardware_entries(false).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn hardware_registered_twice_keeps_the_first_entry() {
+        let (_results_dir, cache) = cache(
+            r#"
+            [[hardware]]
+            identifier = "atlas"
+            display_name = "First"
+
+            [[hardware]]
+            identifier = "atlas"
+            display_name = "Second"
+            retired = true
+            "#,
+        )
+        .await;
+        add_benchmark(&cache, "atlas", "EPYC", "2025-01-01T00:00:00Z");
+        cache.index_hardware();
+
+        let entry = cache.get_hardware_entry("atlas").unwrap();
+        assert_eq!(entry.display_name.as_deref(), Some("First"));
+        assert!(!entry.retired);
+    }
+
+    #[tokio::test]
+    async fn alias_of_several_identifiers_is_not_merged() {
+        let (_results_dir, cache) = cache(
+            r#"
+            [[hardware]]
+            identifier = "atlas"
+            aliases = ["shared"]
+
+            [[hardware]]
+            identifier = "zeus"
+            aliases = ["shared"]
+            "#,
+        )
+        .await;
+        add_benchmark(&cache, "atlas", "EPYC", "2025-01-01T00:00:00Z");
+        add_benchmark(&cache, "zeus", "Xeon", "2025-01-01T00:00:00Z");
+        add_benchmark(&cache, "shared", "EPYC", "2025-01-01T00:00:00Z");
+        cache.index_hardware();
+
+        assert_eq!(
+            cache.get_hardware_entry("shared").unwrap().identifier,
+            "shared"
+        );
+        assert!(cache
+            .get_hardware_entry("atlas")
+            .unwrap()
+            .aliases
+            .is_empty());
+        assert!(cache.get_hardware_entry("zeus").unwrap().aliases.is_empty());
+    }
+
+    #[tokio::test]
+    async fn alias_registered_itself_is_not_merged() {
+        let (_results_dir, cache) = cache(
+            r#"
+            [[hardware]]
+            identifier = "atlas"
+            aliases = ["zeus"]
+
+            [[hardware]]
+            identifier = "zeus"
+            retired = true
+            "#,
+        )
+        .await;
+        add_benchmark(&cache, "atlas", "EPYC", "2025-01-01T00:00:00Z");
+        add_benchmark(&cache, "zeus", "Xeon", "2025-01-01T00:00:00Z");
+        cache.index_hardware();
+
+        let zeus = cache.get_hardware_entry("zeus").unwrap();
+        assert_eq!(zeus.identifier, "zeus");
+        assert!(zeus.retired);
+        assert!(cache
+            .get_hardware_entry("atlas")
+            .unwrap()
+            .aliases
+            .is_empty());
+        assert_eq!(cache.get_hardware_entries(false).len(), 1);
+        assert_eq!(cache.get_hardware_entries(true).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn spec_differences_list_each_value_once_oldest_first() {
+        let (_results_dir, cache) = cache(
+            r#"
+            [[hardware]]
+            identifier = "atlas"
+            aliases = ["atlas-old"]
+            "#,
+        )
+        .await;
+        add_benchmark(&cache, "atlas", "EPYC 9004", "2025-03-01T00:00:00Z");
+        add_benchmark(&cache, "atlas-old", "EPYC 7003", "2025-01-01T00:00:00Z");
+        add_benchmark(&cache, "atlas", "EPYC 7003", "2025-02-01T00:00:00Z");
+        cache.index_hardware();
+
+        let entry = cache.get_hardware_entry("atlas").unwrap();
+        assert_eq!(entry.hardware.cpu_name, "EPYC 9004");
+        let [difference] = entry.spec_differences.as_slice() else {
+            panic!("expected one difference: {:?}", entry.spec_differences);
+        };
+        assert_eq!(difference.field, "cpu_name");
+        let [old, new] = difference.values.as_slice() else {
+            panic!("expected two values: {:?}", difference.values);
+        };
+        assert_eq!(old.value, "EPYC 7003");
+        assert_eq!(old.identifiers, ["atlas-old", "atlas"]);
+        assert_eq!(old.benchmark_count, 2);
+        assert_eq!(old.first_seen, "2025-01-01T00:00:00Z");
+        assert_eq!(old.last_seen, "2025-02-01T00:00:00Z");
+        assert_eq!(new.value, "EPYC 9004");
+        assert_eq!(new.identifiers, ["atlas"]);
+        assert_eq!(new.benchmark_count, 1);
+    }
+}
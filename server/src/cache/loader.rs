@@ -1,23 +1,33 @@
-use super::{BenchmarkCache, Result};
+use super::{BenchmarkCache, Result, CANDIDATES_DIR};
 use crate::error::IggyBenchDashboardServerError;
 use iggy_bench_dashboard_shared::BenchmarkReportLight;
 use std::path::Path;
-use tokio::io::AsyncReadExt;
 use tracing::{error, info};
 
 impl BenchmarkCache {
     pub async fn load(&self) -> Result<()> {
+        self.set_loading(true);
+        let result = match self.load_benchmarks().await {
+            Ok(()) => self.load_candidates(),
+            Err(e) => Err(e),
+        };
+        self.set_loading(false);
+        result
+    }
+
+    async fn load_benchmarks(&self) -> Result<()> {
         info!(
             "Building benchmark cache from directory {}",
             self.results_dir.display()
         );
 
-        self.load_gh_workflows().await;
-
         let entries: Vec<_> = std::fs::read_dir(&self.results_dir)
             .map_err(IggyBenchDashboardServerError::Io)?
             .filter_map(|r: std::result::Result<std::fs::DirEntry, std::io::Error>| r.ok())
             .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .filter(|entry| entry.file_name() != CANDIDATES_DIR)
+            // The staging area and quarantine of ingestion
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
             .collect();
 
         let mut total_removed_size = 0;
@@ -54,40 +64,27 @@ impl BenchmarkCache {
 
             info!("Loaded light benchmark report for {:?}", &entry.path());
 
-            let identifier = if let Some(identifier) = &light_report.hardware.identifier {
-                identifier
-            } else {
+            if light_report.hardware.identifier.is_none() {
                 error!(
                     "No identifier found in benchmark report: {:#?}",
                     &entry.path()
                 );
                 return;
-            };
+            }
 
-            let gitref = if let Some(gitref) = &light_report.params.gitref {
-                gitref
-            } else {
+            if light_report.params.gitref.is_none() {
                 error!("No gitref found in benchmark report: {:#?}", &entry.path());
                 return;
-            };
-
-            // Update hardware to gitref mapping
-            self.hardware_to_gitref
-                .entry(identifier.clone())
-                .or_default()
-                .insert(gitref.clone());
-
-            // Update gitref to benchmarks mapping
-            self.gitref_to_benchmarks
-                .entry(gitref.clone())
-                .or_default()
-                .insert(light_report.uuid);
+            }
 
-            // Store the benchmark report
+            // Store the benchmark report, indexed by hardware and commit once all are loaded
             self.benchmarks
                 .insert(light_report.uuid, (light_report, path));
         });
 
+        self.index_hardware();
+        self.index_commits().await;
+
         info!(
             "Remove HTML files of size: {:.2} MB",
             total_removed_size as f64 / 1_048_576.0
@@ -117,32 +114,6 @@ impl BenchmarkCache {
         })
     }
 
-    async fn load_gh_workflows(&self) {
-        let mut data = String::new();
-        let read = self
-            .gh_workflows_file
-            .lock()
-            .await
-            .read_to_string(&mut data)
-            .await;
-
-        match read {
-            Ok(0) => {
-                info!("No GH workflows file found")
-            }
-            Ok(1_usize..) => {
-                info!("Loaded GH workflows file");
-                data.lines().for_each(|line| {
-                    info!("Adding GH workflow: {}", line);
-                    self.gh_workflows.insert(line.parse().unwrap());
-                });
-            }
-            Err(e) => {
-                info!("Error reading GH workflows file: {e}");
-            }
-        }
-    }
-
     pub fn remove_html_files(&self, entry_path: &Path) -> std::io::Result<u64> {
         let html_files: Vec<_> = std::fs::read_dir(entry_path)?
             .filter_map(|r| r.ok())
@@ -1,96 +1,133 @@
 use crate::error::IggyBenchDashboardServerError;
+use crate::git::GitRepository;
+use crate::ingest::IngestManifest;
 use dashmap::{DashMap, DashSet};
-use iggy_bench_dashboard_shared::BenchmarkReportLight;
+use iggy_bench_dashboard_shared::{
+    commit::CommitIdentity, hardware::HardwareEntry, BenchmarkReportLight,
+};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::fs::{File, OpenOptions};
-use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
-use tracing::{error, info};
 use uuid::Uuid;
 
+mod candidates;
+mod change_grid;
+mod commits;
+mod coverage;
+mod hardware;
 mod loader;
+mod performance_index;
 mod query;
 mod storage;
 mod watcher;
 
+pub use candidates::{candidate_dir, CANDIDATES_DIR, CANDIDATE_FILE};
 pub use watcher::CacheWatcher;
 
 pub type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
 pub type HardwareIdentifier = String;
 pub type Gitref = String;
 
-const WORKFLOW_FILE: &str = "gh_workflows.txt";
-
 #[derive(Debug, Clone)]
 pub struct BenchmarkCache {
     /// Map benchmark identifier to benchmark light report and path
     benchmarks: DashMap<Uuid, (BenchmarkReportLight, PathBuf)>,
 
-    /// Map hardware identifier to git ref
+    /// Map hardware identifier to the hardware, its registry entry and specifications
+    hardware: DashMap<HardwareIdentifier, HardwareEntry>,
+
+    /// Map identifier merged into another by the hardware registry to that identifier
+    hardware_aliases: DashMap<HardwareIdentifier, HardwareIdentifier>,
+
+    /// Map hardware identifier to commit names
     hardware_to_gitref: DashMap<HardwareIdentifier, DashSet<Gitref>>,
 
-    /// Map git ref to benchmark directory names
+    /// Map commit name to benchmark identifiers
     gitref_to_benchmarks: DashMap<Gitref, DashSet<Uuid>>,
 
+    /// Map commit name to the commit and its aliases
+    commits: DashMap<Gitref, CommitIdentity>,
+
+    /// Map every alias of a commit to the commit name
+    gitref_aliases: DashMap<Gitref, Gitref>,
+
+    /// Map commit name to its position in history, oldest first. Commits
+    /// without position nor date have none.
+    commit_ranks: DashMap<Gitref, usize>,
+
+    /// Local clone ordering commits by history, by date if unset
+    git: Option<GitRepository>,
+
+    /// Weight of each benchmark kind in the performance index, by kind name
+    index_weights: BTreeMap<String, f64>,
+
+    /// Map source and pull request number to its candidate benchmarks
+    candidates: DashMap<(String, u64), candidates::Candidate>,
+
+    /// Map candidate benchmark identifier to light report and path, kept apart
+    /// from mainline benchmarks
+    candidate_benchmarks: DashMap<Uuid, (BenchmarkReportLight, PathBuf)>,
+
     /// Path to the results directory
     results_dir: PathBuf,
 
     /// Last reload request time
     last_reload_request: Arc<Mutex<Option<Instant>>>,
 
-    /// Workflows downloaded from GitHub
-    gh_workflows: DashSet<u64>,
+    /// Set while the cache is being (re)built and its content is incomplete
+    loading: Arc<AtomicBool>,
 
-    /// File containing list of downloaded workflows
-    gh_workflows_file: Arc<Mutex<File>>,
+    /// Workflow runs ingested from GitHub
+    manifest: Arc<IngestManifest>,
 }
 
 impl BenchmarkCache {
-    pub async fn new(results_dir: PathBuf) -> Self {
-        let gh_workflows_path = results_dir
-            .join(WORKFLOW_FILE)
-            .to_str()
-            .unwrap()
-            .to_string();
-
-        info!("GH workflows path: {}", gh_workflows_path);
-
-        let gh_workflows_file = OpenOptions::new()
-            .write(true)
-            .read(true)
-            .append(true)
-            .create(true)
-            .open(&gh_workflows_path)
-            .await
-            .unwrap_or_else(|_| panic!("Failed to open GH workflows file: {}", gh_workflows_path));
-
-        Self {
+    /// Fails if the ingest manifest can't be opened, see [`IngestManifest::open`]
+    pub async fn new(results_dir: PathBuf, legacy_source: &str) -> Result<Self> {
+        let manifest = IngestManifest::open(&results_dir, legacy_source).await?;
+
+        Ok(Self {
             benchmarks: DashMap::new(),
+            hardware: DashMap::new(),
+            hardware_aliases: DashMap::new(),
             hardware_to_gitref: DashMap::new(),
             gitref_to_benchmarks: DashMap::new(),
+            commits: DashMap::new(),
+            gitref_aliases: DashMap::new(),
+            commit_ranks: DashMap::new(),
+            git: None,
+            index_weights: BTreeMap::new(),
+            candidates: DashMap::new(),
+            candidate_benchmarks: DashMap::new(),
             results_dir,
             last_reload_request: Arc::new(Mutex::new(None)),
-            gh_workflows: DashSet::new(),
-            gh_workflows_file: Arc::new(Mutex::new(gh_workflows_file)),
-        }
+            loading: Arc::new(AtomicBool::new(true)),
+            manifest: Arc::new(manifest),
+        })
+    }
+
+    pub fn with_git(mut self, git: Option<GitRepository>) -> Self {
+        self.git = git;
+        self
+    }
+
+    pub fn with_index_weights(mut self, weights: BTreeMap<String, f64>) -> Self {
+        self.index_weights = weights;
+        self
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading.load(Ordering::Relaxed)
     }
 
-    pub fn is_gh_workflow_present(&self, workflow_id: u64) -> bool {
-        self.gh_workflows.contains(&workflow_id)
+    fn set_loading(&self, loading: bool) {
+        self.loading.store(loading, Ordering::Relaxed);
     }
 
-    pub async fn insert_gh_workflow(&self, workflow_id: u64) {
-        let mut file = self.gh_workflows_file.lock().await;
-
-        if self.gh_workflows.insert(workflow_id) {
-            if let Err(e) = file
-                .write_all(format!("{}\n", workflow_id).as_bytes())
-                .await
-            {
-                error!("Failed to write GH workflow ID to file: {}", e);
-            }
-        }
+    pub fn manifest(&self) -> &IngestManifest {
+        &self.manifest
     }
 }
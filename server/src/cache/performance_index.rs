@@ -0,0 +1,231 @@
+use super::BenchmarkCache;
+use iggy_bench_dashboard_shared::{performance_index::PerformanceIndex, BenchmarkReportLight};
+use iggy_bench_report::benchmark_kind::BenchmarkKind;
+use std::collections::BTreeMap;
+
+/// Weight of benchmark kinds missing from the configured weights
+const DEFAULT_WEIGHT: f64 = 1.0;
+
+/// Metrics of one benchmark at a commit and at the baseline
+struct IndexSample {
+    weight: f64,
+    throughput: f64,
+    baseline_throughput: f64,
+    p99: f64,
+    baseline_p99: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct IndexMeans {
+    throughput_index: f64,
+    latency_index: f64,
+    benchmark_count: usize,
+}
+
+/// Weighted geometric means of the throughput and inverse P99 latency ratios,
+/// `None` if no sample has a positive weight and positive, finite metrics
+fn weighted_means(samples: impl IntoIterator<Item = IndexSample>) -> Option<IndexMeans> {
+    let mut weight_sum = 0.0;
+    let mut log_throughput = 0.0;
+    let mut log_latency = 0.0;
+    let mut benchmark_count = 0;
+    for sample in samples {
+        if sample.weight <= 0.0
+            || [
+                sample.throughput,
+                sample.baseline_throughput,
+                sample.p99,
+                sample.baseline_p99,
+            ]
+            .iter()
+            .any(|value| !value.is_finite() || *value <= 0.0)
+        {
+            continue;
+        }
+
+        weight_sum += sample.weight;
+        log_throughput += sample.weight * (sample.throughput / sample.baseline_throughput).ln();
+        log_latency += sample.weight * (sample.baseline_p99 / sample.p99).ln();
+        benchmark_count += 1;
+    }
+    if benchmark_count == 0 {
+        return None;
+    }
+
+    Some(IndexMeans {
+        throughput_index: (log_throughput / weight_sum).exp(),
+        latency_index: (log_latency / weight_sum).exp(),
+        benchmark_count,
+    })
+}
+
+impl BenchmarkCache {
+    /// Index of `gitref` against `baseline_gitref` on `hardware`, `None` if
+    /// they have no benchmark with usable metrics in common
+    pub fn get_performance_index(
+        &self,
+        hardware: &str,
+        gitref: &str,
+        baseline_gitref: &str,
+    ) -> Option<PerformanceIndex> {
+        let baseline = self.latest_by_params(hardware, baseline_gitref);
+        let reports = self.latest_by_params(hardware, gitref);
+
+        let samples = reports.iter().filter_map(|(params_identifier, report)| {
+            let baseline = baseline.get(params_identifier)?;
+            Some(IndexSample {
+                weight: self.index_weight(&report.params.benchmark_kind),
+                throughput: report.throughput_messages_per_second()?,
+                baseline_throughput: baseline.throughput_messages_per_second()?,
+                p99: report.p99_latency_ms()?,
+                baseline_p99: baseline.p99_latency_ms()?,
+            })
+        });
+        let IndexMeans {
+            throughput_index,
+            latency_index,
+            benchmark_count,
+        } = weighted_means(samples)?;
+
+        Some(PerformanceIndex {
+            hardware: self.canonical_hardware(hardware),
+            gitref: self.canonical_gitref(gitref),
+            baseline_gitref: self.canonical_gitref(baseline_gitref),
+            index: (throughput_index * latency_index).sqrt(),
+            throughput_index,
+            latency_index,
+            benchmark_count,
+        })
+    }
+
+    /// Index of every commit benchmarked on `hardware` against the baseline,
+    /// oldest first, the oldest commit being the default baseline. Commits
+    /// without benchmarks in common with the baseline are left out.
+    pub fn get_performance_index_trend(
+        &self,
+        hardware: &str,
+        baseline_gitref: Option<&str>,
+    ) -> Vec<PerformanceIndex> {
+        let gitrefs = self.gitrefs_oldest_first(hardware);
+        let Some(baseline_gitref) = baseline_gitref.or(gitrefs.first().map(String::as_str)) else {
+            return Vec::new();
+        };
+
+        gitrefs
+            .iter()
+            .filter_map(|gitref| self.get_performance_index(hardware, gitref, baseline_gitref))
+            .collect()
+    }
+
+    /// Baseline used if none is chosen, the oldest commit benchmarked on `hardware`
+    pub fn get_default_index_baseline(&self, hardware: &str) -> Option<String> {
+        self.gitrefs_oldest_first(hardware).into_iter().next()
+    }
+
+    pub(super) fn gitrefs_oldest_first(&self, hardware: &str) -> Vec<String> {
+        let mut gitrefs: Vec<String> = self
+            .get_gitrefs_for_hardware(hardware)
+            .into_iter()
+            .collect();
+        gitrefs.sort_by_cached_key(|gitref| {
+            let rank = self.commit_rank(gitref);
+            (rank.is_none(), rank, gitref.clone())
+        });
+        gitrefs
+    }
+
+    fn index_weight(&self, kind: &BenchmarkKind) -> f64 {
+        serde_json::to_value(kind)
+            .ok()
+            .and_then(|name| self.index_weights.get(name.as_str()?).copied())
+            .unwrap_or(DEFAULT_WEIGHT)
+    }
+
+    /// Most recent report of every benchmark of the commit on the hardware
+    pub(super) fn latest_by_params(
+        &self,
+        hardware: &str,
+        gitref: &str,
+    ) -> BTreeMap<String, BenchmarkReportLight> {
+        let mut latest: BTreeMap<String, BenchmarkReportLight> = BTreeMap::new();
+        for report in self.get_benchmarks_for_hardware_and_gitref(hardware, gitref) {
+            let is_newer = latest
+                .get(&report.params.params_identifier)
+                .is_none_or(|known| {
+                    Self::parse_date(&known.timestamp) < Self::parse_date(&report.timestamp)
+                });
+            if is_newer {
+                latest.insert(report.params.params_identifier.clone(), report);
+            }
+        }
+        latest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(weight: f64, throughput: f64, p99: f64) -> IndexSample {
+        IndexSample {
+            weight,
+            throughput,
+            baseline_throughput: 100.0,
+            p99,
+            baseline_p99: 10.0,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn unchanged_metrics_give_one() {
+        let means = weighted_means([sample(1.0, 100.0, 10.0)]).unwrap();
+        assert_close(means.throughput_index, 1.0);
+        assert_close(means.latency_index, 1.0);
+        assert_eq!(means.benchmark_count, 1);
+    }
+
+    #[test]
+    fn higher_throughput_and_lower_latency_are_faster() {
+        let means = weighted_means([sample(1.0, 200.0, 5.0)]).unwrap();
+        assert_close(means.throughput_index, 2.0);
+        assert_close(means.latency_index, 2.0);
+    }
+
+    #[test]
+    fn ratios_are_averaged_geometrically_by_weight() {
+        let means = weighted_means([sample(3.0, 200.0, 10.0), sample(1.0, 50.0, 10.0)]).unwrap();
+        // (2^3 * 0.5)^(1/4)
+        assert_close(means.throughput_index, 2f64.sqrt());
+        assert_close(means.latency_index, 1.0);
+        assert_eq!(means.benchmark_count, 2);
+    }
+
+    #[test]
+    fn unusable_samples_are_skipped() {
+        let means = weighted_means([
+            sample(1.0, 200.0, 10.0),
+            sample(0.0, 50.0, 10.0),
+            sample(1.0, 0.0, 10.0),
+            sample(1.0, f64::NAN, 10.0),
+            sample(1.0, 100.0, f64::INFINITY),
+        ])
+        .unwrap();
+        assert_close(means.throughput_index, 2.0);
+        assert_eq!(means.benchmark_count, 1);
+    }
+
+    #[test]
+    fn no_usable_sample_gives_no_index() {
+        assert_eq!(weighted_means([]), None);
+        assert_eq!(weighted_means([sample(0.0, 200.0, 10.0)]), None);
+    }
+}
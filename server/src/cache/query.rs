@@ -1,26 +1,22 @@
 use super::BenchmarkCache;
 use chrono::{self, DateTime, FixedOffset};
-use iggy_bench_dashboard_shared::BenchmarkReportLight;
+use iggy_bench_dashboard_shared::{hardware::HardwareBenchmark, BenchmarkReportLight};
 use iggy_bench_report::hardware::BenchmarkHardware;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
 
 impl BenchmarkCache {
+    /// Specifications of the hardware that is not retired, as last reported
     pub fn get_hardware_configurations(&self) -> Vec<BenchmarkHardware> {
-        let mut hardware_map = HashMap::new();
-
-        for entry in self.benchmarks.iter() {
-            let (report, _) = entry.value();
-            if let Some(identifier) = &report.hardware.identifier {
-                hardware_map.insert(identifier.clone(), report.hardware.clone());
-            }
-        }
-
-        hardware_map.into_values().collect()
+        self.get_hardware_entries(false)
+            .into_iter()
+            .map(|entry| entry.hardware)
+            .collect()
     }
 
+    /// Names of the commits benchmarked on `hardware`
     pub fn get_gitrefs_for_hardware(&self, hardware: &str) -> HashSet<String> {
         self.hardware_to_gitref
-            .get(hardware)
+            .get(&self.canonical_hardware(hardware))
             .map(|set| set.iter().map(|s| s.to_string()).collect())
             .unwrap_or_default()
     }
@@ -30,16 +26,20 @@ impl BenchmarkCache {
         hardware: &str,
         gitref: &str,
     ) -> Vec<BenchmarkReportLight> {
+        let hardware = self.canonical_hardware(hardware);
         let mut result = Vec::new();
 
-        if let Some(benchmark_set) = self.gitref_to_benchmarks.get(gitref) {
+        if let Some(benchmark_set) = self
+            .gitref_to_benchmarks
+            .get(&self.canonical_gitref(gitref))
+        {
             for uuid in benchmark_set.iter() {
                 if let Some(entry) = self.benchmarks.get(&uuid) {
                     let (report, _) = entry.value();
 
                     // Check if this benchmark matches our hardware
                     if let Some(identifier) = &report.hardware.identifier {
-                        if identifier != hardware {
+                        if *identifier != hardware {
                             continue;
                         }
                     } else {
@@ -62,13 +62,14 @@ impl BenchmarkCache {
         params_identifier: &str,
         hardware: &str,
     ) -> Option<Vec<BenchmarkReportLight>> {
+        let hardware = self.canonical_hardware(hardware);
         let mut matching_reports = Vec::new();
 
         for entry in self.benchmarks.iter() {
             let (report, _) = entry.value();
 
             if let Some(identifier) = &report.hardware.identifier {
-                if identifier != hardware {
+                if *identifier != hardware {
                     continue;
                 }
             } else {
@@ -84,20 +85,58 @@ impl BenchmarkCache {
             return None;
         }
 
-        matching_reports.sort_by_key(|report| {
-            let date_str = report
+        // Oldest commit first, commits of unknown position last
+        matching_reports.sort_by_cached_key(|report| {
+            let rank = report
                 .params
-                .gitref_date
+                .gitref
                 .as_deref()
-                .unwrap_or("1970-01-01T00:00:00Z");
-            Self::parse_date(date_str)
+                .and_then(|gitref| self.commit_rank(gitref));
+            (rank.is_none(), rank, report.timestamp.clone())
         });
 
         Some(matching_reports)
     }
 
+    /// The benchmark with `params_identifier` of the commit of `gitref` on every
+    /// hardware it ran on, sorted by identifier. The latest run wins if it ran
+    /// several times on the same hardware.
+    pub fn get_hardware_comparison(
+        &self,
+        params_identifier: &str,
+        gitref: &str,
+    ) -> Vec<HardwareBenchmark> {
+        let mut latest: BTreeMap<String, BenchmarkReportLight> = BTreeMap::new();
+        for report in self.get_benchmarks_for_gitref(gitref) {
+            if report.params.params_identifier != params_identifier {
+                continue;
+            }
+            let Some(identifier) = report.hardware.identifier.clone() else {
+                continue;
+            };
+            let is_newer = latest.get(&identifier).is_none_or(|known| {
+                Self::parse_date(&known.timestamp) < Self::parse_date(&report.timestamp)
+            });
+            if is_newer {
+                latest.insert(identifier, report);
+            }
+        }
+
+        latest
+            .into_iter()
+            .map(|(identifier, report)| HardwareBenchmark {
+                display_name: self
+                    .hardware
+                    .get(&identifier)
+                    .and_then(|entry| entry.display_name.clone()),
+                identifier,
+                report,
+            })
+            .collect()
+    }
+
     // Helper function to parse dates with a fallback
-    fn parse_date(date_str: &str) -> DateTime<FixedOffset> {
+    pub(super) fn parse_date(date_str: &str) -> DateTime<FixedOffset> {
         DateTime::parse_from_rfc3339(date_str)
             .unwrap_or_else(|_| DateTime::parse_from_rfc3339("1970-01-01T00:00:00Z").unwrap())
     }
@@ -4,20 +4,25 @@ use std::path::PathBuf;
 use uuid::Uuid;
 
 impl BenchmarkCache {
+    /// Path of the report of a mainline or candidate benchmark
     pub fn get_benchmark_json_path(&self, uuid: &Uuid) -> Option<PathBuf> {
         self.benchmarks
             .get(uuid)
+            .or_else(|| self.candidate_benchmarks.get(uuid))
             .map(|ref_guard| ref_guard.value().1.clone())
     }
 
     pub fn get_benchmark_path(&self, uuid: &Uuid) -> Option<PathBuf> {
-        self.benchmarks
-            .get(uuid)
-            .map(|ref_guard| ref_guard.value().1.clone().parent().unwrap().to_path_buf())
+        self.get_benchmark_json_path(uuid)
+            .map(|path| path.parent().unwrap().to_path_buf())
     }
 
+    /// Benchmarks of the commit `gitref` is an alias of
     pub fn get_benchmarks_for_gitref(&self, gitref: &str) -> Vec<BenchmarkReportLight> {
-        if let Some(benchmark_set) = self.gitref_to_benchmarks.get(gitref) {
+        if let Some(benchmark_set) = self
+            .gitref_to_benchmarks
+            .get(&self.canonical_gitref(gitref))
+        {
             benchmark_set
                 .iter()
                 .filter_map(|uuid| self.benchmarks.get(&uuid))
@@ -31,12 +36,20 @@ impl BenchmarkCache {
     pub fn get_benchmark_report_light(&self, uuid: &Uuid) -> Option<BenchmarkReportLight> {
         self.benchmarks
             .get(uuid)
+            .or_else(|| self.candidate_benchmarks.get(uuid))
             .map(|entry| entry.value().0.clone())
     }
 
     pub(crate) fn clear(&self) {
         self.benchmarks.clear();
+        self.hardware.clear();
+        self.hardware_aliases.clear();
         self.hardware_to_gitref.clear();
         self.gitref_to_benchmarks.clear();
+        self.commits.clear();
+        self.gitref_aliases.clear();
+        self.commit_ranks.clear();
+        self.candidates.clear();
+        self.candidate_benchmarks.clear();
     }
 }
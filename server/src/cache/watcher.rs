@@ -1,5 +1,6 @@
 use super::BenchmarkCache;
 use crate::error::IggyBenchDashboardServerError;
+use crate::ingest::{IngestManifest, QUARANTINE_DIR, STAGING_DIR};
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -9,7 +10,7 @@ use tokio::time::sleep;
 use tracing::{error, info};
 
 pub struct CacheWatcher {
-    _watcher: RecommendedWatcher,
+    watcher: RecommendedWatcher,
 }
 
 impl CacheWatcher {
@@ -19,13 +20,23 @@ impl CacheWatcher {
     ) -> Result<Self, IggyBenchDashboardServerError> {
         let cache_clone = Arc::clone(&cache);
         let runtime_handle = Handle::current();
+        // Ingestion writes there first, only the final rename matters
+        let ignored_dirs = [STAGING_DIR, QUARANTINE_DIR].map(|dir| results_dir.join(dir));
+        // The manifest is not benchmark data and is appended to on every ingest
+        let ignored_files = IngestManifest::files(&results_dir);
 
         let mut watcher = notify::recommended_watcher(move |res: Result<Event, _>| match res {
             Ok(event) => {
-                if matches!(
-                    event.kind,
-                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
-                ) {
+                let ignored = event.paths.iter().all(|path| {
+                    ignored_dirs.iter().any(|dir| path.starts_with(dir))
+                        || ignored_files.contains(path)
+                });
+                if !ignored
+                    && matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    )
+                {
                     let cache = Arc::clone(&cache_clone);
                     runtime_handle.spawn(async move {
                         cache.schedule_reload().await;
@@ -40,7 +51,13 @@ impl CacheWatcher {
             .watch(&results_dir, RecursiveMode::Recursive)
             .map_err(|e| IggyBenchDashboardServerError::InvalidPath(e.to_string()))?;
 
-        Ok(Self { _watcher: watcher })
+        Ok(Self { watcher })
+    }
+
+    /// Stops watching the results directory
+    pub fn stop(self) {
+        drop(self.watcher);
+        info!("Cache watcher stopped");
     }
 }
 
@@ -61,6 +78,7 @@ impl BenchmarkCache {
         sleep(Duration::from_secs(5)).await;
         info!("Reloading cache...");
 
+        self.set_loading(true);
         self.clear();
         if let Err(e) = self.load().await {
             error!("Failed to reload cache: {}", e);
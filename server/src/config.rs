@@ -0,0 +1,612 @@
+use crate::{
+    args::{IggyBenchDashboardServerArgs, PollGithubArgs},
+    error::IggyBenchDashboardServerError,
+    git::GitConfig,
+    github::GithubSource,
+    ingest::{ArtifactLimits, ArtifactSourceConfig, IngestConfig},
+    tls::TlsConfig,
+};
+use chrono::NaiveDate;
+use clap::{error::ErrorKind, CommandFactory};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+use tracing::{error, info, warn};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 8061;
+const DEFAULT_RESULTS_DIR: &str = "./performance_results";
+const DEFAULT_LOG_LEVEL: &str = "info";
+const DEFAULT_SHUTDOWN_TIMEOUT_SECONDS: u64 = 30;
+const DEFAULT_CORS_ORIGINS: &str = "*";
+const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 60;
+const DEFAULT_POLL_BRANCH: &str = "master";
+const DEFAULT_GIT_MAINLINE: &str = "master";
+const DEFAULT_INGEST_INTERVAL_SECONDS: u64 = 60;
+
+/// Handle used to swap the log filter on SIGHUP
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Layout of the `--config` TOML file. Every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    server: ServerSection,
+    tls: TlsSection,
+    cors: CorsSection,
+    poller: Option<PollerSection>,
+    ingest: IngestSection,
+    performance_index: PerformanceIndexConfig,
+    auth: AuthSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerSection {
+    host: Option<String>,
+    port: Option<u16>,
+    results_dir: Option<PathBuf>,
+    log_level: Option<String>,
+    shutdown_timeout_seconds: Option<u64>,
+    git_repo: Option<PathBuf>,
+    git_mainline: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TlsSection {
+    cert: Option<PathBuf>,
+    key: Option<PathBuf>,
+    redirect_port: Option<u16>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CorsSection {
+    origins: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PollerSection {
+    interval_seconds: Option<u64>,
+    branch: Option<String>,
+    sources: Vec<GithubSource>,
+    backfill: Option<BackfillConfig>,
+    api_url: Option<String>,
+    pr_comments: Option<PrCommentConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct IngestSection {
+    interval_seconds: Option<u64>,
+    sources: Vec<ArtifactSourceConfig>,
+    limits: ArtifactLimits,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AuthSection {
+    github_token: Option<String>,
+    admin_token: Option<String>,
+}
+
+/// Weights of the benchmarks in the composite performance index
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PerformanceIndexConfig {
+    /// Weight of each benchmark kind by its name in the API, e.g.
+    /// `pinned_producer`. Unlisted kinds weigh 1, 0 leaves a kind out.
+    pub weights: BTreeMap<String, f64>,
+}
+
+/// Bounds of the one-off import of past workflow runs done at poller startup
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackfillConfig {
+    /// Only runs created on or after this date are imported
+    pub since: Option<NaiveDate>,
+    /// Most recent runs imported per branch
+    pub max_runs: Option<usize>,
+}
+
+/// Markdown comment posted on pull requests once their benchmarks are imported
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrCommentConfig {
+    /// Public URL of the dashboard, the comment links to its views if set
+    pub dashboard_url: Option<String>,
+    /// Throughput drop in percent highlighted as a regression
+    pub throughput_regression_percent: f64,
+    /// P99 latency increase in percent highlighted as a regression
+    pub p99_regression_percent: f64,
+}
+
+impl Default for PrCommentConfig {
+    fn default() -> Self {
+        Self {
+            dashboard_url: None,
+            throughput_regression_percent: 5.0,
+            p99_regression_percent: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PollerConfig {
+    pub interval_seconds: u64,
+    /// Polled independently of each other
+    pub sources: Vec<GithubSource>,
+    /// Past runs are imported before polling starts if set
+    pub backfill: Option<BackfillConfig>,
+    /// Pull requests of sources ingesting them are commented on if set
+    pub pr_comments: Option<PrCommentConfig>,
+}
+
+/// Server settings resolved from command line arguments, environment variables
+/// and the configuration file, in that order of precedence
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub results_dir: PathBuf,
+    pub log_level: String,
+    pub shutdown_timeout: Duration,
+    pub tls: Option<TlsConfig>,
+    pub cors_origins: Vec<String>,
+    pub poller: Option<PollerConfig>,
+    /// Orders git refs by commit history if set, by date otherwise
+    pub git: Option<GitConfig>,
+    /// Artifact sources other than GitHub, disabled if none are configured
+    pub ingest: Option<IngestConfig>,
+    /// Applied to artifacts of every source, GitHub included
+    pub artifact_limits: ArtifactLimits,
+    pub performance_index: PerformanceIndexConfig,
+    pub github_token: Option<String>,
+    /// Bearer token of the admin endpoints, which are disabled if unset
+    pub admin_token: Option<String>,
+}
+
+impl ServerConfig {
+    pub fn load(
+        args: &IggyBenchDashboardServerArgs,
+    ) -> Result<Self, IggyBenchDashboardServerError> {
+        let file = match &args.config {
+            Some(path) => read_config_file(path)?,
+            None => ConfigFile::default(),
+        };
+
+        // Arguments of the `poll-github` subcommand have no clap `env` binding,
+        // as clap only reads it when the subcommand is present
+        let poll_args = args.poll_github_args();
+        let poller = if poll_args.is_some() || file.poller.is_some() {
+            let section = file.poller.unwrap_or_default();
+            // Applies to every source without an API URL of its own
+            let api_url = env_value::<String>("IGGY_DASHBOARD_GITHUB_API_URL")?.or(section.api_url);
+            let mut sources = if section.sources.is_empty() {
+                // The branch only applies to the default source, configured
+                // sources list their branches themselves
+                vec![GithubSource::apache_iggy(
+                    poll_args
+                        .and_then(|poll_args| poll_args.branch.clone())
+                        .or(env_value("IGGY_DASHBOARD_POLL_BRANCH")?)
+                        .or(section.branch)
+                        .unwrap_or_else(|| DEFAULT_POLL_BRANCH.to_owned()),
+                )]
+            } else {
+                section.sources
+            };
+            for source in &mut sources {
+                if source.api_url.is_none() {
+                    source.api_url = api_url.clone();
+                }
+            }
+            Some(PollerConfig {
+                interval_seconds: poll_args
+                    .and_then(|poll_args| poll_args.interval_seconds)
+                    .or(env_value("IGGY_DASHBOARD_POLL_INTERVAL_SECONDS")?)
+                    .or(section.interval_seconds)
+                    .unwrap_or(DEFAULT_POLL_INTERVAL_SECONDS),
+                sources,
+                backfill: backfill_config(poll_args, section.backfill),
+                pr_comments: section.pr_comments,
+            })
+        } else {
+            None
+        };
+
+        // The certificate and key always come from the same place, mixing a
+        // CLI certificate with a key from the file would be confusing
+        let tls_files = match (&args.tls_cert, &args.tls_key) {
+            (Some(cert), Some(key)) => Some((cert.clone(), key.clone())),
+            _ => match (file.tls.cert, file.tls.key) {
+                (Some(cert), Some(key)) => Some((cert, key)),
+                (None, None) => None,
+                _ => {
+                    return Err(IggyBenchDashboardServerError::InvalidConfig(
+                        "tls.cert and tls.key must be set together".into(),
+                    ))
+                }
+            },
+        };
+        let tls_redirect_port = args.tls_redirect_port.or(file.tls.redirect_port);
+        let tls = match tls_files {
+            Some((cert, key)) => Some(TlsConfig {
+                cert,
+                key,
+                redirect_port: tls_redirect_port,
+            }),
+            None if tls_redirect_port.is_some() => {
+                return Err(IggyBenchDashboardServerError::InvalidConfig(
+                    "HTTPS redirect port set, but no TLS certificate and key".into(),
+                ))
+            }
+            None => None,
+        };
+
+        if let Some(source) = poller
+            .iter()
+            .flat_map(|poller| &poller.sources)
+            .find(|source| source.branches.is_empty())
+        {
+            return Err(IggyBenchDashboardServerError::InvalidConfig(format!(
+                "GitHub source {} has no branches",
+                source
+            )));
+        }
+
+        let cors_origins = match &args.cors_origins {
+            Some(origins) => split_origins(origins),
+            None => file
+                .cors
+                .origins
+                .unwrap_or_else(|| split_origins(DEFAULT_CORS_ORIGINS)),
+        };
+
+        Ok(Self {
+            host: args
+                .host
+                .clone()
+                .or(file.server.host)
+                .unwrap_or_else(|| DEFAULT_HOST.to_owned()),
+            port: args.port.or(file.server.port).unwrap_or(DEFAULT_PORT),
+            results_dir: args
+                .results_dir
+                .clone()
+                .or(file.server.results_dir)
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_RESULTS_DIR)),
+            log_level: args
+                .log_level
+                .clone()
+                .or(file.server.log_level)
+                .unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_owned()),
+            shutdown_timeout: Duration::from_secs(
+                args.shutdown_timeout_seconds
+                    .or(file.server.shutdown_timeout_seconds)
+                    .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECONDS),
+            ),
+            tls,
+            cors_origins,
+            poller,
+            git: args
+                .git_repo
+                .clone()
+                .or(file.server.git_repo)
+                .map(|repo| GitConfig {
+                    repo,
+                    mainline: args
+                        .git_mainline
+                        .clone()
+                        .or(file.server.git_mainline)
+                        .unwrap_or_else(|| DEFAULT_GIT_MAINLINE.to_owned()),
+                }),
+            ingest: (!file.ingest.sources.is_empty()).then(|| IngestConfig {
+                interval: Duration::from_secs(
+                    file.ingest
+                        .interval_seconds
+                        .unwrap_or(DEFAULT_INGEST_INTERVAL_SECONDS),
+                ),
+                sources: file.ingest.sources,
+            }),
+            artifact_limits: file.ingest.limits,
+            performance_index: file.performance_index,
+            github_token: std::env::var("GITHUB_TOKEN")
+                .ok()
+                .or(file.auth.github_token),
+            admin_token: std::env::var("IGGY_DASHBOARD_ADMIN_TOKEN")
+                .ok()
+                .or(file.auth.admin_token),
+        })
+    }
+
+    pub fn validate(&self) {
+        let mut cmd = IggyBenchDashboardServerArgs::command();
+        if !self.results_dir.exists() {
+            cmd.error(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Results directory does not exist: {}",
+                    self.results_dir.display()
+                ),
+            )
+            .exit();
+        }
+        if !self.results_dir.is_dir() {
+            cmd.error(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Results path is not a directory: {}",
+                    self.results_dir.display()
+                ),
+            )
+            .exit();
+        }
+
+        if let Some(git) = &self.git {
+            if !git.repo.join(".git").exists() && !git.repo.join("HEAD").is_file() {
+                cmd.error(
+                    ErrorKind::InvalidValue,
+                    format!("Not a git repository: {}", git.repo.display()),
+                )
+                .exit();
+            }
+        }
+
+        for (kind, weight) in &self.performance_index.weights {
+            if !weight.is_finite() || *weight < 0.0 {
+                cmd.error(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Performance index weight of {} must be a non-negative number, got {}",
+                        kind, weight
+                    ),
+                )
+                .exit();
+            }
+        }
+
+        if self.poller.is_some() && self.github_token.is_none() {
+            cmd.error(
+                ErrorKind::InvalidValue,
+                "GitHub token not set (GITHUB_TOKEN env variable or auth.github_token), but GitHub polling enabled",
+            )
+            .exit();
+        }
+    }
+
+    pub fn server_addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// Settings that can change while the server is running, reloaded on SIGHUP
+#[derive(Debug)]
+pub struct ReloadableSettings {
+    cors_origins: RwLock<Vec<String>>,
+    poll_interval_seconds: AtomicU64,
+}
+
+impl ReloadableSettings {
+    pub fn new(config: &ServerConfig) -> Self {
+        Self {
+            cors_origins: RwLock::new(config.cors_origins.clone()),
+            poll_interval_seconds: AtomicU64::new(
+                config
+                    .poller
+                    .as_ref()
+                    .map(|poller| poller.interval_seconds)
+                    .unwrap_or(DEFAULT_POLL_INTERVAL_SECONDS),
+            ),
+        }
+    }
+
+    pub fn is_origin_allowed(&self, origin: &[u8]) -> bool {
+        self.cors_origins
+            .read()
+            .unwrap()
+            .iter()
+            .any(|allowed| allowed == "*" || origin.ends_with(allowed.as_bytes()))
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_seconds.load(Ordering::Relaxed))
+    }
+
+    fn apply(&self, config: &ServerConfig) {
+        *self.cors_origins.write().unwrap() = config.cors_origins.clone();
+        if let Some(poller) = &config.poller {
+            self.poll_interval_seconds
+                .store(poller.interval_seconds, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Re-reads the configuration on every SIGHUP and applies the settings that are
+/// safe to change at runtime. Everything else requires a restart.
+pub async fn reload_on_sighup(
+    args: IggyBenchDashboardServerArgs,
+    settings: Arc<ReloadableSettings>,
+    log_filter: LogFilterHandle,
+) {
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            error!("Failed to install SIGHUP handler: {}", e);
+            return;
+        }
+    };
+
+    while hangup.recv().await.is_some() {
+        info!("SIGHUP received, reloading configuration...");
+        let config = match ServerConfig::load(&args) {
+            Ok(config) => config,
+            Err(e) => {
+                error!(
+                    "Failed to reload configuration, keeping current settings: {}",
+                    e
+                );
+                continue;
+            }
+        };
+
+        settings.apply(&config);
+
+        if std::env::var(EnvFilter::DEFAULT_ENV).is_ok() {
+            warn!("{} is set, log level not reloaded", EnvFilter::DEFAULT_ENV);
+        } else if let Err(e) = log_filter.reload(EnvFilter::new(&config.log_level)) {
+            error!("Failed to reload log level: {}", e);
+        }
+
+        info!(
+            "Configuration reloaded: log level {}, CORS origins {:?}, poll interval {:?}",
+            config.log_level,
+            config.cors_origins,
+            settings.poll_interval()
+        );
+    }
+}
+
+fn read_config_file(path: &Path) -> Result<ConfigFile, IggyBenchDashboardServerError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        IggyBenchDashboardServerError::InvalidConfig(format!(
+            "Failed to read {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    Ok(toml::from_str(&content)?)
+}
+
+/// Backfill is enabled by `--backfill`, any of its bounds on the command line
+/// or a `[poller.backfill]` section. Bounds given on the command line win.
+fn backfill_config(
+    poll_args: Option<&PollGithubArgs>,
+    section: Option<BackfillConfig>,
+) -> Option<BackfillConfig> {
+    let Some(poll_args) = poll_args else {
+        return section;
+    };
+    let requested = poll_args.backfill
+        || poll_args.backfill_since.is_some()
+        || poll_args.backfill_max_runs.is_some();
+    if !requested && section.is_none() {
+        return None;
+    }
+
+    let section = section.unwrap_or_default();
+    Some(BackfillConfig {
+        since: poll_args.backfill_since.or(section.since),
+        max_runs: poll_args.backfill_max_runs.or(section.max_runs),
+    })
+}
+
+fn env_value<T: FromStr>(name: &str) -> Result<Option<T>, IggyBenchDashboardServerError> {
+    match std::env::var(name) {
+        Ok(value) => value.parse().map(Some).map_err(|_| {
+            IggyBenchDashboardServerError::InvalidConfig(format!(
+                "Invalid value of {}: '{}'",
+                name, value
+            ))
+        }),
+        Err(_) => Ok(None),
+    }
+}
+
+fn split_origins(origins: &str) -> Vec<String> {
+    origins.split(',').map(|s| s.trim().to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::io::Write;
+
+    fn config_file(content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    fn load(file: &tempfile::NamedTempFile, args: &[&str]) -> ServerConfig {
+        let config = file.path().to_str().unwrap();
+        let args = ["iggy-bench-dashboard-server", "--config", config]
+            .into_iter()
+            .chain(args.iter().copied());
+        ServerConfig::load(&IggyBenchDashboardServerArgs::parse_from(args)).unwrap()
+    }
+
+    // The only test setting environment variables, tests run in parallel
+    #[test]
+    fn cli_wins_over_env_which_wins_over_file() {
+        let file = config_file(
+            r#"
+            [server]
+            host = "file.example.com"
+            port = 1001
+            log_level = "trace"
+
+            [poller]
+            interval_seconds = 11
+            branch = "file-branch"
+            "#,
+        );
+        std::env::set_var("IGGY_DASHBOARD_HOST", "env.example.com");
+        std::env::set_var("IGGY_DASHBOARD_PORT", "1002");
+        std::env::set_var("IGGY_DASHBOARD_POLL_INTERVAL_SECONDS", "12");
+        std::env::set_var("IGGY_DASHBOARD_POLL_BRANCH", "env-branch");
+
+        let config = load(
+            &file,
+            &[
+                "--host",
+                "cli.example.com",
+                "poll-github",
+                "--interval-seconds",
+                "13",
+            ],
+        );
+
+        std::env::remove_var("IGGY_DASHBOARD_HOST");
+        std::env::remove_var("IGGY_DASHBOARD_PORT");
+        std::env::remove_var("IGGY_DASHBOARD_POLL_INTERVAL_SECONDS");
+        std::env::remove_var("IGGY_DASHBOARD_POLL_BRANCH");
+
+        assert_eq!(config.host, "cli.example.com");
+        assert_eq!(config.port, 1002);
+        assert_eq!(config.log_level, "trace");
+        let poller = config.poller.unwrap();
+        assert_eq!(poller.interval_seconds, 13);
+        assert_eq!(poller.sources[0].branches, ["env-branch"]);
+    }
+
+    #[test]
+    fn tls_cert_and_key_come_from_the_same_place() {
+        let file = config_file(
+            r#"
+            [tls]
+            cert = "file.pem"
+            "#,
+        );
+        let config = file.path().to_str().unwrap();
+        let args = IggyBenchDashboardServerArgs::parse_from([
+            "iggy-bench-dashboard-server",
+            "--config",
+            config,
+        ]);
+        assert!(matches!(
+            ServerConfig::load(&args),
+            Err(IggyBenchDashboardServerError::InvalidConfig(_))
+        ));
+    }
+}
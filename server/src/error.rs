@@ -1,6 +1,7 @@
-use actix_web::{HttpResponse, ResponseError};
-use serde_json::json;
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
 
 #[derive(Error, Debug)]
 pub enum IggyBenchDashboardServerError {
@@ -14,17 +15,143 @@ pub enum IggyBenchDashboardServerError {
     InvalidJson(String),
     #[error("Invalid UUID format: {0}")]
     InvalidUuid(String),
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid artifact: {0}")]
+    InvalidArtifact(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Rate limited, retry after {0:?}")]
+    RateLimited(std::time::Duration),
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
     #[error("Internal error: {0}")]
     InternalError(String),
 }
 
+impl IggyBenchDashboardServerError {
+    /// Machine-readable error code used in the versioned API
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Io(_) => "io_error",
+            Self::InvalidPath(_) => "invalid_path",
+            Self::NotFound(_) => "not_found",
+            Self::InvalidJson(_) => "invalid_json",
+            Self::InvalidUuid(_) => "invalid_uuid",
+            Self::InvalidQuery(_) => "invalid_query",
+            Self::InvalidConfig(_) => "invalid_config",
+            Self::InvalidArtifact(_) => "invalid_artifact",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::Conflict(_) => "conflict",
+            Self::RateLimited(_) => "rate_limited",
+            Self::Unavailable(_) => "unavailable",
+            Self::InternalError(_) => "internal_error",
+        }
+    }
+
+    /// Error description without the kind prefix, the kind is carried by [`Self::code`]
+    pub fn message(&self) -> String {
+        match self {
+            Self::Io(e) => e.to_string(),
+            Self::RateLimited(wait) => format!("Retry after {:?}", wait),
+            Self::InvalidPath(msg)
+            | Self::NotFound(msg)
+            | Self::InvalidJson(msg)
+            | Self::InvalidUuid(msg)
+            | Self::InvalidQuery(msg)
+            | Self::InvalidConfig(msg)
+            | Self::InvalidArtifact(msg)
+            | Self::Unauthorized(msg)
+            | Self::Conflict(msg)
+            | Self::Unavailable(msg)
+            | Self::InternalError(msg) => msg.clone(),
+        }
+    }
+
+    /// Status code used in the versioned API
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidPath(_) | Self::InvalidUuid(_) | Self::InvalidQuery(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::InvalidArtifact(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Io(_)
+            | Self::InvalidJson(_)
+            | Self::InvalidConfig(_)
+            | Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Error of the versioned API, rendered as an [`ErrorEnvelope`] with a status code
+/// matching the error kind
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct ApiError(#[from] pub IggyBenchDashboardServerError);
+
+/// Body of every error response of the versioned API
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorEnvelope {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetail {
+    /// Machine-readable error code, e.g. `invalid_uuid` or `not_found`
+    #[schema(example = "not_found")]
+    pub code: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.0.status()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(ErrorEnvelope {
+            error: ErrorDetail {
+                code: self.0.code().to_owned(),
+                message: self.0.message(),
+            },
+        })
+    }
+}
+
+/// Body of every error response of the unversioned API
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+impl ErrorResponse {
+    fn new(error: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+        }
+    }
+}
+
 impl ResponseError for IggyBenchDashboardServerError {
     fn error_response(&self) -> HttpResponse {
         match self {
             IggyBenchDashboardServerError::NotFound(msg) => {
-                HttpResponse::NotFound().json(json!({ "error": msg }))
+                HttpResponse::NotFound().json(ErrorResponse::new(msg))
+            }
+            IggyBenchDashboardServerError::InvalidQuery(msg) => {
+                HttpResponse::BadRequest().json(ErrorResponse::new(msg))
             }
-            _ => HttpResponse::InternalServerError().json(json!({ "error": self.to_string() })),
+            _ => HttpResponse::InternalServerError().json(ErrorResponse::new(self.to_string())),
         }
     }
 }
@@ -37,7 +164,11 @@ impl From<octocrab::Error> for IggyBenchDashboardServerError {
 
 impl From<zip::result::ZipError> for IggyBenchDashboardServerError {
     fn from(err: zip::result::ZipError) -> Self {
-        Self::InternalError(err.to_string())
+        match err {
+            zip::result::ZipError::Io(e) => Self::Io(e),
+            // Anything else is a broken archive
+            err => Self::InvalidArtifact(err.to_string()),
+        }
     }
 }
 
@@ -46,3 +177,21 @@ impl From<std::env::VarError> for IggyBenchDashboardServerError {
         Self::InternalError(err.to_string())
     }
 }
+
+impl From<toml::de::Error> for IggyBenchDashboardServerError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::InvalidConfig(err.to_string())
+    }
+}
+
+impl From<s3::error::S3Error> for IggyBenchDashboardServerError {
+    fn from(err: s3::error::S3Error) -> Self {
+        Self::InternalError(err.to_string())
+    }
+}
+
+impl From<reqwest::Error> for IggyBenchDashboardServerError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::InternalError(err.to_string())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::IggyBenchDashboardServerError;
+use chrono::{DateTime, FixedOffset};
+use iggy_bench_dashboard_shared::commit::CommitChange;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Most commits listed between two git refs, newest first
+const MAX_CHANGES: usize = 500;
+
+/// Local clone of the benchmarked repository, kept up to date by the operator
+#[derive(Debug, Clone)]
+pub struct GitConfig {
+    pub repo: PathBuf,
+    /// Branch whose first-parent history orders the git refs
+    pub mainline: String,
+}
+
+/// Commit on the first-parent history of the mainline
+#[derive(Debug, Clone)]
+pub struct MainlineCommit {
+    pub sha: String,
+    pub committed_at: Option<DateTime<FixedOffset>>,
+}
+
+/// Read-only queries against the local clone, run through the `git` command
+#[derive(Debug, Clone)]
+pub struct GitRepository {
+    config: GitConfig,
+}
+
+impl GitRepository {
+    pub fn new(config: GitConfig) -> Self {
+        Self { config }
+    }
+
+    /// First-parent history of the mainline, oldest first
+    pub fn mainline_history(&self) -> Result<Vec<MainlineCommit>, IggyBenchDashboardServerError> {
+        let output = self.git(&[
+            "log",
+            "--first-parent",
+            "--reverse",
+            "--format=%H %cI",
+            &self.config.mainline,
+            "--",
+        ])?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let (sha, date) = line.split_once(' ')?;
+                Some(MainlineCommit {
+                    sha: sha.to_owned(),
+                    committed_at: DateTime::parse_from_rfc3339(date).ok(),
+                })
+            })
+            .collect())
+    }
+
+    /// Full SHA of the commit `gitref` names in the clone, e.g. a tag
+    pub fn resolve(&self, gitref: &str) -> Option<String> {
+        let revision = format!("{}^{{commit}}", gitref);
+        self.git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "--end-of-options",
+            &revision,
+        ])
+        .ok()
+        .map(|output| output.trim().to_owned())
+        .filter(|sha| !sha.is_empty())
+    }
+
+    pub fn commit_date(&self, sha: &str) -> Option<DateTime<FixedOffset>> {
+        let output = self
+            .git(&["show", "-s", "--format=%cI", "--end-of-options", sha])
+            .ok()?;
+        DateTime::parse_from_rfc3339(output.trim()).ok()
+    }
+
+    /// Whether `sha` is in the history of the mainline, first parent or not
+    pub fn is_on_mainline(&self, sha: &str) -> bool {
+        self.is_ancestor(sha, &self.config.mainline)
+    }
+
+    /// Whether `ancestor` is in the history of `descendant`, or the same commit
+    pub fn is_ancestor(&self, ancestor: &str, descendant: &str) -> bool {
+        Command::new("git")
+            .arg("-C")
+            .arg(&self.config.repo)
+            .args(["merge-base", "--is-ancestor", ancestor, descendant])
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+
+    /// Commits reachable from `to` but not from `from`, newest first
+    pub fn changes(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<CommitChange>, IggyBenchDashboardServerError> {
+        let max_count = format!("--max-count={}", MAX_CHANGES);
+        let range = format!("{}..{}", from, to);
+        // Fields are separated by the unit separator, commits by the record separator
+        let output = self.git(&[
+            "log",
+            &max_count,
+            "--format=%H%x1f%an%x1f%cI%x1f%B%x1e",
+            "--end-of-options",
+            &range,
+            "--",
+        ])?;
+        Ok(output
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').splitn(4, '\x1f');
+                let sha = fields.next().filter(|sha| !sha.is_empty())?;
+                let author = fields.next()?;
+                let date = fields.next()?;
+                let message = fields.next().unwrap_or_default();
+                Some(CommitChange {
+                    sha: sha.to_owned(),
+                    subject: message.lines().next().unwrap_or_default().to_owned(),
+                    author: author.to_owned(),
+                    date: date.to_owned(),
+                    pr_number: pr_number(message),
+                })
+            })
+            .collect())
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String, IggyBenchDashboardServerError> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.config.repo)
+            .args(args)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(IggyBenchDashboardServerError::InternalError(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                stderr.trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Pull request number of a squash merge subject ending in `(#123)`, or of a
+/// `Merge pull request #123` merge commit
+fn pr_number(message: &str) -> Option<u64> {
+    let subject = message.lines().next()?.trim_end();
+    let digits = match subject.strip_prefix("Merge pull request #") {
+        Some(rest) => rest.split(|c: char| !c.is_ascii_digit()).next()?,
+        None => subject.strip_suffix(')')?.rsplit_once("(#")?.1,
+    };
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pr_number_of_a_squash_merge() {
+        assert_eq!(pr_number("Add the change grid (#1834)"), Some(1834));
+        assert_eq!(pr_number("Fix (#12) (#34) \n\nbody (#56)"), Some(34));
+    }
+
+    #[test]
+    fn pr_number_of_a_merge_commit() {
+        assert_eq!(
+            pr_number("Merge pull request #42 from fork/branch\n\nTitle"),
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn pr_number_only_from_the_subject() {
+        assert_eq!(pr_number("Bump the version\n\nSee (#7)"), None);
+        assert_eq!(pr_number("Mention #7 in passing"), None);
+        assert_eq!(pr_number("Not a number (#abc)"), None);
+        assert_eq!(pr_number(""), None);
+    }
+}
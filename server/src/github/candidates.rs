@@ -0,0 +1,251 @@
+use super::client::{IggyBenchDashboardGithubClient, OpenPullRequest};
+use super::comment::{render_comment, COMMENT_MARKER};
+use super::poller::SourcePoller;
+use crate::cache::{candidate_dir, CANDIDATES_DIR, CANDIDATE_FILE};
+use crate::error::IggyBenchDashboardServerError;
+use crate::ingest::{
+    copy_into, staging_dir, ArtifactItem, ImportOutcome, IngestStatus, REPLACED_DIR,
+};
+use chrono::Utc;
+use iggy_bench_dashboard_shared::candidate::PullRequestCandidate;
+use octocrab::models::{repos::Tag, workflows::Run};
+use std::collections::HashSet;
+use tokio::{fs, sync::watch};
+use tracing::{error, info, warn};
+
+impl SourcePoller {
+    /// Ingests the latest successful run of every open pull request as a
+    /// candidate, and removes the candidates of closed pull requests
+    pub(super) async fn poll_pull_requests(
+        &mut self,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        let open_pull_requests = self.gh.get_open_pull_requests().await?;
+        self.expire_candidates(&open_pull_requests).await;
+
+        // Runs are listed newest first, older runs of a pull request are superseded
+        let mut runs: Vec<(ArtifactItem, Run, OpenPullRequest)> = Vec::new();
+        for run in self.gh.get_successful_pull_request_runs().await? {
+            let Some(pull_request) = open_pull_requests
+                .iter()
+                .find(|pull_request| pull_request.head_sha == run.head_sha)
+            else {
+                continue;
+            };
+            if runs
+                .iter()
+                .all(|(_, _, ingested)| ingested.number != pull_request.number)
+            {
+                let item = IggyBenchDashboardGithubClient::run_item(&run);
+                runs.push((item, run, pull_request.clone()));
+            }
+        }
+        runs.retain(|(item, _, _)| self.is_pending(item));
+        if runs.is_empty() {
+            return Ok(());
+        }
+
+        let tags = self.gh.get_server_tags().await?;
+        for (mut item, run, pull_request) in runs {
+            if *shutdown_rx.borrow() {
+                info!("Shutdown signal received, exiting poll loop");
+                return Ok(());
+            }
+
+            item.gitref = Some(IggyBenchDashboardGithubClient::gitref_for_commit(
+                &tags,
+                &run.head_sha,
+            ));
+            match self
+                .ingest_candidate(&item, &run, &pull_request, &tags, shutdown_rx)
+                .await
+            {
+                Ok(ImportOutcome::Imported) => {
+                    self.failed_attempts.remove(&item.id);
+                }
+                Ok(ImportOutcome::Abandoned) => return Ok(()),
+                Err(e @ IggyBenchDashboardServerError::RateLimited(_)) => return Err(e),
+                Err(e) => {
+                    self.record_failure(&item, Some(pull_request.number), &e)
+                        .await
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the candidate of the pull request with the benchmarks of `run`
+    async fn ingest_candidate(
+        &self,
+        item: &ArtifactItem,
+        run: &Run,
+        pull_request: &OpenPullRequest,
+        tags: &[Tag],
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<ImportOutcome, IggyBenchDashboardServerError> {
+        let mut record = self.pipeline.record(&self.source_name, item);
+        record.pull_request = Some(pull_request.number);
+
+        let base_committed_at = match self.gh.get_commit_date(&pull_request.base_sha).await {
+            Ok(date) => date,
+            Err(e @ IggyBenchDashboardServerError::RateLimited(_)) => return Err(e),
+            // Only needed when the base commit itself wasn't benchmarked
+            Err(e) => {
+                warn!(
+                    "Failed to get the date of base commit {} of pull request #{}: {}",
+                    pull_request.base_sha, pull_request.number, e
+                );
+                None
+            }
+        };
+
+        let artifacts = tokio::select! {
+            artifacts = self.gh.download_artifacts(*run.id, self.pipeline.limits()) => {
+                self.pipeline.fetched(&self.source_name, item, artifacts).await?
+            }
+            _ = shutdown_rx.changed() => {
+                info!("Shutdown signal received during download of workflow {}, exiting poll loop", run.id);
+                return Ok(ImportOutcome::Abandoned);
+            }
+        };
+        let accepted = self
+            .pipeline
+            .accept(&self.source_name, item, &artifacts)
+            .await?;
+        record.benchmark_uuids = accepted.benchmark_uuids.clone();
+
+        let candidate = PullRequestCandidate {
+            pr_number: pull_request.number,
+            title: pull_request.title.clone(),
+            source: self.source_name.clone(),
+            run_id: *run.id,
+            head_sha: run.head_sha.clone(),
+            head_ref: pull_request.head_ref.clone(),
+            base_sha: pull_request.base_sha.clone(),
+            base_ref: pull_request.base_ref.clone(),
+            base_gitref: IggyBenchDashboardGithubClient::gitref_for_commit(
+                tags,
+                &pull_request.base_sha,
+            ),
+            base_committed_at: base_committed_at.map(|date| date.to_rfc3339()),
+            ingested_at: Utc::now().to_rfc3339(),
+        };
+
+        // Built in the staging area, validated, then swapped in with renames
+        let staging = staging_dir(&self.performance_results_dir).await?;
+        let staged_dir = staging.path().join(format!("pr-{}", pull_request.number));
+        fs::create_dir_all(&staged_dir).await?;
+        copy_into(&accepted.benchmarks_dirs, &staged_dir).await?;
+        self.pipeline.validate(std::slice::from_ref(&staged_dir))?;
+        let candidate_json = serde_json::to_string_pretty(&candidate)
+            .map_err(|e| IggyBenchDashboardServerError::InternalError(e.to_string()))?;
+        fs::write(staged_dir.join(CANDIDATE_FILE), candidate_json).await?;
+
+        let candidate_dir = candidate_dir(
+            &self.performance_results_dir,
+            &self.source_name,
+            pull_request.number,
+        );
+        fs::create_dir_all(self.performance_results_dir.join(CANDIDATES_DIR)).await?;
+        // Kept where a startup after a crash between the renames finds it
+        let replaced_dir = staging
+            .path()
+            .join(REPLACED_DIR)
+            .join(CANDIDATES_DIR)
+            .join(candidate_dir.file_name().unwrap());
+        let replaced = candidate_dir.exists();
+        if replaced {
+            fs::create_dir_all(replaced_dir.parent().unwrap()).await?;
+            fs::rename(&candidate_dir, &replaced_dir).await?;
+        }
+        if let Err(e) = fs::rename(&staged_dir, &candidate_dir).await {
+            if replaced {
+                if let Err(e) = fs::rename(&replaced_dir, &candidate_dir).await {
+                    error!("Failed to restore {:?}: {}", candidate_dir, e);
+                }
+            }
+            return Err(e.into());
+        }
+        info!(
+            "Ingested workflow run {} as candidate of pull request #{}",
+            run.id, pull_request.number
+        );
+
+        record.artifacts = artifacts
+            .into_iter()
+            .map(|artifact| artifact.record)
+            .collect();
+        record.status = IngestStatus::Imported;
+        record.finished_at = Some(Utc::now());
+        self.cache.manifest().record(record).await;
+
+        if let Err(e) = self.comment_on_pull_request(run, pull_request.number).await {
+            warn!(
+                "Failed to comment on pull request #{}: {}",
+                pull_request.number, e
+            );
+        }
+
+        Ok(ImportOutcome::Imported)
+    }
+
+    /// Posts or updates the benchmark comparison comment of a pull request, if enabled
+    async fn comment_on_pull_request(
+        &self,
+        run: &Run,
+        pr_number: u64,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        let Some(config) = &self.pr_comments else {
+            return Ok(());
+        };
+
+        self.cache.reload_candidate(&self.source_name, pr_number)?;
+        let comparison = self
+            .cache
+            .compare_candidate(&self.source_name, pr_number)
+            .ok_or_else(|| {
+                IggyBenchDashboardServerError::NotFound(format!(
+                    "Candidate of pull request #{} not loaded",
+                    pr_number
+                ))
+            })?;
+        let body = render_comment(&comparison, config, run.html_url.as_str());
+        self.gh
+            .upsert_pull_request_comment(pr_number, COMMENT_MARKER, &body)
+            .await
+    }
+
+    /// Removes candidates of this source whose pull request is no longer open
+    async fn expire_candidates(&self, open_pull_requests: &[OpenPullRequest]) {
+        let open: HashSet<u64> = open_pull_requests
+            .iter()
+            .map(|pull_request| pull_request.number)
+            .collect();
+
+        for candidate in self.cache.get_candidates() {
+            if candidate.source != self.source_name || open.contains(&candidate.pr_number) {
+                continue;
+            }
+
+            info!(
+                "Pull request #{} is closed, removing its candidate",
+                candidate.pr_number
+            );
+            let dir = candidate_dir(
+                &self.performance_results_dir,
+                &self.source_name,
+                candidate.pr_number,
+            );
+            match fs::remove_dir_all(&dir).await {
+                Ok(()) => self
+                    .cache
+                    .remove_candidate(&self.source_name, candidate.pr_number),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => self
+                    .cache
+                    .remove_candidate(&self.source_name, candidate.pr_number),
+                Err(e) => error!("Failed to remove {:?}: {}", dir, e),
+            }
+        }
+    }
+}
@@ -1,168 +1,583 @@
+use super::source::GithubSource;
 use crate::error::IggyBenchDashboardServerError;
+use crate::ingest::{
+    download_zip, ArtifactItem, ArtifactLimits, ArtifactRecord, ArtifactSource, FetchedArtifact,
+};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use http::{
+    header::{ACCEPT, ETAG, IF_NONE_MATCH, LOCATION, RETRY_AFTER},
+    HeaderMap, HeaderValue, StatusCode,
+};
 use octocrab::{
     models::{
         repos::Tag,
         workflows::{Run, WorkflowListArtifact},
-        RunId,
+        ArtifactId, RunId,
     },
-    params::actions::ArchiveFormat,
+    params::State,
     Octocrab,
 };
-use std::io::Cursor;
-use tempfile::TempDir;
-use tokio::fs::read_dir;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::Duration,
+};
+use tempfile::NamedTempFile;
 use tracing::{info, trace};
-use zip::ZipArchive;
+use url::form_urlencoded::byte_serialize;
+
+/// Wait used when GitHub reports a rate limit without saying for how long
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+const DEFAULT_API_URL: &str = "https://api.github.com";
+/// Most runs GitHub lists for a query filtering runs, e.g. by creation date
+const MAX_FILTERED_RUNS: usize = 1000;
+const DOWNLOAD_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest pause between two chunks of an artifact download
+const DOWNLOAD_READ_TIMEOUT: Duration = Duration::from_secs(60);
 
-const OWNER: &str = "iggy-rs";
-const REPO: &str = "iggy";
-const WORKFLOW_FILE: &str = "performance.yml";
+#[derive(Debug, Deserialize)]
+struct WorkflowRunsPage {
+    workflow_runs: Vec<Run>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitResponse {
+    commit: CommitDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitDetails {
+    committer: Option<CommitSignature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitSignature {
+    date: Option<DateTime<Utc>>,
+}
+
+/// Open pull request of the source repository
+#[derive(Debug, Clone)]
+pub struct OpenPullRequest {
+    pub number: u64,
+    pub title: Option<String>,
+    pub head_sha: String,
+    pub head_ref: String,
+    pub base_sha: String,
+    pub base_ref: String,
+}
+
+/// Rate limit reported by the last GitHub response
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimit {
+    pub remaining: Option<u64>,
+    pub reset_at: Option<DateTime<Utc>>,
+}
+
+impl RateLimit {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let header_number = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<i64>().ok())
+        };
+        Self {
+            remaining: header_number("x-ratelimit-remaining").map(|remaining| remaining as u64),
+            reset_at: header_number("x-ratelimit-reset")
+                .and_then(|reset| DateTime::from_timestamp(reset, 0)),
+        }
+    }
+
+    /// Time until the quota is replenished, if it is exhausted
+    pub fn exhausted_for(&self) -> Option<Duration> {
+        if self.remaining != Some(0) {
+            return None;
+        }
+        let wait = self
+            .reset_at
+            .and_then(|reset_at| (reset_at - Utc::now()).to_std().ok())
+            .unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+        Some(wait)
+    }
+}
 
 pub struct IggyBenchDashboardGithubClient {
     octocrab: Octocrab,
+    /// Artifact downloads bypass octocrab, which follows redirects and buffers bodies
+    download_client: reqwest::Client,
+    token: String,
+    source: GithubSource,
+    /// ETag and runs of the last successful response for each runs URL
+    run_etags: Mutex<HashMap<String, (HeaderValue, Vec<Run>)>>,
+    rate_limit: Mutex<RateLimit>,
 }
 
 impl IggyBenchDashboardGithubClient {
-    pub fn new() -> Result<Self, IggyBenchDashboardServerError> {
-        let token = std::env::var("GITHUB_TOKEN").map_err(|_| {
-            IggyBenchDashboardServerError::InternalError("GITHUB_TOKEN not set".into())
-        })?;
-        let octocrab = Octocrab::builder().personal_token(token).build()?;
-        Ok(Self { octocrab })
-    }
-    pub async fn download_artifact(
-        &self,
-        workflow_id: u64,
-    ) -> Result<TempDir, IggyBenchDashboardServerError> {
-        let runs = self.get_all_workflow_runs().await?;
-        if runs.is_empty() {
-            return Err(IggyBenchDashboardServerError::NotFound(
-                "No successful workflow runs found".into(),
-            ));
+    pub fn new(token: &str, source: GithubSource) -> Result<Self, IggyBenchDashboardServerError> {
+        let mut builder = Octocrab::builder().personal_token(token.to_owned());
+        if let Some(api_url) = &source.api_url {
+            builder = builder.base_uri(api_url.as_str())?;
         }
+        let octocrab = builder.build()?;
+        let download_client = reqwest::Client::builder()
+            .connect_timeout(DOWNLOAD_CONNECT_TIMEOUT)
+            .read_timeout(DOWNLOAD_READ_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent(concat!("iggy-bench-dashboard/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        Ok(Self {
+            octocrab,
+            download_client,
+            token: token.to_owned(),
+            source,
+            run_etags: Mutex::new(HashMap::new()),
+            rate_limit: Mutex::new(RateLimit::default()),
+        })
+    }
 
-        let run_id = RunId(workflow_id);
+    pub fn rate_limit(&self) -> RateLimit {
+        *self.rate_limit.lock().unwrap()
+    }
 
-        runs.iter().find(|run| run.id == run_id).ok_or_else(|| {
-            IggyBenchDashboardServerError::NotFound(format!(
-                "Workflow run {} not found in {}",
-                workflow_id, WORKFLOW_FILE
-            ))
-        })?;
+    /// Downloads and unpacks every artifact of a workflow run whose name matches
+    /// the artifact pattern of the source, one temporary directory per artifact
+    pub async fn download_artifacts(
+        &self,
+        workflow_id: u64,
+        limits: &ArtifactLimits,
+    ) -> Result<Vec<FetchedArtifact>, IggyBenchDashboardServerError> {
+        let run_id = RunId(workflow_id);
+        let artifacts: Vec<WorkflowListArtifact> = self
+            .get_artifacts_for_workflow_run(run_id)
+            .await?
+            .into_iter()
+            .filter(|artifact| self.source.matches_artifact(&artifact.name))
+            .collect();
+        if artifacts.is_empty() {
+            return Err(IggyBenchDashboardServerError::NotFound(format!(
+                "No artifacts matching '{}' found in workflow run {}",
+                self.source.artifact_pattern, workflow_id
+            )));
+        }
 
-        let artifacts = self.get_artifacts_for_workflow_run(run_id).await?;
+        let mut downloaded = Vec::with_capacity(artifacts.len());
+        for artifact in artifacts {
+            downloaded.push(FetchedArtifact {
+                archive: self.download_artifact(artifact.id, limits).await?,
+                record: ArtifactRecord {
+                    id: *artifact.id,
+                    name: artifact.name,
+                    size_bytes: artifact.size_in_bytes as u64,
+                },
+            });
+        }
+        Ok(downloaded)
+    }
 
-        let artifact = artifacts.first().ok_or_else(|| {
-            IggyBenchDashboardServerError::NotFound("No artifacts found in the workflow run".into())
-        })?;
-        let artifact_id = artifact.id;
+    pub async fn get_workflow_run(
+        &self,
+        run_id: u64,
+    ) -> Result<Run, IggyBenchDashboardServerError> {
+        self.octocrab
+            .workflows(&self.source.owner, &self.source.repo)
+            .get(RunId(run_id))
+            .await
+            .map_err(|e| self.github_error(e))
+    }
 
+    /// Streams an artifact to a temporary file. GitHub usually redirects the
+    /// download to a short-lived URL that needs no authentication, so the token
+    /// is only sent to the API and a direct 200 response is streamed as well.
+    async fn download_artifact(
+        &self,
+        artifact_id: ArtifactId,
+        limits: &ArtifactLimits,
+    ) -> Result<NamedTempFile, IggyBenchDashboardServerError> {
         info!("Downloading new artifact ID: {}", artifact_id);
 
-        let bytes = self
-            .octocrab
-            .actions()
-            .download_artifact(OWNER, REPO, artifact_id, ArchiveFormat::Zip)
-            .await?;
-        info!(
-            "Downloaded artifact ID: {}, bytes length: {}",
-            artifact_id,
-            bytes.len()
+        let url = format!(
+            "{}/repos/{}/{}/actions/artifacts/{}/zip",
+            self.source
+                .api_url
+                .as_deref()
+                .unwrap_or(DEFAULT_API_URL)
+                .trim_end_matches('/'),
+            encode(&self.source.owner),
+            encode(&self.source.repo),
+            artifact_id
         );
+        let response = self
+            .download_client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .header(ACCEPT, "application/vnd.github+json")
+            .send()
+            .await?;
+        self.check_rate_limit(response.status(), response.headers())?;
 
-        let temp_dir = TempDir::new()?;
-        let output_dir = temp_dir.path();
+        let response = if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .ok_or_else(|| {
+                    IggyBenchDashboardServerError::InternalError(format!(
+                        "GitHub returned {} without a download location for {}",
+                        response.status(),
+                        url
+                    ))
+                })?;
+            self.download_client.get(location).send().await?
+        } else {
+            response
+        };
 
-        info!("Unzipping to directory: {:?}", output_dir);
+        let archive = download_zip(response.error_for_status()?, limits).await?;
+        info!("Downloaded artifact ID: {}", artifact_id);
+        Ok(archive)
+    }
+
+    /// Retrieves workflow runs of the source workflow that were triggered on
+    /// a given branch and have a successful status.
+    pub async fn get_successful_workflow_runs(
+        &self,
+        branch: &str,
+    ) -> Result<Vec<Run>, IggyBenchDashboardServerError> {
+        let runs = self.list_successful_runs(self.runs_uri(branch)).await?;
 
-        let cursor = Cursor::new(bytes);
-        let mut zip = ZipArchive::new(cursor)?;
+        trace!(
+            "Found {} successful workflow runs on {} branch",
+            runs.len(),
+            branch
+        );
+        Ok(runs)
+    }
 
-        for i in 0..zip.len() {
-            let mut file = zip.by_index(i)?;
-            let outpath = output_dir.join(file.mangled_name());
+    /// Pages through every successful run of the source workflow on a branch,
+    /// newest first, stopping at runs created before `since` or after `max_runs` runs.
+    /// GitHub lists at most [`MAX_FILTERED_RUNS`] runs for a filtered query, so
+    /// older runs are listed in further queries limited to runs created before
+    /// the oldest one seen.
+    pub async fn get_all_successful_workflow_runs(
+        &self,
+        branch: &str,
+        since: Option<NaiveDate>,
+        max_runs: Option<usize>,
+    ) -> Result<Vec<Run>, IggyBenchDashboardServerError> {
+        let mut runs: Vec<Run> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut before: Option<DateTime<Utc>> = None;
+        'windows: loop {
+            let created = match (since, before) {
+                (Some(since), Some(before)) => {
+                    Some(format!("{}..{}", since, created_query(before)))
+                }
+                (Some(since), None) => Some(format!(">={}", since)),
+                (None, Some(before)) => Some(format!("<={}", created_query(before))),
+                (None, None) => None,
+            };
 
-            if file.is_dir() {
-                std::fs::create_dir_all(&outpath)?;
-            } else {
-                if let Some(parent) = outpath.parent() {
-                    std::fs::create_dir_all(parent)?;
+            let mut listed = 0;
+            let mut added = 0;
+            for page in 1.. {
+                let mut uri = format!("{}&page={}", self.runs_uri(branch), page);
+                if let Some(created) = &created {
+                    uri.push_str(&format!("&created={}", encode(created)));
                 }
-                let mut outfile = std::fs::File::create(&outpath)?;
-                std::io::copy(&mut file, &mut outfile)?;
-            }
 
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
+                let response = self.octocrab._get(uri.as_str()).await?;
+                self.check_rate_limit(response.status(), response.headers())?;
+                if !response.status().is_success() {
+                    return Err(IggyBenchDashboardServerError::InternalError(format!(
+                        "GitHub returned {} for {}",
+                        response.status(),
+                        uri
+                    )));
+                }
+                let body = self.octocrab.body_to_string(response).await?;
+                let page: WorkflowRunsPage = serde_json::from_str(&body)
+                    .map_err(|e| IggyBenchDashboardServerError::InvalidJson(e.to_string()))?;
 
-                if let Some(mode) = file.unix_mode() {
-                    std::fs::set_permissions(&outpath, std::fs::Permissions::from_mode(mode))?;
+                if page.workflow_runs.is_empty() {
+                    break;
+                }
+                listed += page.workflow_runs.len();
+                for run in page.workflow_runs {
+                    if seen.insert(run.id) {
+                        runs.push(run);
+                        added += 1;
+                    }
+                }
+                if max_runs.is_some_and(|max_runs| runs.len() >= max_runs) {
+                    break 'windows;
+                }
+                if listed >= MAX_FILTERED_RUNS {
+                    break;
                 }
             }
-        }
 
-        info!("Artifact unzipped to: {:?}", output_dir);
-
-        let mut entries = Vec::new();
-        let mut dir = read_dir(output_dir).await?;
-        while let Some(entry) = dir.next_entry().await? {
-            let path = entry.path();
-            if path.is_dir() {
-                entries.push(path);
+            // A listing cut short by the limit goes on with the runs created up to
+            // its oldest one, which is listed again along with others of that second
+            if listed < MAX_FILTERED_RUNS || added == 0 {
+                break;
             }
+            before = runs.last().map(|run| run.created_at);
         }
 
-        if entries.len() != 1 {
-            return Err(IggyBenchDashboardServerError::InternalError(format!(
-                "Expected exactly one directory in the unzipped artifact directory {}, found {}",
-                temp_dir.path().display(),
-                entries.len()
-            )));
+        if let Some(max_runs) = max_runs {
+            runs.truncate(max_runs);
         }
+        info!(
+            "Found {} successful workflow runs of {} on {} branch",
+            runs.len(),
+            self.source,
+            branch
+        );
+        Ok(runs)
+    }
 
-        Ok(temp_dir)
+    /// Retrieves the latest successful runs of the source workflow triggered by pull requests
+    pub async fn get_successful_pull_request_runs(
+        &self,
+    ) -> Result<Vec<Run>, IggyBenchDashboardServerError> {
+        let uri = format!(
+            "/repos/{}/{}/actions/workflows/{}/runs?status=success&per_page=100&event=pull_request",
+            encode(&self.source.owner),
+            encode(&self.source.repo),
+            encode(&self.source.workflow)
+        );
+        let runs = self.list_successful_runs(uri).await?;
+
+        trace!("Found {} successful pull request runs", runs.len());
+        Ok(runs)
     }
 
-    /// Retrieves workflow runs for the specified workflow file (`performance.yml`)
-    /// that have a successful status.
-    async fn get_all_workflow_runs(&self) -> Result<Vec<Run>, IggyBenchDashboardServerError> {
-        let runs: Vec<Run> = self
+    pub async fn get_open_pull_requests(
+        &self,
+    ) -> Result<Vec<OpenPullRequest>, IggyBenchDashboardServerError> {
+        let mut pull_requests = Vec::new();
+        let mut page = self
             .octocrab
-            .workflows(OWNER, REPO)
-            .list_runs(WORKFLOW_FILE)
-            .status("success")
+            .pulls(&self.source.owner, &self.source.repo)
+            .list()
+            .state(State::Open)
             .per_page(100)
             .send()
-            .await?
-            .into_iter()
-            .collect();
-        Ok(runs)
+            .await
+            .map_err(|e| self.github_error(e))?;
+
+        loop {
+            for pull_request in &page {
+                pull_requests.push(OpenPullRequest {
+                    number: pull_request.number,
+                    title: pull_request.title.clone(),
+                    head_sha: pull_request.head.sha.clone(),
+                    head_ref: pull_request.head.ref_field.clone(),
+                    base_sha: pull_request.base.sha.clone(),
+                    base_ref: pull_request.base.ref_field.clone(),
+                });
+            }
+            page = match self
+                .octocrab
+                .get_page(&page.next)
+                .await
+                .map_err(|e| self.github_error(e))?
+            {
+                Some(next_page) => next_page,
+                None => break,
+            };
+        }
+
+        Ok(pull_requests)
     }
 
-    /// Retrieves workflow runs for the specified workflow file (`performance.yml`)
-    /// that were triggered on a given branch and have a successful status.
-    pub async fn get_successful_workflow_runs(
+    /// Updates the comment of the pull request containing `marker`, or creates
+    /// it if there is none, so that repeated runs don't add more comments
+    pub async fn upsert_pull_request_comment(
         &self,
-        branch: &str,
-    ) -> Result<Vec<Run>, IggyBenchDashboardServerError> {
-        let runs: Vec<Run> = self
-            .octocrab
-            .workflows(OWNER, REPO)
-            .list_runs(WORKFLOW_FILE)
-            .status("success")
-            .branch(branch)
+        pr_number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        let issues = self.octocrab.issues(&self.source.owner, &self.source.repo);
+        let mut page = issues
+            .list_comments(pr_number)
+            .per_page(100)
             .send()
-            .await?
-            .into_iter()
-            .collect();
+            .await
+            .map_err(|e| self.github_error(e))?;
 
-        trace!(
-            "Found {} successful workflow runs on {} branch",
-            runs.len(),
-            branch
+        let existing = loop {
+            let found = page.items.iter().find(|comment| {
+                comment
+                    .body
+                    .as_deref()
+                    .is_some_and(|body| body.contains(marker))
+            });
+            if let Some(comment) = found {
+                break Some(comment.id);
+            }
+            page = match self
+                .octocrab
+                .get_page(&page.next)
+                .await
+                .map_err(|e| self.github_error(e))?
+            {
+                Some(next_page) => next_page,
+                None => break None,
+            };
+        };
+
+        match existing {
+            Some(comment_id) => {
+                issues
+                    .update_comment(comment_id, body)
+                    .await
+                    .map_err(|e| self.github_error(e))?;
+                info!(
+                    "Updated benchmark comment {} on pull request #{}",
+                    comment_id, pr_number
+                );
+            }
+            None => {
+                let comment = issues
+                    .create_comment(pr_number, body)
+                    .await
+                    .map_err(|e| self.github_error(e))?;
+                info!(
+                    "Posted benchmark comment {} on pull request #{}",
+                    comment.id, pr_number
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Committer date of a commit of the source repository
+    pub async fn get_commit_date(
+        &self,
+        sha: &str,
+    ) -> Result<Option<DateTime<Utc>>, IggyBenchDashboardServerError> {
+        let uri = format!(
+            "/repos/{}/{}/commits/{}",
+            encode(&self.source.owner),
+            encode(&self.source.repo),
+            encode(sha)
         );
-        Ok(runs)
+        let response = self.octocrab._get(uri.as_str()).await?;
+        self.check_rate_limit(response.status(), response.headers())?;
+        if !response.status().is_success() {
+            return Err(IggyBenchDashboardServerError::InternalError(format!(
+                "GitHub returned {} for {}",
+                response.status(),
+                uri
+            )));
+        }
+        let body = self.octocrab.body_to_string(response).await?;
+        let commit: CommitResponse = serde_json::from_str(&body)
+            .map_err(|e| IggyBenchDashboardServerError::InvalidJson(e.to_string()))?;
+        Ok(commit.commit.committer.and_then(|committer| committer.date))
+    }
+
+    fn runs_uri(&self, branch: &str) -> String {
+        format!(
+            "/repos/{}/{}/actions/workflows/{}/runs?status=success&per_page=100&branch={}",
+            encode(&self.source.owner),
+            encode(&self.source.repo),
+            encode(&self.source.workflow),
+            encode(branch)
+        )
+    }
+
+    /// Lists the latest successful runs with a conditional request, so that an
+    /// unchanged list is answered with 304 and doesn't count against the quota.
+    async fn list_successful_runs(
+        &self,
+        uri: String,
+    ) -> Result<Vec<Run>, IggyBenchDashboardServerError> {
+        let cached = self.run_etags.lock().unwrap().get(&uri).cloned();
+        let mut headers = HeaderMap::new();
+        if let Some((etag, _)) = &cached {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+
+        let response = self
+            .octocrab
+            ._get_with_headers(uri.as_str(), Some(headers))
+            .await?;
+        self.check_rate_limit(response.status(), response.headers())?;
+
+        match (response.status(), cached) {
+            (StatusCode::NOT_MODIFIED, Some((_, runs))) => {
+                trace!("Workflow runs at {} not modified", uri);
+                Ok(runs)
+            }
+            (status, _) if status.is_success() => {
+                let etag = response.headers().get(ETAG).cloned();
+                let body = self.octocrab.body_to_string(response).await?;
+                let page: WorkflowRunsPage = serde_json::from_str(&body)
+                    .map_err(|e| IggyBenchDashboardServerError::InvalidJson(e.to_string()))?;
+                if let Some(etag) = etag {
+                    self.run_etags
+                        .lock()
+                        .unwrap()
+                        .insert(uri, (etag, page.workflow_runs.clone()));
+                }
+                Ok(page.workflow_runs)
+            }
+            (status, _) => Err(IggyBenchDashboardServerError::InternalError(format!(
+                "GitHub returned {} for {}",
+                status, uri
+            ))),
+        }
+    }
+
+    /// Records the rate limit of a response and turns rate limit rejections
+    /// into [`IggyBenchDashboardServerError::RateLimited`]
+    fn check_rate_limit(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        let rate_limit = RateLimit::from_headers(headers);
+        if rate_limit.remaining.is_some() {
+            *self.rate_limit.lock().unwrap() = rate_limit;
+        }
+
+        if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(());
+        }
+
+        let retry_after = headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_secs);
+        match retry_after.or_else(|| rate_limit.exhausted_for()) {
+            Some(wait) => Err(IggyBenchDashboardServerError::RateLimited(wait)),
+            None if status == StatusCode::TOO_MANY_REQUESTS => Err(
+                IggyBenchDashboardServerError::RateLimited(DEFAULT_RATE_LIMIT_WAIT),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Like the `From` conversion, but recognizes rate limit rejections of typed requests
+    fn github_error(&self, err: octocrab::Error) -> IggyBenchDashboardServerError {
+        if let octocrab::Error::GitHub { source, .. } = &err {
+            if source.status_code == StatusCode::TOO_MANY_REQUESTS
+                || (source.status_code == StatusCode::FORBIDDEN
+                    && source.message.to_lowercase().contains("rate limit"))
+            {
+                let wait = self
+                    .rate_limit()
+                    .exhausted_for()
+                    .unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+                return IggyBenchDashboardServerError::RateLimited(wait);
+            }
+        }
+        err.into()
     }
 
     async fn get_artifacts_for_workflow_run(
@@ -172,9 +587,10 @@ impl IggyBenchDashboardGithubClient {
         let artifact_response = self
             .octocrab
             .actions()
-            .list_workflow_run_artifacts(OWNER, REPO, run_id)
+            .list_workflow_run_artifacts(&self.source.owner, &self.source.repo, run_id)
             .send()
-            .await?;
+            .await
+            .map_err(|e| self.github_error(e))?;
 
         let artifacts = artifact_response
             .value
@@ -189,8 +605,28 @@ impl IggyBenchDashboardGithubClient {
         Ok(artifacts)
     }
 
+    /// Item of a workflow run, its git ref is only known once the tags are fetched
+    pub fn run_item(run: &Run) -> ArtifactItem {
+        ArtifactItem {
+            id: *run.id,
+            key: run.id.to_string(),
+            head_sha: Some(run.head_sha.clone()),
+            gitref: None,
+            branch: Some(run.head_branch.clone()),
+            created_at: Some(run.created_at),
+        }
+    }
+
     /// Given a list of tags and a commit SHA, returns the tag that starts with that commit SHA.
-    pub fn get_tag_for_commit(tags: &Vec<Tag>, commit_sha: &str) -> Option<Tag> {
+    /// Git ref benchmarks of a commit are filed under: its server tag, or the
+    /// first 8 characters of its SHA
+    pub fn gitref_for_commit(tags: &[Tag], commit_sha: &str) -> String {
+        Self::get_tag_for_commit(tags, commit_sha)
+            .map(|tag| tag.name)
+            .unwrap_or_else(|| commit_sha.chars().take(8).collect())
+    }
+
+    pub fn get_tag_for_commit(tags: &[Tag], commit_sha: &str) -> Option<Tag> {
         for tag in tags {
             if tag.commit.sha.starts_with(commit_sha) {
                 return Some(tag.clone());
@@ -201,7 +637,13 @@ impl IggyBenchDashboardGithubClient {
 
     pub async fn get_server_tags(&self) -> Result<Vec<Tag>, IggyBenchDashboardServerError> {
         let mut tags = Vec::new();
-        let mut page = self.octocrab.repos(OWNER, REPO).list_tags().send().await?;
+        let mut page = self
+            .octocrab
+            .repos(&self.source.owner, &self.source.repo)
+            .list_tags()
+            .send()
+            .await
+            .map_err(|e| self.github_error(e))?;
 
         loop {
             for tag in &page {
@@ -209,7 +651,12 @@ impl IggyBenchDashboardGithubClient {
                     tags.push(tag.clone());
                 }
             }
-            page = match self.octocrab.get_page(&page.next).await? {
+            page = match self
+                .octocrab
+                .get_page(&page.next)
+                .await
+                .map_err(|e| self.github_error(e))?
+            {
                 Some(next_page) => next_page,
                 None => break,
             };
@@ -218,3 +665,179 @@ impl IggyBenchDashboardGithubClient {
         Ok(tags)
     }
 }
+
+/// Items are the successful workflow runs on the branches of the source
+impl ArtifactSource for IggyBenchDashboardGithubClient {
+    fn name(&self) -> String {
+        self.source.to_string()
+    }
+
+    async fn list_new(&self) -> Result<Vec<ArtifactItem>, IggyBenchDashboardServerError> {
+        let mut items = Vec::new();
+        for branch in &self.source.branches {
+            let runs = self.get_successful_workflow_runs(branch).await?;
+            items.extend(runs.iter().map(Self::run_item));
+        }
+        Ok(items)
+    }
+
+    async fn fetch(
+        &self,
+        item: &ArtifactItem,
+        limits: &ArtifactLimits,
+    ) -> Result<Vec<FetchedArtifact>, IggyBenchDashboardServerError> {
+        self.download_artifacts(item.id, limits).await
+    }
+
+    async fn acknowledge(&self, _item: &ArtifactItem) -> Result<(), IggyBenchDashboardServerError> {
+        Ok(())
+    }
+}
+
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+/// Time in the format of the `created` filter of run listings
+fn created_query(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use serde_json::{json, Value};
+
+    /// Comments of the mocked pull request and how often one was created
+    #[derive(Default)]
+    struct MockComments {
+        comments: Mutex<Vec<(u64, String)>>,
+        created: Mutex<usize>,
+    }
+
+    fn comment_json(id: u64, body: &str) -> Value {
+        let url = "https://github.example.com/";
+        json!({
+            "id": id,
+            "node_id": "node",
+            "url": url,
+            "html_url": url,
+            "body": body,
+            "author_association": "NONE",
+            "user": {
+                "login": "bot",
+                "id": 1,
+                "node_id": "node",
+                "avatar_url": url,
+                "gravatar_id": "",
+                "url": url,
+                "html_url": url,
+                "followers_url": url,
+                "following_url": url,
+                "gists_url": url,
+                "starred_url": url,
+                "subscriptions_url": url,
+                "organizations_url": url,
+                "repos_url": url,
+                "events_url": url,
+                "received_events_url": url,
+                "type": "User",
+                "site_admin": false
+            },
+            "created_at": "2025-01-01T00:00:00Z"
+        })
+    }
+
+    async fn list_comments(mock: web::Data<MockComments>) -> HttpResponse {
+        let comments = mock.comments.lock().unwrap();
+        HttpResponse::Ok().json(
+            comments
+                .iter()
+                .map(|(id, body)| comment_json(*id, body))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    async fn create_comment(mock: web::Data<MockComments>, body: web::Json<Value>) -> HttpResponse {
+        let body = body["body"].as_str().unwrap().to_owned();
+        let mut comments = mock.comments.lock().unwrap();
+        let id = 100 + comments.len() as u64;
+        comments.push((id, body.clone()));
+        *mock.created.lock().unwrap() += 1;
+        HttpResponse::Created().json(comment_json(id, &body))
+    }
+
+    async fn update_comment(
+        mock: web::Data<MockComments>,
+        path: web::Path<(String, String, u64)>,
+        body: web::Json<Value>,
+    ) -> HttpResponse {
+        let (_, _, id) = path.into_inner();
+        let body = body["body"].as_str().unwrap().to_owned();
+        let mut comments = mock.comments.lock().unwrap();
+        let Some(comment) = comments
+            .iter_mut()
+            .find(|(comment_id, _)| *comment_id == id)
+        else {
+            return HttpResponse::NotFound().finish();
+        };
+        comment.1 = body.clone();
+        HttpResponse::Ok().json(comment_json(id, &body))
+    }
+
+    #[actix_web::test]
+    async fn comment_with_the_marker_is_updated_instead_of_posted_again() {
+        let mock = web::Data::new(MockComments::default());
+        mock.comments
+            .lock()
+            .unwrap()
+            .push((1, "Looks good to me".to_owned()));
+        let server = HttpServer::new({
+            let mock = mock.clone();
+            move || {
+                App::new()
+                    .app_data(mock.clone())
+                    .route(
+                        "/repos/{owner}/{repo}/issues/{number}/comments",
+                        web::get().to(list_comments),
+                    )
+                    .route(
+                        "/repos/{owner}/{repo}/issues/{number}/comments",
+                        web::post().to(create_comment),
+                    )
+                    .route(
+                        "/repos/{owner}/{repo}/issues/comments/{id}",
+                        web::post().to(update_comment),
+                    )
+            }
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let address = server.addrs()[0];
+        let server = server.run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        let mut source = GithubSource::apache_iggy("master".to_owned());
+        source.api_url = Some(format!("http://{}", address));
+        let client = IggyBenchDashboardGithubClient::new("token", source).unwrap();
+        let marker = "<!-- marker -->";
+        client
+            .upsert_pull_request_comment(42, marker, &format!("{}\nfirst", marker))
+            .await
+            .unwrap();
+        client
+            .upsert_pull_request_comment(42, marker, &format!("{}\nsecond", marker))
+            .await
+            .unwrap();
+        handle.stop(false).await;
+
+        assert_eq!(*mock.created.lock().unwrap(), 1);
+        let comments = mock.comments.lock().unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].1, "Looks good to me");
+        assert_eq!(comments[1].1, format!("{}\nsecond", marker));
+    }
+}
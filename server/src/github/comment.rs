@@ -0,0 +1,296 @@
+use crate::config::PrCommentConfig;
+use iggy_bench_dashboard_shared::candidate::{
+    BenchmarkComparison, CandidateComparison, HardwareComparison,
+};
+use std::fmt::Write;
+use url::form_urlencoded::byte_serialize;
+
+/// Hidden marker identifying the comment to update on later runs
+pub const COMMENT_MARKER: &str = "<!-- iggy-bench-dashboard:benchmark-comparison -->";
+
+/// Markdown comparing the benchmarks of a pull request run with its base
+pub fn render_comment(
+    comparison: &CandidateComparison,
+    config: &PrCommentConfig,
+    run_url: &str,
+) -> String {
+    let candidate = &comparison.candidate;
+    let dashboard_url = config
+        .dashboard_url
+        .as_deref()
+        .map(|url| url.trim_end_matches('/'));
+
+    let mut body = String::new();
+    let _ = writeln!(body, "{}", COMMENT_MARKER);
+    let _ = writeln!(body, "## Benchmark results\n");
+    let _ = writeln!(
+        body,
+        "[Workflow run {}]({}) at `{}` compared against `{}` (`{}`).\n",
+        candidate.run_id,
+        run_url,
+        short_sha(&candidate.head_sha),
+        candidate.base_gitref,
+        candidate.base_ref
+    );
+
+    let regressions: usize = comparison
+        .hardware
+        .iter()
+        .flat_map(|hardware| &hardware.benchmarks)
+        .filter(|benchmark| is_regression(benchmark, config))
+        .count();
+    if regressions > 0 {
+        let _ = writeln!(
+            body,
+            "> [!WARNING]\n> {} benchmark(s) regressed by more than {}% throughput or {}% P99 latency.\n",
+            regressions, config.throughput_regression_percent, config.p99_regression_percent
+        );
+    }
+
+    if comparison.hardware.is_empty() {
+        let _ = writeln!(body, "The run contained no benchmarks.\n");
+    }
+    for hardware in &comparison.hardware {
+        render_hardware(&mut body, hardware, config, dashboard_url);
+    }
+
+    if let Some(dashboard_url) = dashboard_url {
+        let _ = writeln!(
+            body,
+            "[Open in the dashboard]({}/pulls/{}?source={})",
+            dashboard_url,
+            candidate.pr_number,
+            encode(&candidate.source)
+        );
+    }
+    body
+}
+
+fn render_hardware(
+    body: &mut String,
+    hardware: &HardwareComparison,
+    config: &PrCommentConfig,
+    dashboard_url: Option<&str>,
+) {
+    match &hardware.baseline_gitref {
+        Some(gitref) => {
+            let _ = writeln!(body, "### {} vs `{}`\n", hardware.hardware, gitref);
+        }
+        None => {
+            let _ = writeln!(
+                body,
+                "### {} (no mainline results to compare with)\n",
+                hardware.hardware
+            );
+        }
+    }
+
+    let _ = writeln!(
+        body,
+        "| Benchmark | Throughput (msg/s) | Baseline | Change | P99 (ms) | Baseline | Change |"
+    );
+    let _ = writeln!(body, "|---|---:|---:|---:|---:|---:|---:|");
+    for benchmark in &hardware.benchmarks {
+        let candidate = &benchmark.candidate;
+        let baseline = benchmark.baseline.as_ref();
+        let name = match (dashboard_url, &hardware.baseline_gitref, baseline) {
+            (Some(url), Some(gitref), Some(_)) => format!(
+                "[{}]({}/?hardware={}&gitref={}&params_identifier={})",
+                candidate.params.pretty_name,
+                url,
+                encode(&hardware.hardware),
+                encode(gitref),
+                encode(&candidate.params.params_identifier)
+            ),
+            _ => candidate.params.pretty_name.clone(),
+        };
+
+        let _ = writeln!(
+            body,
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            name,
+            format_metric(candidate.throughput_messages_per_second(), 0),
+            format_metric(baseline.and_then(|b| b.throughput_messages_per_second()), 0),
+            format_change(
+                benchmark.throughput_change_percent,
+                is_throughput_regression(benchmark, config)
+            ),
+            format_metric(candidate.p99_latency_ms(), 2),
+            format_metric(baseline.and_then(|b| b.p99_latency_ms()), 2),
+            format_change(
+                benchmark.p99_change_percent,
+                is_p99_regression(benchmark, config)
+            ),
+        );
+    }
+    body.push('\n');
+}
+
+fn is_regression(benchmark: &BenchmarkComparison, config: &PrCommentConfig) -> bool {
+    is_throughput_regression(benchmark, config) || is_p99_regression(benchmark, config)
+}
+
+fn is_throughput_regression(benchmark: &BenchmarkComparison, config: &PrCommentConfig) -> bool {
+    benchmark
+        .throughput_change_percent
+        .is_some_and(|change| -change > config.throughput_regression_percent)
+}
+
+fn is_p99_regression(benchmark: &BenchmarkComparison, config: &PrCommentConfig) -> bool {
+    benchmark
+        .p99_change_percent
+        .is_some_and(|change| change > config.p99_regression_percent)
+}
+
+fn format_change(change: Option<f64>, regression: bool) -> String {
+    match change {
+        Some(change) if regression => format!(":warning: **{:+.2}%**", change),
+        Some(change) => format!("{:+.2}%", change),
+        None => "-".to_owned(),
+    }
+}
+
+fn format_metric(value: Option<f64>, precision: usize) -> String {
+    value
+        .map(|value| format!("{:.*}", precision, value))
+        .unwrap_or_else(|| "-".to_owned())
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..8).unwrap_or(sha)
+}
+
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy_bench_dashboard_shared::{candidate::PullRequestCandidate, BenchmarkReportLight};
+
+    fn config(dashboard_url: Option<&str>) -> PrCommentConfig {
+        PrCommentConfig {
+            dashboard_url: dashboard_url.map(str::to_owned),
+            throughput_regression_percent: 5.0,
+            p99_regression_percent: 10.0,
+        }
+    }
+
+    fn report(pretty_name: &str, params_identifier: &str) -> BenchmarkReportLight {
+        let mut report = BenchmarkReportLight::default();
+        report.params.pretty_name = pretty_name.to_owned();
+        report.params.params_identifier = params_identifier.to_owned();
+        report
+    }
+
+    fn benchmark(name: &str, throughput_change: f64, p99_change: f64) -> BenchmarkComparison {
+        BenchmarkComparison {
+            candidate: report(name, name),
+            baseline: Some(report(name, name)),
+            throughput_change_percent: Some(throughput_change),
+            p99_change_percent: Some(p99_change),
+        }
+    }
+
+    fn comparison(hardware: Vec<HardwareComparison>) -> CandidateComparison {
+        CandidateComparison {
+            candidate: PullRequestCandidate {
+                pr_number: 42,
+                title: None,
+                source: "apache/iggy/performance.yml".to_owned(),
+                run_id: 7,
+                head_sha: "0123456789abcdef".to_owned(),
+                head_ref: "feature".to_owned(),
+                base_sha: "fedcba9876543210".to_owned(),
+                base_ref: "master".to_owned(),
+                base_gitref: "0.4.300".to_owned(),
+                base_committed_at: None,
+                ingested_at: "2025-01-01T00:00:00Z".to_owned(),
+            },
+            hardware,
+        }
+    }
+
+    #[test]
+    fn only_changes_beyond_the_thresholds_are_regressions() {
+        let comparison = comparison(vec![HardwareComparison {
+            hardware: "atlas".to_owned(),
+            baseline_gitref: Some("0.4.300".to_owned()),
+            benchmarks: vec![
+                benchmark("throughput drop", -6.0, 10.0),
+                benchmark("latency rise", -5.0, 10.5),
+                benchmark("improvement", 3.0, -2.0),
+            ],
+        }]);
+
+        let body = render_comment(&comparison, &config(None), "https://github.com/run/7");
+
+        assert!(body.starts_with(COMMENT_MARKER));
+        assert!(body.contains("2 benchmark(s) regressed by more than 5% throughput or 10% P99"));
+        assert!(body.contains(":warning: **-6.00%**"));
+        assert!(body.contains(":warning: **+10.50%**"));
+        // Right at the thresholds
+        assert!(body.contains("| -5.00% |"));
+        assert!(body.contains("| +10.00% |"));
+        assert!(body.contains("| +3.00% |"));
+        assert!(body.contains("| -2.00% |"));
+    }
+
+    #[test]
+    fn hardware_without_baseline_is_listed_without_changes() {
+        let comparison = comparison(vec![HardwareComparison {
+            hardware: "atlas".to_owned(),
+            baseline_gitref: None,
+            benchmarks: vec![BenchmarkComparison {
+                candidate: report("pinned producer", "pinned_producer"),
+                baseline: None,
+                throughput_change_percent: None,
+                p99_change_percent: None,
+            }],
+        }]);
+
+        let body = render_comment(
+            &comparison,
+            &config(Some("https://dashboard.example.com")),
+            "https://github.com/run/7",
+        );
+
+        assert!(body.contains("### atlas (no mainline results to compare with)"));
+        assert!(body.contains("| pinned producer | - | - | - | - | - | - |"));
+        assert!(!body.contains("[!WARNING]"));
+    }
+
+    #[test]
+    fn no_benchmarks_are_reported() {
+        let body = render_comment(&comparison(Vec::new()), &config(None), "https://run");
+
+        assert!(body.contains("The run contained no benchmarks."));
+        assert!(!body.contains("Open in the dashboard"));
+    }
+
+    #[test]
+    fn dashboard_links_are_encoded() {
+        let mut benchmark = benchmark("pinned producer", 0.0, 0.0);
+        benchmark.candidate = report("pinned producer", "pinned_producer/a&b");
+        let comparison = comparison(vec![HardwareComparison {
+            hardware: "atlas 1".to_owned(),
+            baseline_gitref: Some("0.4.300+rc".to_owned()),
+            benchmarks: vec![benchmark],
+        }]);
+
+        let body = render_comment(
+            &comparison,
+            &config(Some("https://dashboard.example.com/")),
+            "https://github.com/run/7",
+        );
+
+        assert!(body.contains(
+            "[pinned producer](https://dashboard.example.com/?hardware=atlas+1&gitref=0.4.300%2Brc&params_identifier=pinned_producer%2Fa%26b)"
+        ));
+        assert!(body.contains(
+            "[Open in the dashboard](https://dashboard.example.com/pulls/42?source=apache%2Figgy%2Fperformance.yml)"
+        ));
+        assert!(body.contains("[Workflow run 7](https://github.com/run/7) at `01234567`"));
+    }
+}
@@ -1,4 +1,10 @@
+mod candidates;
 mod client;
+mod comment;
 mod poller;
+mod source;
+mod status;
 
 pub use poller::IggyBenchDashboardGithubPoller;
+pub use source::GithubSource;
+pub use status::{PollerStatus, SourceStatus};
@@ -1,144 +1,415 @@
 use crate::cache::BenchmarkCache;
+use crate::config::{BackfillConfig, PollerConfig, PrCommentConfig, ReloadableSettings};
+use crate::github::{GithubSource, PollerStatus};
+use crate::ingest::{
+    ArtifactItem, ArtifactSource, ImportOutcome, ImportPipeline, IngestStatus, MAX_IMPORT_ATTEMPTS,
+};
 use crate::{error::IggyBenchDashboardServerError, github::client::IggyBenchDashboardGithubClient};
-use file_operation::async_copy_dir_files;
-use std::{path::PathBuf, sync::Arc, time::Duration};
-use tokio::{fs, sync::watch, task::JoinHandle, time::sleep};
-use tracing::{info, trace};
+use chrono::Utc;
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{
+    sync::watch,
+    task::JoinHandle,
+    time::{sleep, timeout_at, Instant},
+};
+use tracing::{error, info, trace, warn};
 
-/// A long-running background task that polls GitHub for workflow runs.
+/// First retry delay after a failed poll, doubled with every further failure
+const BASE_BACKOFF: Duration = Duration::from_secs(10);
+const MAX_BACKOFF: Duration = Duration::from_secs(15 * 60);
+/// Failed backfills in a row after which only new runs are polled
+const MAX_BACKFILL_ATTEMPTS: u32 = 5;
+
+/// Long-running background tasks, one per configured source, that poll GitHub
+/// for workflow runs. A failing source doesn't affect the others.
 pub struct IggyBenchDashboardGithubPoller {
     shutdown_tx: watch::Sender<bool>,
-    join_handle: JoinHandle<()>,
+    join_handles: Vec<(GithubSource, JoinHandle<()>)>,
 }
 
 impl IggyBenchDashboardGithubPoller {
     pub fn start(
         output_dir: PathBuf,
-        branch: String,
-        interval_seconds: u64,
+        config: PollerConfig,
+        github_token: String,
+        settings: Arc<ReloadableSettings>,
+        pipeline: Arc<ImportPipeline>,
         cache: Arc<BenchmarkCache>,
+        status: Arc<PollerStatus>,
     ) -> Self {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let join_handle = tokio::spawn(async move {
-            if let Err(e) =
-                poll_github_with_shutdown(output_dir, branch, interval_seconds, shutdown_rx, cache)
-                    .await
-            {
-                tracing::error!("Error in poll_github: {}", e);
-            }
-        });
+        let mut join_handles = Vec::new();
+        for source in config.sources.iter().cloned() {
+            let source_name = source.to_string();
+            let status_key = status.register_github_source(&source);
+
+            let gh = match IggyBenchDashboardGithubClient::new(&github_token, source.clone()) {
+                Ok(gh) => gh,
+                Err(e) => {
+                    tracing::error!("Error in poll_github for {}: {}", source_name, e);
+                    status.record_error(&status_key, e.to_string());
+                    continue;
+                }
+            };
+            cache.manifest().accept_reimports(&source_name);
+
+            let mut poller = SourcePoller {
+                gh,
+                source_name,
+                status_key,
+                branches: source.branches.clone(),
+                pull_requests: source.pull_requests,
+                performance_results_dir: output_dir.clone(),
+                pipeline: Arc::clone(&pipeline),
+                cache: Arc::clone(&cache),
+                status: Arc::clone(&status),
+                backfill: config.backfill.clone(),
+                pr_comments: config.pr_comments.clone(),
+                failed_attempts: HashMap::new(),
+            };
+            let settings = Arc::clone(&settings);
+            let shutdown_rx = shutdown_rx.clone();
+            let join_handle = tokio::spawn(async move { poller.run(settings, shutdown_rx).await });
+            join_handles.push((source, join_handle));
+        }
         Self {
             shutdown_tx,
-            join_handle,
+            join_handles,
         }
     }
 
-    pub async fn shutdown(self) {
+    /// Signals the poller to stop and waits up to `deadline` for it to finish
+    /// the workflows it is copying. Downloads in progress are abandoned. Tasks
+    /// still running once the deadline passes are aborted.
+    pub async fn shutdown(self, deadline: Duration) {
         if let Err(err) = self.shutdown_tx.send(true) {
             tracing::error!("Error sending shutdown signal: {:?}", err);
         }
-        if let Err(e) = self.join_handle.await {
-            tracing::error!("GithubPoller task join error: {:?}", e);
+
+        let deadline = Instant::now() + deadline;
+        for (source, mut join_handle) in self.join_handles {
+            match timeout_at(deadline, &mut join_handle).await {
+                Ok(Ok(())) => info!("GithubPoller for {} stopped", source),
+                Ok(Err(e)) => {
+                    tracing::error!("GithubPoller task join error for {}: {:?}", source, e)
+                }
+                Err(_) => {
+                    warn!("GithubPoller for {} did not stop in time, aborting", source);
+                    join_handle.abort();
+                }
+            }
         }
     }
 }
 
-/// Poll GitHub for successful workflow runs, download and copy artifacts.
-/// This function periodically checks for a shutdown signal.
-async fn poll_github_with_shutdown(
-    performance_results_dir: PathBuf,
-    branch: String,
-    interval_seconds: u64,
-    mut shutdown_rx: watch::Receiver<bool>,
-    cache: Arc<BenchmarkCache>,
-) -> Result<(), IggyBenchDashboardServerError> {
-    let gh = IggyBenchDashboardGithubClient::new()?;
-
-    info!(
-        "Polling GitHub for successful workflow runs on branch {} every {} seconds, copying artifacts to {:?}...",
-        branch, interval_seconds, performance_results_dir
-    );
-
-    loop {
-        if *shutdown_rx.borrow() {
-            info!("Shutdown signal received, exiting poll loop");
-            break;
+/// Polling state of a single source
+pub(super) struct SourcePoller {
+    pub(super) gh: IggyBenchDashboardGithubClient,
+    pub(super) source_name: String,
+    /// Key of the source in the poller status, see [`GithubSource::status_key`]
+    status_key: String,
+    branches: Vec<String>,
+    /// Whether runs of pull requests are ingested as candidates
+    pull_requests: bool,
+    pub(super) performance_results_dir: PathBuf,
+    pub(super) pipeline: Arc<ImportPipeline>,
+    pub(super) cache: Arc<BenchmarkCache>,
+    status: Arc<PollerStatus>,
+    backfill: Option<BackfillConfig>,
+    /// Ingested pull requests are commented on if set
+    pub(super) pr_comments: Option<PrCommentConfig>,
+    /// Failed ingestion attempts per workflow run
+    pub(super) failed_attempts: HashMap<u64, u32>,
+}
+
+impl SourcePoller {
+    /// Poll GitHub for successful workflow runs of the source, download and copy artifacts.
+    /// This function periodically checks for a shutdown signal. The poll interval
+    /// is re-read on every iteration, so it follows configuration reloads. Failed
+    /// polls are retried with exponential backoff, and rate limits are waited out.
+    async fn run(
+        &mut self,
+        settings: Arc<ReloadableSettings>,
+        mut shutdown_rx: watch::Receiver<bool>,
+    ) {
+        info!(
+            "Polling GitHub {} for successful workflow runs on branches {:?} every {:?}, copying artifacts to {:?}...",
+            self.source_name,
+            self.branches,
+            settings.poll_interval(),
+            self.performance_results_dir
+        );
+
+        if let Some(backfill) = self.backfill.clone() {
+            self.run_backfill(&backfill, &mut shutdown_rx).await;
         }
 
-        trace!("Woken up...");
+        let mut consecutive_failures = 0;
+        loop {
+            if *shutdown_rx.borrow() {
+                info!("Shutdown signal received, exiting poll loop");
+                break;
+            }
+
+            trace!("Woken up...");
+            self.status.update(&self.status_key, |status| {
+                status.last_poll_at = Some(Utc::now());
+            });
+
+            let delay = match self.poll_once(&mut shutdown_rx).await {
+                Ok(()) => {
+                    consecutive_failures = 0;
+                    self.status.update(&self.status_key, |status| {
+                        status.last_success_at = Some(Utc::now());
+                        status.consecutive_failures = 0;
+                    });
+                    // Don't wake up before an exhausted quota is replenished
+                    let interval = settings.poll_interval();
+                    self.gh
+                        .rate_limit()
+                        .exhausted_for()
+                        .map_or(interval, |wait| wait.max(interval))
+                }
+                Err(IggyBenchDashboardServerError::RateLimited(wait)) => {
+                    warn!(
+                        "GitHub rate limit hit for {}, waiting {:?}",
+                        self.source_name, wait
+                    );
+                    self.status
+                        .record_error(&self.status_key, format!("Rate limited for {:?}", wait));
+                    wait
+                }
+                Err(e) => {
+                    consecutive_failures += 1;
+                    let delay = backoff(consecutive_failures);
+                    error!(
+                        "Error in poll_github for {} ({} in a row), retrying in {:?}: {}",
+                        self.source_name, consecutive_failures, delay, e
+                    );
+                    self.status.record_error(&self.status_key, e.to_string());
+                    self.status.update(&self.status_key, |status| {
+                        status.consecutive_failures = consecutive_failures;
+                    });
+                    delay
+                }
+            };
 
-        let workflows = gh.get_successful_workflow_runs(&branch).await?;
-        if workflows.is_empty() {
-            info!("No workflow runs found, sleeping...");
-            sleep(Duration::from_secs(interval_seconds)).await;
-            continue;
+            let rate_limit = self.gh.rate_limit();
+            self.status.update(&self.status_key, |status| {
+                status.rate_limit_remaining = rate_limit.remaining;
+                status.next_poll_at = chrono::Duration::from_std(delay)
+                    .ok()
+                    .map(|delay| Utc::now() + delay);
+            });
+
+            let cache = Arc::clone(&self.cache);
+            tokio::select! {
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        info!("Shutdown signal received during sleep, exiting poll loop");
+                        break;
+                    }
+                }
+                _ = cache.manifest().reimport_requested() => {
+                    info!("Re-import requested, polling {} now", self.source_name);
+                }
+                _ = sleep(delay) => {},
+            }
         }
+    }
 
-        let tags = gh.get_server_tags().await?;
+    async fn poll_once(
+        &mut self,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        let mut items = self.gh.list_new().await?;
 
-        for workflow in workflows {
-            if cache.is_gh_workflow_present(*workflow.id) {
-                trace!(
-                    "Workflow ID {} is already in the cache, skipping",
-                    workflow.id
-                );
+        // Requested re-imports get a fresh set of attempts
+        for run_id in self.cache.manifest().pending_reimports(&self.source_name) {
+            self.failed_attempts.remove(&run_id);
+            if items.iter().any(|item| item.id == run_id) {
                 continue;
             }
+            match self.gh.get_workflow_run(run_id).await {
+                Ok(workflow) => items.push(IggyBenchDashboardGithubClient::run_item(&workflow)),
+                Err(e @ IggyBenchDashboardServerError::RateLimited(_)) => return Err(e),
+                // E.g. a run deleted on GitHub, which must not fail every poll
+                Err(e) => {
+                    error!(
+                        "Failed to fetch workflow run {} of {} for re-import: {}",
+                        run_id, self.source_name, e
+                    );
+                    if let Some(mut record) = self.cache.manifest().get(&self.source_name, run_id) {
+                        record.status = IngestStatus::Failed;
+                        record.error = Some(e.to_string());
+                        record.finished_at = Some(Utc::now());
+                        self.cache.manifest().record(record).await;
+                    }
+                }
+            }
+        }
+        self.ingest_workflows(items, shutdown_rx).await?;
 
-            let sha1 = &workflow.head_sha;
-            let gitref = IggyBenchDashboardGithubClient::get_tag_for_commit(&tags, sha1)
-                .map(|tag| tag.name)
-                .unwrap_or_else(|| sha1.chars().take(8).collect());
+        if self.pull_requests {
+            self.poll_pull_requests(shutdown_rx).await?;
+        }
+        Ok(())
+    }
 
-            let workflow_id = workflow.id;
-            let artifacts_dir = gh.download_artifact(*workflow_id).await?;
-            let temp_dir = artifacts_dir.path().join("performance_results");
-            info!(
-                "Artifacts for git ref {} (sha1 {}) are in {:?}",
-                gitref, sha1, temp_dir
-            );
+    /// Imports every run of the source workflow that is missing locally, within
+    /// the configured bounds. Rate limits are waited out, other errors are
+    /// retried with backoff until [`MAX_BACKFILL_ATTEMPTS`] failed in a row, then
+    /// regular polling takes over.
+    async fn run_backfill(
+        &mut self,
+        backfill: &BackfillConfig,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) {
+        info!(
+            "Backfilling {} (since: {:?}, max runs per branch: {:?})...",
+            self.source_name, backfill.since, backfill.max_runs
+        );
 
-            let mut dir_entries = fs::read_dir(&temp_dir).await?;
-            while let Some(entry) = dir_entries.next_entry().await? {
-                if *shutdown_rx.borrow() {
-                    info!("Shutdown signal received during artifact processing, exiting poll loop");
-                    return Ok(());
+        let mut consecutive_failures = 0;
+        loop {
+            let result = self.backfill_once(backfill, shutdown_rx).await;
+            match result {
+                Ok(()) => {
+                    info!("Backfill of {} finished", self.source_name);
+                    return;
+                }
+                Err(IggyBenchDashboardServerError::RateLimited(wait)) => {
+                    warn!(
+                        "GitHub rate limit hit during backfill of {}, waiting {:?}",
+                        self.source_name, wait
+                    );
+                    self.status
+                        .record_error(&self.status_key, format!("Rate limited for {:?}", wait));
+                    tokio::select! {
+                        _ = shutdown_rx.changed() => return,
+                        _ = sleep(wait) => {},
+                    }
                 }
+                Err(e) => {
+                    consecutive_failures += 1;
+                    self.status
+                        .record_error(&self.status_key, format!("Backfill: {}", e));
+                    if consecutive_failures >= MAX_BACKFILL_ATTEMPTS {
+                        error!(
+                            "Backfill of {} failed {} times in a row, giving up: {}",
+                            self.source_name, consecutive_failures, e
+                        );
+                        return;
+                    }
 
-                let path = entry.path();
-                if path.is_dir() {
-                    let dir_name = path.file_name().and_then(|n| n.to_str()).ok_or_else(|| {
-                        IggyBenchDashboardServerError::InvalidPath("Invalid directory name".into())
-                    })?;
-                    let bench_destination_dir = performance_results_dir.join(dir_name);
-                    info!("Copying {} to {:?}", path.display(), bench_destination_dir);
-
-                    let source = path.to_str().ok_or_else(|| {
-                        IggyBenchDashboardServerError::InvalidPath("Invalid source path".into())
-                    })?;
-                    let destination = bench_destination_dir.to_str().ok_or_else(|| {
-                        IggyBenchDashboardServerError::InvalidPath(
-                            "Invalid destination path".into(),
-                        )
-                    })?;
-
-                    async_copy_dir_files(source, destination).await?;
+                    let delay = backoff(consecutive_failures);
+                    error!(
+                        "Backfill of {} failed ({} in a row), retrying in {:?}: {}",
+                        self.source_name, consecutive_failures, delay, e
+                    );
+                    tokio::select! {
+                        _ = shutdown_rx.changed() => return,
+                        _ = sleep(delay) => {},
+                    }
                 }
             }
+        }
+    }
 
-            cache.insert_gh_workflow(*workflow_id).await;
+    async fn backfill_once(
+        &mut self,
+        backfill: &BackfillConfig,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        let mut items = Vec::new();
+        for branch in &self.branches {
+            let runs = self
+                .gh
+                .get_all_successful_workflow_runs(branch, backfill.since, backfill.max_runs)
+                .await?;
+            items.extend(runs.iter().map(IggyBenchDashboardGithubClient::run_item));
         }
+        self.ingest_workflows(items, shutdown_rx).await
+    }
 
-        tokio::select! {
-            _ = shutdown_rx.changed() => {
-                if *shutdown_rx.borrow() {
-                    info!("Shutdown signal received during sleep, exiting poll loop");
-                    break;
+    /// Ingests the workflows that are neither present yet nor given up on
+    async fn ingest_workflows(
+        &mut self,
+        mut items: Vec<ArtifactItem>,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        items.retain(|item| self.is_pending(item));
+        if items.is_empty() {
+            info!(
+                "No new workflow runs found for {}, sleeping...",
+                self.source_name
+            );
+            return Ok(());
+        }
+
+        let tags = self.gh.get_server_tags().await?;
+
+        for mut item in items {
+            if *shutdown_rx.borrow() {
+                info!("Shutdown signal received, exiting poll loop");
+                return Ok(());
+            }
+
+            let sha1 = item.head_sha.clone().unwrap_or_default();
+            item.gitref = Some(IggyBenchDashboardGithubClient::gitref_for_commit(
+                &tags, &sha1,
+            ));
+            match self.pipeline.import(&self.gh, &item, shutdown_rx).await {
+                Ok(ImportOutcome::Imported) => {
+                    self.failed_attempts.remove(&item.id);
                 }
+                Ok(ImportOutcome::Abandoned) => return Ok(()),
+                // The next poll waits for the rate limit, the workflow is retried then
+                Err(e @ IggyBenchDashboardServerError::RateLimited(_)) => return Err(e),
+                // A broken workflow must not stop the others from being ingested
+                Err(e) => self.record_failure(&item, None, &e).await,
             }
-            _ = sleep(Duration::from_secs(interval_seconds)) => {},
+        }
+
+        Ok(())
+    }
+
+    /// Counts a failed attempt at ingesting `item` and gives up on it after
+    /// [`MAX_IMPORT_ATTEMPTS`]
+    pub(super) async fn record_failure(
+        &mut self,
+        item: &ArtifactItem,
+        pull_request: Option<u64>,
+        e: &IggyBenchDashboardServerError,
+    ) {
+        let attempts = self.failed_attempts.entry(item.id).or_default();
+        *attempts = ImportPipeline::failed_attempts_after(*attempts, e);
+        let attempts = *attempts;
+        self.pipeline
+            .record_failure(&self.source_name, item, pull_request, attempts, e)
+            .await;
+        self.status
+            .record_error(&self.status_key, format!("Workflow {}: {}", item.id, e));
+
+        if attempts >= MAX_IMPORT_ATTEMPTS {
+            self.status.update(&self.status_key, |status| {
+                status.skipped_workflows.push(item.id);
+            });
         }
     }
 
-    Ok(())
+    /// Whether `item` is neither ingested yet nor given up on
+    pub(super) fn is_pending(&self, item: &ArtifactItem) -> bool {
+        let attempts = self.failed_attempts.get(&item.id).copied().unwrap_or(0);
+        self.pipeline.is_pending(&self.source_name, item, attempts)
+    }
+}
+
+/// Exponential backoff with up to 50% random jitter, so that sources failing
+/// at the same time don't retry in lockstep
+fn backoff(consecutive_failures: u32) -> Duration {
+    let exponent = consecutive_failures.saturating_sub(1).min(16);
+    let backoff = BASE_BACKOFF.saturating_mul(1 << exponent).min(MAX_BACKOFF);
+    let jitter_ms = rand::random_range(0..=backoff.as_millis() as u64 / 2);
+    backoff + Duration::from_millis(jitter_ms)
 }
@@ -0,0 +1,94 @@
+use serde::Deserialize;
+use std::fmt;
+
+const DEFAULT_OWNER: &str = "apache";
+const DEFAULT_REPO: &str = "iggy";
+const DEFAULT_WORKFLOW: &str = "performance.yml";
+const DEFAULT_ARTIFACT_PATTERN: &str = "*";
+
+/// Repository workflow whose successful runs carry benchmark artifacts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GithubSource {
+    pub owner: String,
+    pub repo: String,
+    #[serde(default = "default_workflow")]
+    pub workflow: String,
+    /// Branches whose runs are ingested
+    pub branches: Vec<String>,
+    /// Artifacts whose name doesn't match are ignored, `*` matches any sequence of characters
+    #[serde(default = "default_artifact_pattern")]
+    pub artifact_pattern: String,
+    /// GitHub API base URL, e.g. of a GitHub Enterprise instance or a local mock
+    pub api_url: Option<String>,
+    /// Also ingest runs of open pull requests, as candidates compared against their base
+    #[serde(default)]
+    pub pull_requests: bool,
+}
+
+impl GithubSource {
+    /// The apache/iggy performance workflow, polled when no sources are configured
+    pub fn apache_iggy(branch: String) -> Self {
+        Self {
+            owner: DEFAULT_OWNER.to_owned(),
+            repo: DEFAULT_REPO.to_owned(),
+            workflow: default_workflow(),
+            branches: vec![branch],
+            artifact_pattern: default_artifact_pattern(),
+            api_url: None,
+            pull_requests: false,
+        }
+    }
+
+    /// Identifies the source among the polled ones. Unlike its name, which
+    /// ingested runs are recorded under, it tells apart sources of the same
+    /// workflow polled on other branches or of another GitHub instance.
+    pub fn status_key(&self) -> String {
+        let mut key = format!("{} on {}", self, self.branches.join(","));
+        if let Some(api_url) = &self.api_url {
+            key.push_str(" at ");
+            key.push_str(api_url);
+        }
+        key
+    }
+
+    pub fn matches_artifact(&self, artifact_name: &str) -> bool {
+        wildcard_match(&self.artifact_pattern, artifact_name)
+    }
+}
+
+impl fmt::Display for GithubSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.owner, self.repo, self.workflow)
+    }
+}
+
+fn default_workflow() -> String {
+    DEFAULT_WORKFLOW.to_owned()
+}
+
+fn default_artifact_pattern() -> String {
+    DEFAULT_ARTIFACT_PATTERN.to_owned()
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard in the pattern
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
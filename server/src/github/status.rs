@@ -0,0 +1,132 @@
+use super::GithubSource;
+use chrono::{DateTime, Utc};
+use dashmap::{DashMap, DashSet};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Health of the polling of a single GitHub source
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct SourceStatus {
+    /// `owner/repo/workflow`, or the name of another artifact source
+    pub source: String,
+    /// Branches polled, empty for other artifact sources
+    pub branches: Vec<String>,
+    /// GitHub API base URL, if not the public API
+    pub api_url: Option<String>,
+    pub last_poll_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// Failed polls in a row, drives the backoff
+    pub consecutive_failures: u32,
+    pub next_poll_at: Option<DateTime<Utc>>,
+    /// GitHub API requests left in the current rate limit window
+    pub rate_limit_remaining: Option<u64>,
+    /// Workflow runs given up on after failing repeatedly
+    pub skipped_workflows: Vec<u64>,
+}
+
+/// Status of every polled source, shared between the poller tasks and the API
+#[derive(Debug, Default)]
+pub struct PollerStatus {
+    sources: DashMap<String, SourceStatus>,
+    /// Keys of the configured GitHub sources, the others are artifact sources
+    github_sources: DashSet<String>,
+}
+
+impl PollerStatus {
+    /// Adds a configured GitHub source, returns the key its status is updated under
+    pub fn register_github_source(&self, source: &GithubSource) -> String {
+        let key = source.status_key();
+        self.github_sources.insert(key.clone());
+        self.sources
+            .entry(key.clone())
+            .or_insert_with(|| SourceStatus {
+                source: source.to_string(),
+                branches: source.branches.clone(),
+                api_url: source.api_url.clone(),
+                ..Default::default()
+            });
+        key
+    }
+
+    pub fn update(&self, source: &str, update: impl FnOnce(&mut SourceStatus)) {
+        let mut status = self
+            .sources
+            .entry(source.to_owned())
+            .or_insert_with(|| SourceStatus {
+                source: source.to_owned(),
+                ..Default::default()
+            });
+        update(&mut status);
+    }
+
+    pub fn record_error(&self, source: &str, error: String) {
+        self.update(source, |status| {
+            status.last_error = Some(error);
+            status.last_error_at = Some(Utc::now());
+        });
+    }
+
+    /// Whether GitHub sources are configured, even if their polling failed to start
+    pub fn github_enabled(&self) -> bool {
+        !self.github_sources.is_empty()
+    }
+
+    /// Sources sorted by name, then branches and API URL
+    pub fn snapshot(&self) -> Vec<SourceStatus> {
+        let mut sources: Vec<SourceStatus> = self
+            .sources
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        sources.sort_by(|a, b| {
+            (&a.source, &a.branches, &a.api_url).cmp(&(&b.source, &b.branches, &b.api_url))
+        });
+        sources
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_of_one_workflow_on_other_branches_are_kept_apart() {
+        let status = PollerStatus::default();
+        let master = GithubSource::apache_iggy("master".to_owned());
+        let release = GithubSource::apache_iggy("release".to_owned());
+        let enterprise = GithubSource {
+            api_url: Some("https://github.example.com/api/v3".to_owned()),
+            ..master.clone()
+        };
+        let keys = [
+            status.register_github_source(&release),
+            status.register_github_source(&master),
+            status.register_github_source(&enterprise),
+        ];
+        status.record_error(&keys[0], "failed".to_owned());
+
+        let snapshot = status.snapshot();
+        assert_eq!(snapshot.len(), 3);
+        assert!(snapshot
+            .iter()
+            .all(|source| source.source == master.to_string()));
+        assert_eq!(snapshot[0].branches, ["master"]);
+        assert_eq!(snapshot[0].api_url, None);
+        assert_eq!(snapshot[1].api_url, enterprise.api_url);
+        assert_eq!(snapshot[2].branches, ["release"]);
+        assert_eq!(snapshot[2].last_error.as_deref(), Some("failed"));
+        assert!(snapshot[0].last_error.is_none());
+    }
+
+    #[test]
+    fn other_artifact_sources_do_not_enable_github() {
+        let status = PollerStatus::default();
+        status.update("s3://results", |_| {});
+        assert!(!status.github_enabled());
+
+        status.register_github_source(&GithubSource::apache_iggy("master".to_owned()));
+        assert!(status.github_enabled());
+    }
+}
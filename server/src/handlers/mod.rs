@@ -0,0 +1,262 @@
+pub mod v1;
+pub mod v2;
+
+use crate::{
+    cache::BenchmarkCache,
+    error::IggyBenchDashboardServerError,
+    github::PollerStatus,
+    ingest::{IngestRecord, IngestStatus},
+    pagination::{paginate, paginate_benchmarks, sort_benchmarks, BenchmarkListQuery, PageQuery},
+};
+use actix_web::{web, HttpRequest};
+use iggy_bench_dashboard_shared::{pagination::Page, BenchmarkReportLight};
+use iggy_bench_report::hardware::BenchmarkHardware;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, collections::HashMap, path::Path, sync::Arc};
+use utoipa::{IntoParams, ToSchema};
+use walkdir::WalkDir;
+use zip::{write::FileOptions, ZipWriter};
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+pub struct AppState {
+    pub cache: Arc<BenchmarkCache>,
+    pub poller_status: Arc<PollerStatus>,
+    /// Bearer token of the admin endpoints, which are disabled if unset
+    pub admin_token: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct HealthStatus {
+    #[schema(example = "healthy")]
+    pub status: &'static str,
+}
+
+/// Registers all REST API handlers
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.configure(v2::configure).configure(v1::configure);
+}
+
+fn hardware_page(cache: &BenchmarkCache, query: &PageQuery) -> Result<Page<BenchmarkHardware>> {
+    let mut hardware_list: Vec<(String, BenchmarkHardware)> = cache
+        .get_hardware_configurations()
+        .into_iter()
+        .map(|hardware| (hardware_key(&hardware), hardware))
+        .collect();
+    hardware_list.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // Identical descriptions are told apart by their position among each other
+    let mut duplicates: HashMap<String, usize> = HashMap::new();
+    for (key, _) in &mut hardware_list {
+        let ordinal = duplicates.entry(key.clone()).or_default();
+        key.push_str(&format!("|{}", ordinal));
+        *ordinal += 1;
+    }
+
+    let page = paginate(hardware_list, query, |(key, _)| key.clone())?;
+    Ok(Page {
+        items: page
+            .items
+            .into_iter()
+            .map(|(_, hardware)| hardware)
+            .collect(),
+        total: page.total,
+        next_cursor: page.next_cursor,
+    })
+}
+
+/// Cursor key of a hardware, its identifier followed by the specifications
+/// that set apart hardware reported without one
+fn hardware_key(hardware: &BenchmarkHardware) -> String {
+    format!(
+        "{}|{}|{}|{}",
+        hardware.identifier.as_deref().unwrap_or_default(),
+        hardware.cpu_name,
+        hardware.os_name,
+        hardware.os_version
+    )
+}
+
+fn gitrefs_page(cache: &BenchmarkCache, hardware: &str, query: &PageQuery) -> Result<Page<String>> {
+    let mut gitrefs: Vec<String> = cache
+        .get_gitrefs_for_hardware(hardware)
+        .into_iter()
+        .collect();
+    sort_newest_first(cache, &mut gitrefs, |gitref| gitref);
+
+    paginate(gitrefs, query, |gitref| gitref.clone())
+}
+
+/// Sorts items by the history of their commit, newest first. Commits of
+/// unknown position come last, by name.
+fn sort_newest_first<T>(cache: &BenchmarkCache, items: &mut [T], gitref: impl Fn(&T) -> &str) {
+    items.sort_by_cached_key(|item| {
+        let gitref = gitref(item);
+        let rank = cache.commit_rank(gitref);
+        (rank.is_none(), Reverse(rank), gitref.to_owned())
+    });
+}
+
+fn benchmarks_for_gitref_page(
+    cache: &BenchmarkCache,
+    gitref: &str,
+    query: &BenchmarkListQuery,
+) -> Result<Page<BenchmarkReportLight>> {
+    let mut benchmarks = cache.get_benchmarks_for_gitref(gitref);
+    sort_benchmarks(
+        &mut benchmarks,
+        query.sort.unwrap_or_default(),
+        query.order.unwrap_or_default(),
+    );
+
+    paginate_benchmarks(benchmarks, query)
+}
+
+fn benchmarks_for_hardware_and_gitref_page(
+    cache: &BenchmarkCache,
+    hardware: &str,
+    gitref: &str,
+    query: &BenchmarkListQuery,
+) -> Result<Page<BenchmarkReportLight>> {
+    let mut benchmarks = cache.get_benchmarks_for_hardware_and_gitref(hardware, gitref);
+    sort_benchmarks(
+        &mut benchmarks,
+        query.sort.unwrap_or_default(),
+        query.order.unwrap_or_default(),
+    );
+
+    paginate_benchmarks(benchmarks, query)
+}
+
+/// Query parameters of the ingest history
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IngestHistoryQuery {
+    /// Page size, defaults to 100 and is capped at 1000
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Only runs with this status
+    pub status: Option<IngestStatus>,
+    /// Only runs of this `owner/repo/workflow` source
+    pub source: Option<String>,
+}
+
+fn ingest_history_page(
+    cache: &BenchmarkCache,
+    query: &IngestHistoryQuery,
+) -> Result<Page<IngestRecord>> {
+    let mut records = cache.manifest().history();
+    records.retain(|record| {
+        query.status.is_none_or(|status| record.status == status)
+            && query
+                .source
+                .as_ref()
+                .is_none_or(|source| record.source == *source)
+    });
+    let page = PageQuery {
+        limit: query.limit,
+        cursor: query.cursor.clone(),
+    };
+    paginate(records, &page, |record| {
+        format!("{}/{}", record.source, record.run_id)
+    })
+}
+
+fn trend_page(
+    cache: &BenchmarkCache,
+    hardware: &str,
+    params_identifier: &str,
+    query: &BenchmarkListQuery,
+) -> Result<Page<BenchmarkReportLight>> {
+    let mut trend_data = cache
+        .get_benchmark_trend_data(params_identifier, hardware)
+        .ok_or_else(|| {
+            IggyBenchDashboardServerError::NotFound(format!(
+                "Trend data not found for hardware '{}' with params identifier '{}'",
+                hardware, params_identifier
+            ))
+        })?;
+
+    // Trend data is ordered by git ref date unless the client asks otherwise
+    if let Some(sort) = query.sort {
+        sort_benchmarks(&mut trend_data, sort, query.order.unwrap_or_default());
+    }
+
+    paginate_benchmarks(trend_data, query)
+}
+
+/// Packs every file of a benchmark directory into an in-memory ZIP archive
+fn create_artifacts_zip(artifacts_dir: &Path) -> Result<Vec<u8>> {
+    // Create a buffer for the zip file
+    let mut zip_buffer = Vec::new();
+    {
+        let mut zip = ZipWriter::new(std::io::Cursor::new(&mut zip_buffer));
+        let options = FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o755)
+            as FileOptions<zip::write::ExtendedFileOptions>;
+
+        // Walk through all files in the directory
+        for entry in WalkDir::new(artifacts_dir) {
+            let entry = entry.map_err(|e| {
+                IggyBenchDashboardServerError::InternalError(format!(
+                    "Error walking directory: {}",
+                    e
+                ))
+            })?;
+
+            if entry.file_type().is_file() {
+                let path = entry.path();
+                let relative_path = path.strip_prefix(artifacts_dir).map_err(|e| {
+                    IggyBenchDashboardServerError::InternalError(format!(
+                        "Error creating relative path: {}",
+                        e
+                    ))
+                })?;
+
+                // Add file to zip
+                zip.start_file(
+                    relative_path.to_string_lossy().into_owned(),
+                    options.clone(),
+                )
+                .map_err(|e| {
+                    IggyBenchDashboardServerError::InternalError(format!(
+                        "Error adding file to zip: {}",
+                        e
+                    ))
+                })?;
+
+                let mut file = std::fs::File::open(path).map_err(|e| {
+                    IggyBenchDashboardServerError::InternalError(format!(
+                        "Error opening file: {}",
+                        e
+                    ))
+                })?;
+                std::io::copy(&mut file, &mut zip).map_err(|e| {
+                    IggyBenchDashboardServerError::InternalError(format!(
+                        "Error copying file to zip: {}",
+                        e
+                    ))
+                })?;
+            }
+        }
+
+        // Finish zip file
+        zip.finish().map_err(|e| {
+            IggyBenchDashboardServerError::InternalError(format!(
+                "Error finalizing zip file: {}",
+                e
+            ))
+        })?;
+    } // zip is dropped here
+
+    Ok(zip_buffer)
+}
+
+fn get_client_addr(req: &HttpRequest) -> String {
+    req.connection_info()
+        .peer_addr()
+        .unwrap_or("unknown")
+        .to_string()
+}
@@ -0,0 +1,511 @@
+use super::{
+    benchmarks_for_gitref_page, benchmarks_for_hardware_and_gitref_page, create_artifacts_zip,
+    get_client_addr, gitrefs_page, hardware_page, ingest_history_page, trend_page, AppState,
+    HealthStatus, IngestHistoryQuery,
+};
+use crate::{
+    error::{ErrorResponse, IggyBenchDashboardServerError},
+    ingest::IngestRecord,
+    openapi::BenchmarkHardwareSchema,
+    pagination::{BenchmarkListQuery, PageQuery},
+};
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use iggy_bench_dashboard_shared::{
+    commit::CommitChange, coverage::BenchmarkCoverage, hardware::HardwareBenchmark,
+    pagination::Page, BenchmarkReportLight,
+};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+/// Registers the unversioned API, kept for compatibility with existing clients
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(health_check)
+        .service(list_hardware)
+        .service(list_gitrefs_for_hardware)
+        .service(list_benchmarks_for_gitref)
+        .service(list_benchmarks_for_hardware_and_gitref)
+        .service(get_benchmark_report_full)
+        .service(get_benchmark_report_light)
+        .service(get_benchmark_trend)
+        .service(get_test_artifacts_zip)
+        .service(list_changes)
+        .service(compare_hardware)
+        .service(get_coverage)
+        .service(get_ingest_history);
+}
+
+/// Check server health
+#[utoipa::path(
+    tag = "health",
+    responses((status = 200, description = "Server is up", body = HealthStatus))
+)]
+#[get("/health")]
+pub async fn health_check(req: HttpRequest) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Health check request", client_addr);
+    Ok(HttpResponse::Ok().json(HealthStatus { status: "healthy" }))
+}
+
+/// List hardware configurations, sorted by identifier
+#[utoipa::path(
+    tag = "hardware",
+    params(PageQuery),
+    responses(
+        (status = 200, description = "Page of hardware configurations", body = Page<BenchmarkHardwareSchema>),
+        (status = 400, description = "Invalid query", body = ErrorResponse)
+    )
+)]
+#[get("/api/hardware")]
+pub async fn list_hardware(
+    data: web::Data<AppState>,
+    query: web::Query<PageQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Listing hardware configurations", client_addr);
+
+    let page = hardware_page(&data.cache, &query)?;
+
+    info!(
+        "{}: Found {} hardware configurations",
+        client_addr, page.total
+    );
+
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// List git refs benchmarked on the given hardware, newest first
+#[utoipa::path(
+    tag = "gitrefs",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        PageQuery
+    ),
+    responses(
+        (status = 200, description = "Page of git refs", body = Page<String>),
+        (status = 400, description = "Invalid query", body = ErrorResponse)
+    )
+)]
+#[get("/api/gitrefs/{hardware}")]
+pub async fn list_gitrefs_for_hardware(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    query: web::Query<PageQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Listing git refs for hardware '{}'",
+        client_addr, hardware
+    );
+
+    let page = gitrefs_page(&data.cache, &hardware, &query)?;
+
+    info!(
+        "{}: Found {} git refs for hardware '{}'",
+        client_addr, page.total, hardware
+    );
+
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// List benchmarks of a git ref across all hardware
+#[utoipa::path(
+    tag = "benchmarks",
+    params(
+        ("gitref" = String, Path, description = "Git ref"),
+        BenchmarkListQuery
+    ),
+    responses(
+        (status = 200, description = "Page of benchmark summaries", body = Page<BenchmarkReportLight>),
+        (status = 400, description = "Invalid query", body = ErrorResponse)
+    )
+)]
+#[get("/api/benchmarks/{gitref}")]
+pub async fn list_benchmarks_for_gitref(
+    data: web::Data<AppState>,
+    gitref: web::Path<String>,
+    query: web::Query<BenchmarkListQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let gitref = gitref.into_inner();
+    info!(
+        "{}: Listing benchmarks for git ref '{}'",
+        client_addr, gitref
+    );
+
+    let page = benchmarks_for_gitref_page(&data.cache, &gitref, &query)?;
+
+    info!(
+        "{}: Found {} benchmarks for git ref '{}'",
+        client_addr, page.total, gitref
+    );
+
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// List benchmarks of a git ref on the given hardware
+#[utoipa::path(
+    tag = "benchmarks",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        ("gitref" = String, Path, description = "Git ref"),
+        BenchmarkListQuery
+    ),
+    responses(
+        (status = 200, description = "Page of benchmark summaries", body = Page<BenchmarkReportLight>),
+        (status = 400, description = "Invalid query", body = ErrorResponse)
+    )
+)]
+#[get("/api/benchmarks/{hardware}/{gitref}")]
+pub async fn list_benchmarks_for_hardware_and_gitref(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<BenchmarkListQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let (hardware, gitref) = path.into_inner();
+    info!(
+        "{}: Listing benchmarks for git ref '{}'",
+        client_addr, gitref
+    );
+
+    let page = benchmarks_for_hardware_and_gitref_page(&data.cache, &hardware, &gitref, &query)?;
+    info!(
+        "{}: Found {} benchmarks for git ref '{}'",
+        client_addr, page.total, gitref
+    );
+
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Get the full benchmark report including time series
+#[utoipa::path(
+    tag = "benchmarks",
+    params(("unique_id" = String, Path, description = "Benchmark UUID")),
+    responses(
+        (status = 200, description = "Full benchmark report", body = Object),
+        (status = 404, description = "Benchmark not found", body = ErrorResponse)
+    )
+)]
+#[get("/api/benchmark/full/{unique_id}")]
+pub async fn get_benchmark_report_full(
+    data: web::Data<AppState>,
+    uuid_str: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting full benchmark report '{}'",
+        client_addr, uuid_str
+    );
+
+    let uuid = Uuid::parse_str(&uuid_str).map_err(|_| {
+        IggyBenchDashboardServerError::NotFound(format!("Invalid UUID format: '{}'", uuid_str))
+    })?;
+
+    let json_path = data.cache.get_benchmark_json_path(&uuid).ok_or_else(|| {
+        IggyBenchDashboardServerError::NotFound(format!("Benchmark '{}' not found", uuid_str))
+    })?;
+
+    let json_content = std::fs::read_to_string(&json_path).map_err(|e| {
+        IggyBenchDashboardServerError::NotFound(format!(
+            "Report file not found for '{}': {}",
+            json_path.display(),
+            e
+        ))
+    })?;
+
+    info!(
+        "{}: Found full benchmark report for uuid '{}' at '{:?}'",
+        client_addr, uuid_str, json_path
+    );
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_content))
+}
+
+/// Get the benchmark report without time series
+#[utoipa::path(
+    tag = "benchmarks",
+    params(("unique_id" = String, Path, description = "Benchmark UUID")),
+    responses(
+        (status = 200, description = "Light benchmark report", body = BenchmarkReportLight),
+        (status = 404, description = "Benchmark not found", body = ErrorResponse),
+        (status = 500, description = "Invalid UUID", body = ErrorResponse)
+    )
+)]
+#[get("/api/benchmark/light/{unique_id}")]
+pub async fn get_benchmark_report_light(
+    data: web::Data<AppState>,
+    uuid_str: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting light benchmark report '{}'",
+        client_addr, uuid_str
+    );
+
+    let uuid = match Uuid::parse_str(&uuid_str) {
+        Ok(uuid) => uuid,
+        Err(e) => {
+            warn!(
+                "{client_addr}: Invalid UUID format in light benchmark request: '{uuid_str}', error: {e}",
+            );
+            return Err(IggyBenchDashboardServerError::InvalidUuid(format!(
+                "Invalid UUID format: '{}'",
+                uuid_str
+            )));
+        }
+    };
+
+    match data.cache.get_benchmark_report_light(&uuid) {
+        Some(report) => {
+            info!(
+                "{}: Found light benchmark report for uuid '{}'",
+                client_addr, uuid_str
+            );
+            Ok(HttpResponse::Ok().json(report))
+        }
+        None => {
+            warn!(
+                "{}: Light benchmark report not found for uuid '{}'",
+                client_addr, uuid_str
+            );
+            Err(IggyBenchDashboardServerError::NotFound(format!(
+                "Benchmark '{}' not found",
+                uuid_str
+            )))
+        }
+    }
+}
+
+/// Get all runs of a benchmark on the given hardware, ordered by git ref date
+#[utoipa::path(
+    tag = "benchmarks",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        ("params_identifier" = String, Path, description = "Benchmark parameters identifier"),
+        BenchmarkListQuery
+    ),
+    responses(
+        (status = 200, description = "Page of benchmark summaries", body = Page<BenchmarkReportLight>),
+        (status = 400, description = "Invalid query", body = ErrorResponse),
+        (status = 404, description = "No runs found", body = ErrorResponse)
+    )
+)]
+#[get("/api/benchmark/trend/{hardware}/{params_identifier}")]
+pub async fn get_benchmark_trend(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<BenchmarkListQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let (hardware, params_identifier) = path.into_inner();
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting trend data for hardware '{}' with params identifier '{}'",
+        client_addr, hardware, params_identifier
+    );
+
+    let page = trend_page(&data.cache, &hardware, &params_identifier, &query)?;
+
+    info!(
+        "{}: Found {} trend data points for hardware '{}' with params identifier '{}'",
+        client_addr, page.total, hardware, params_identifier
+    );
+
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Download all artifacts of a benchmark as a ZIP archive
+#[utoipa::path(
+    tag = "artifacts",
+    params(("uuid" = String, Path, description = "Benchmark UUID")),
+    responses(
+        (status = 200, description = "ZIP archive with test artifacts", content_type = "application/zip", body = Vec<u8>),
+        (status = 404, description = "Benchmark not found", body = ErrorResponse),
+        (status = 500, description = "Invalid UUID or archive error", body = ErrorResponse)
+    )
+)]
+#[get("/api/artifacts/{uuid}")]
+pub async fn get_test_artifacts_zip(
+    data: web::Data<AppState>,
+    uuid_str: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting test artifacts for uuid '{}'",
+        client_addr, uuid_str
+    );
+
+    let uuid = match Uuid::parse_str(&uuid_str) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            warn!("{client_addr}: Invalid UUID format in test artifacts request: '{uuid_str}'",);
+            return Err(IggyBenchDashboardServerError::InvalidUuid(
+                uuid_str.to_string(),
+            ));
+        }
+    };
+
+    // Get the benchmark report to find its directory
+    let artifacts_dir = match data.cache.get_benchmark_path(&uuid) {
+        Some(path) => path,
+        None => {
+            warn!(
+                "{}: Benchmark not found for uuid '{}'",
+                client_addr, uuid_str
+            );
+            return Err(IggyBenchDashboardServerError::NotFound(format!(
+                "Benchmark '{}' not found",
+                uuid_str
+            )));
+        }
+    };
+
+    let zip_buffer = create_artifacts_zip(&artifacts_dir)?;
+
+    info!(
+        "{}: Successfully created zip archive for test artifacts of uuid '{}'",
+        client_addr, uuid_str
+    );
+
+    // Return the zip file
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .append_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"test_artifacts_{}.zip\"", uuid_str),
+        ))
+        .body(zip_buffer))
+}
+
+/// List the commits between two git refs, read from the configured git clone
+#[utoipa::path(
+    tag = "commits",
+    params(
+        ("from_gitref" = String, Path, description = "Git ref the changes start after"),
+        ("to_gitref" = String, Path, description = "Git ref the changes lead up to")
+    ),
+    responses(
+        (status = 200, description = "Commits reachable from `to_gitref` but not from `from_gitref`, newest first", body = Vec<CommitChange>),
+        (status = 404, description = "A git ref is not in the git clone", body = ErrorResponse),
+        (status = 500, description = "No git clone is configured", body = ErrorResponse)
+    )
+)]
+#[get("/api/changes/{from_gitref}/{to_gitref}")]
+pub async fn list_changes(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let (from_gitref, to_gitref) = path.into_inner();
+    info!(
+        "{}: Listing changes from '{}' to '{}'",
+        client_addr, from_gitref, to_gitref
+    );
+
+    let cache = Arc::clone(&data.cache);
+    let changes = web::block(move || cache.get_changes(&from_gitref, &to_gitref))
+        .await
+        .map_err(|e| IggyBenchDashboardServerError::InternalError(e.to_string()))??;
+    Ok(HttpResponse::Ok().json(changes))
+}
+
+/// Compare a benchmark of a git ref across all hardware it ran on
+#[utoipa::path(
+    tag = "hardware",
+    params(
+        ("params_identifier" = String, Path, description = "Benchmark parameters identifier"),
+        ("gitref" = String, Path, description = "Git ref")
+    ),
+    responses(
+        (status = 200, description = "Latest report of the benchmark on every hardware, sorted by identifier", body = Vec<HardwareBenchmark>),
+        (status = 404, description = "Benchmark did not run on this git ref", body = ErrorResponse)
+    )
+)]
+#[get("/api/compare/hardware/{params_identifier}/{gitref}")]
+pub async fn compare_hardware(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let (params_identifier, gitref) = path.into_inner();
+    info!(
+        "{}: Comparing hardware for benchmark '{}' at git ref '{}'",
+        client_addr, params_identifier, gitref
+    );
+
+    let comparison = data
+        .cache
+        .get_hardware_comparison(&params_identifier, &gitref);
+    if comparison.is_empty() {
+        return Err(IggyBenchDashboardServerError::NotFound(format!(
+            "Benchmark '{}' did not run on git ref '{}'",
+            params_identifier, gitref
+        )));
+    }
+    Ok(HttpResponse::Ok().json(comparison))
+}
+
+/// Runs of every benchmark at every commit benchmarked on the hardware
+#[utoipa::path(
+    tag = "benchmarks",
+    params(("hardware" = String, Path, description = "Hardware identifier")),
+    responses(
+        (status = 200, description = "Benchmark by git ref matrix of run counts, git refs oldest first", body = BenchmarkCoverage),
+        (status = 404, description = "Hardware has no benchmarks", body = ErrorResponse)
+    )
+)]
+#[get("/api/coverage/{hardware}")]
+pub async fn get_coverage(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting benchmark coverage for hardware '{}'",
+        client_addr, hardware
+    );
+
+    if data.cache.get_gitrefs_for_hardware(&hardware).is_empty() {
+        return Err(IggyBenchDashboardServerError::NotFound(format!(
+            "No benchmarks on hardware '{}'",
+            hardware
+        )));
+    }
+    Ok(HttpResponse::Ok().json(data.cache.get_coverage(&hardware)))
+}
+
+/// List ingested workflow runs, most recently ingested first
+#[utoipa::path(
+    tag = "ingest",
+    params(IngestHistoryQuery),
+    responses(
+        (status = 200, description = "Page of ingest records", body = Page<IngestRecord>),
+        (status = 400, description = "Invalid query", body = ErrorResponse)
+    )
+)]
+#[get("/api/ingest/history")]
+pub async fn get_ingest_history(
+    data: web::Data<AppState>,
+    query: web::Query<IngestHistoryQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Requesting ingest history", client_addr);
+
+    let page = ingest_history_page(&data.cache, &query)?;
+    Ok(HttpResponse::Ok().json(page))
+}
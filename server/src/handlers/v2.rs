@@ -0,0 +1,1007 @@
+use super::{
+    benchmarks_for_gitref_page, benchmarks_for_hardware_and_gitref_page, create_artifacts_zip,
+    get_client_addr, gitrefs_page, hardware_page, ingest_history_page, sort_newest_first,
+    trend_page, AppState, IngestHistoryQuery,
+};
+use crate::{
+    cache::BenchmarkCache,
+    error::{ApiError, ErrorEnvelope, IggyBenchDashboardServerError},
+    github::SourceStatus,
+    ingest::IngestRecord,
+    openapi::BenchmarkHardwareSchema,
+    pagination::{paginate, BenchmarkListQuery, PageQuery},
+};
+use actix_web::{get, http::header, post, web, HttpRequest, HttpResponse};
+use iggy_bench_dashboard_shared::{
+    candidate::{CandidateComparison, PullRequestCandidate},
+    change_grid::{ChangeGrid, GridMetric},
+    commit::{CommitChange, CommitIdentity},
+    coverage::BenchmarkCoverage,
+    hardware::{HardwareBenchmark, HardwareEntry},
+    pagination::Page,
+    performance_index::PerformanceIndex,
+    BenchmarkReportLight,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+type Result<T> = std::result::Result<T, ApiError>;
+
+/// Prefix of the versioned API
+pub const API_V2_PREFIX: &str = "/api/v2";
+
+/// Start of the `not_found` message of paths under the prefix that match no endpoint
+pub const UNMATCHED_ENDPOINT: &str = "No such endpoint";
+
+/// Registers the versioned API. Unlike the unversioned API it reports bad input
+/// with 400, missing resources with 404 and a loading cache with 503, always
+/// using the [`ErrorEnvelope`] body.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope(API_V2_PREFIX)
+            .app_data(web::QueryConfig::default().error_handler(|err, _req| {
+                ApiError(IggyBenchDashboardServerError::InvalidQuery(err.to_string())).into()
+            }))
+            .app_data(web::PathConfig::default().error_handler(|err, _req| {
+                ApiError(IggyBenchDashboardServerError::InvalidPath(err.to_string())).into()
+            }))
+            .service(list_hardware)
+            .service(list_hardware_registry)
+            .service(get_hardware_registry_entry)
+            .service(list_gitrefs_for_hardware)
+            .service(list_commits_for_hardware)
+            .service(get_commit)
+            .service(list_changes)
+            .service(list_benchmarks_for_gitref)
+            .service(list_benchmarks_for_hardware_and_gitref)
+            .service(get_benchmark_report_full)
+            .service(get_benchmark_report_light)
+            .service(get_benchmark_trend)
+            .service(get_test_artifacts_zip)
+            .service(get_poller_status)
+            .service(list_candidates)
+            .service(get_candidate_comparison)
+            .service(compare_hardware)
+            .service(get_performance_index_trend)
+            .service(get_performance_index)
+            .service(get_coverage)
+            .service(get_change_grid)
+            .service(get_ingest_history)
+            .service(reimport_workflow_run)
+            .default_service(web::to(|req: HttpRequest| async move {
+                Err::<HttpResponse, _>(ApiError(IggyBenchDashboardServerError::NotFound(format!(
+                    "{}: {}",
+                    UNMATCHED_ENDPOINT,
+                    req.path()
+                ))))
+            })),
+    );
+}
+
+/// List hardware configurations, sorted by identifier
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(PageQuery),
+    responses(
+        (status = 200, description = "Page of hardware configurations", body = Page<BenchmarkHardwareSchema>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/hardware")]
+pub async fn list_hardware(
+    data: web::Data<AppState>,
+    query: web::Query<PageQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Listing hardware configurations (v2)", client_addr);
+
+    ensure_ready(&data.cache)?;
+    let page = hardware_page(&data.cache, &query)?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Query parameters of the hardware registry
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HardwareRegistryQuery {
+    /// Page size, defaults to 100 and is capped at 1000
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Also list retired hardware
+    #[serde(default)]
+    pub include_retired: bool,
+}
+
+/// List hardware with display names, merged identifiers and specification
+/// differences, sorted by identifier. Retired hardware is left out by default.
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(HardwareRegistryQuery),
+    responses(
+        (status = 200, description = "Page of hardware", body = Page<HardwareEntry>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/hardware/registry")]
+pub async fn list_hardware_registry(
+    data: web::Data<AppState>,
+    query: web::Query<HardwareRegistryQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Listing hardware registry (v2)", client_addr);
+
+    ensure_ready(&data.cache)?;
+    let entries = data.cache.get_hardware_entries(query.include_retired);
+    let page = PageQuery {
+        limit: query.limit,
+        cursor: query.cursor.clone(),
+    };
+    let page = paginate(entries, &page, |entry| entry.identifier.clone())?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Get hardware by its identifier or any identifier merged into it
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("hardware" = String, Path, description = "Hardware identifier or alias")),
+    responses(
+        (status = 200, description = "Hardware", body = HardwareEntry),
+        (status = 404, description = "No benchmarks on this hardware", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/hardware/registry/{hardware}")]
+pub async fn get_hardware_registry_entry(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Requesting hardware '{}' (v2)", client_addr, hardware);
+
+    ensure_ready(&data.cache)?;
+    let entry = data.cache.get_hardware_entry(&hardware).ok_or_else(|| {
+        ApiError(IggyBenchDashboardServerError::NotFound(format!(
+            "No benchmarks on hardware '{}'",
+            hardware
+        )))
+    })?;
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+/// List git refs benchmarked on the given hardware, newest first
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        PageQuery
+    ),
+    responses(
+        (status = 200, description = "Page of git refs", body = Page<String>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/gitrefs/{hardware}")]
+pub async fn list_gitrefs_for_hardware(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    query: web::Query<PageQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Listing git refs for hardware '{}' (v2)",
+        client_addr, hardware
+    );
+
+    ensure_ready(&data.cache)?;
+    let page = gitrefs_page(&data.cache, &hardware, &query)?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// List commits benchmarked on the given hardware with their aliases, newest first
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        PageQuery
+    ),
+    responses(
+        (status = 200, description = "Page of commits", body = Page<CommitIdentity>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/commits/{hardware}")]
+pub async fn list_commits_for_hardware(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    query: web::Query<PageQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Listing commits for hardware '{}' (v2)",
+        client_addr, hardware
+    );
+
+    ensure_ready(&data.cache)?;
+    let mut commits = data.cache.get_commits_for_hardware(&hardware);
+    sort_newest_first(&data.cache, &mut commits, |commit| &commit.name);
+    let page = paginate(commits, &query, |commit| commit.name.clone())?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Resolve a tag, short or full SHA or other git ref to its commit
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("gitref" = String, Path, description = "Any alias of the commit, or a SHA prefix")),
+    responses(
+        (status = 200, description = "Commit and its aliases", body = CommitIdentity),
+        (status = 404, description = "No commit known under this git ref", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/commit/{gitref}")]
+pub async fn get_commit(
+    data: web::Data<AppState>,
+    gitref: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Resolving git ref '{}' (v2)", client_addr, gitref);
+
+    ensure_ready(&data.cache)?;
+    let commit = data.cache.get_commit(&gitref).ok_or_else(|| {
+        ApiError(IggyBenchDashboardServerError::NotFound(format!(
+            "No commit known as '{}'",
+            gitref
+        )))
+    })?;
+    Ok(HttpResponse::Ok().json(commit))
+}
+
+/// List the commits between two git refs, read from the configured git clone
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("from_gitref" = String, Path, description = "Git ref the changes start after"),
+        ("to_gitref" = String, Path, description = "Git ref the changes lead up to")
+    ),
+    responses(
+        (status = 200, description = "Commits reachable from `to_gitref` but not from `from_gitref`, newest first", body = Vec<CommitChange>),
+        (status = 404, description = "A git ref is not in the git clone", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading, or no git clone is configured", body = ErrorEnvelope)
+    )
+)]
+#[get("/changes/{from_gitref}/{to_gitref}")]
+pub async fn list_changes(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let (from_gitref, to_gitref) = path.into_inner();
+    info!(
+        "{}: Listing changes from '{}' to '{}' (v2)",
+        client_addr, from_gitref, to_gitref
+    );
+
+    ensure_ready(&data.cache)?;
+    let cache = Arc::clone(&data.cache);
+    let changes = web::block(move || cache.get_changes(&from_gitref, &to_gitref))
+        .await
+        .map_err(|e| IggyBenchDashboardServerError::InternalError(e.to_string()))??;
+    Ok(HttpResponse::Ok().json(changes))
+}
+
+/// List benchmarks of a git ref across all hardware
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("gitref" = String, Path, description = "Git ref"),
+        BenchmarkListQuery
+    ),
+    responses(
+        (status = 200, description = "Page of benchmark summaries", body = Page<BenchmarkReportLight>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/benchmarks/{gitref}")]
+pub async fn list_benchmarks_for_gitref(
+    data: web::Data<AppState>,
+    gitref: web::Path<String>,
+    query: web::Query<BenchmarkListQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Listing benchmarks for git ref '{}' (v2)",
+        client_addr, gitref
+    );
+
+    ensure_ready(&data.cache)?;
+    let page = benchmarks_for_gitref_page(&data.cache, &gitref, &query)?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// List benchmarks of a git ref on the given hardware
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        ("gitref" = String, Path, description = "Git ref"),
+        BenchmarkListQuery
+    ),
+    responses(
+        (status = 200, description = "Page of benchmark summaries", body = Page<BenchmarkReportLight>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/benchmarks/{hardware}/{gitref}")]
+pub async fn list_benchmarks_for_hardware_and_gitref(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<BenchmarkListQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let (hardware, gitref) = path.into_inner();
+    info!(
+        "{}: Listing benchmarks for hardware '{}' and git ref '{}' (v2)",
+        client_addr, hardware, gitref
+    );
+
+    ensure_ready(&data.cache)?;
+    let page = benchmarks_for_hardware_and_gitref_page(&data.cache, &hardware, &gitref, &query)?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Get the full benchmark report including time series
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("uuid" = String, Path, description = "Benchmark UUID")),
+    responses(
+        (status = 200, description = "Full benchmark report", body = Object),
+        (status = 400, description = "Invalid UUID", body = ErrorEnvelope),
+        (status = 404, description = "Benchmark not found", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/benchmark/full/{uuid}")]
+pub async fn get_benchmark_report_full(
+    data: web::Data<AppState>,
+    uuid_str: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting full benchmark report '{}' (v2)",
+        client_addr, uuid_str
+    );
+
+    let uuid = parse_uuid(&uuid_str)?;
+    ensure_ready(&data.cache)?;
+
+    let json_path = data
+        .cache
+        .get_benchmark_json_path(&uuid)
+        .ok_or_else(|| benchmark_not_found(&uuid))?;
+
+    let json_content = tokio::fs::read_to_string(&json_path).await.map_err(|e| {
+        warn!(
+            "{}: Failed to read report file '{}': {}",
+            client_addr,
+            json_path.display(),
+            e
+        );
+        benchmark_not_found(&uuid)
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json_content))
+}
+
+/// Get the benchmark report without time series
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("uuid" = String, Path, description = "Benchmark UUID")),
+    responses(
+        (status = 200, description = "Light benchmark report", body = BenchmarkReportLight),
+        (status = 400, description = "Invalid UUID", body = ErrorEnvelope),
+        (status = 404, description = "Benchmark not found", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/benchmark/light/{uuid}")]
+pub async fn get_benchmark_report_light(
+    data: web::Data<AppState>,
+    uuid_str: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting light benchmark report '{}' (v2)",
+        client_addr, uuid_str
+    );
+
+    let uuid = parse_uuid(&uuid_str)?;
+    ensure_ready(&data.cache)?;
+
+    let report = data
+        .cache
+        .get_benchmark_report_light(&uuid)
+        .ok_or_else(|| benchmark_not_found(&uuid))?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Get all runs of a benchmark on the given hardware, ordered by git ref date
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        ("params_identifier" = String, Path, description = "Benchmark parameters identifier"),
+        BenchmarkListQuery
+    ),
+    responses(
+        (status = 200, description = "Page of benchmark summaries", body = Page<BenchmarkReportLight>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 404, description = "No runs found", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/benchmark/trend/{hardware}/{params_identifier}")]
+pub async fn get_benchmark_trend(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<BenchmarkListQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let (hardware, params_identifier) = path.into_inner();
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting trend data for hardware '{}' with params identifier '{}' (v2)",
+        client_addr, hardware, params_identifier
+    );
+
+    ensure_ready(&data.cache)?;
+    let page = trend_page(&data.cache, &hardware, &params_identifier, &query)?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Download all artifacts of a benchmark as a ZIP archive
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("uuid" = String, Path, description = "Benchmark UUID")),
+    responses(
+        (status = 200, description = "ZIP archive with test artifacts", content_type = "application/zip", body = Vec<u8>),
+        (status = 400, description = "Invalid UUID", body = ErrorEnvelope),
+        (status = 404, description = "Benchmark not found", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/artifacts/{uuid}")]
+pub async fn get_test_artifacts_zip(
+    data: web::Data<AppState>,
+    uuid_str: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting test artifacts for uuid '{}' (v2)",
+        client_addr, uuid_str
+    );
+
+    let uuid = parse_uuid(&uuid_str)?;
+    ensure_ready(&data.cache)?;
+
+    let artifacts_dir = data
+        .cache
+        .get_benchmark_path(&uuid)
+        .ok_or_else(|| benchmark_not_found(&uuid))?;
+    let zip_buffer = create_artifacts_zip(&artifacts_dir)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .append_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"test_artifacts_{}.zip\"", uuid),
+        ))
+        .body(zip_buffer))
+}
+
+/// Polling state of the GitHub sources
+#[derive(Serialize, ToSchema)]
+pub struct PollerStatusResponse {
+    /// False when no GitHub sources are configured
+    pub enabled: bool,
+    pub sources: Vec<SourceStatus>,
+}
+
+/// Get the state of the GitHub poller: last success, last error and backoff per source
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    responses((status = 200, description = "GitHub poller state", body = PollerStatusResponse))
+)]
+#[get("/poller/status")]
+pub async fn get_poller_status(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Requesting GitHub poller status (v2)", client_addr);
+
+    Ok(HttpResponse::Ok().json(PollerStatusResponse {
+        enabled: data.poller_status.github_enabled(),
+        sources: data.poller_status.snapshot(),
+    }))
+}
+
+/// List pull request candidates, most recently ingested first
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(PageQuery),
+    responses(
+        (status = 200, description = "Page of pull request candidates", body = Page<PullRequestCandidate>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/candidates")]
+pub async fn list_candidates(
+    data: web::Data<AppState>,
+    query: web::Query<PageQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Listing pull request candidates (v2)", client_addr);
+
+    ensure_ready(&data.cache)?;
+    let page = paginate(data.cache.get_candidates(), &query, |candidate| {
+        format!("{}/{}", candidate.source, candidate.pr_number)
+    })?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Query parameters of a candidate comparison
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CandidateQuery {
+    /// `owner/repo/workflow` source of the pull request, needed when pull requests of several sources have this number
+    pub source: Option<String>,
+}
+
+/// Compare the benchmarks of a pull request with the closest mainline git ref on each hardware
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("pr_number" = u64, Path, description = "Pull request number"), CandidateQuery),
+    responses(
+        (status = 200, description = "Candidate compared with its baseline", body = CandidateComparison),
+        (status = 400, description = "Invalid pull request number", body = ErrorEnvelope),
+        (status = 404, description = "No candidate for the pull request", body = ErrorEnvelope),
+        (status = 409, description = "Pull requests of several sources have this number and no source was given", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/candidates/{pr_number}")]
+pub async fn get_candidate_comparison(
+    data: web::Data<AppState>,
+    pr_number: web::Path<u64>,
+    query: web::Query<CandidateQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let pr_number = pr_number.into_inner();
+    info!(
+        "{}: Requesting comparison of pull request #{} (v2)",
+        client_addr, pr_number
+    );
+
+    ensure_ready(&data.cache)?;
+    let source = data
+        .cache
+        .candidate_source(pr_number, query.source.as_deref())?;
+    let comparison = data
+        .cache
+        .compare_candidate(&source, pr_number)
+        .ok_or_else(|| {
+            ApiError(IggyBenchDashboardServerError::NotFound(format!(
+                "No candidate for pull request #{}",
+                pr_number
+            )))
+        })?;
+    Ok(HttpResponse::Ok().json(comparison))
+}
+
+/// Compare a benchmark of a git ref across all hardware it ran on
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("params_identifier" = String, Path, description = "Benchmark parameters identifier"),
+        ("gitref" = String, Path, description = "Git ref")
+    ),
+    responses(
+        (status = 200, description = "Latest report of the benchmark on every hardware, sorted by identifier", body = Vec<HardwareBenchmark>),
+        (status = 404, description = "Benchmark did not run on this git ref", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/compare/hardware/{params_identifier}/{gitref}")]
+pub async fn compare_hardware(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let (params_identifier, gitref) = path.into_inner();
+    info!(
+        "{}: Comparing hardware for benchmark '{}' at git ref '{}' (v2)",
+        client_addr, params_identifier, gitref
+    );
+
+    ensure_ready(&data.cache)?;
+    let comparison = data
+        .cache
+        .get_hardware_comparison(&params_identifier, &gitref);
+    if comparison.is_empty() {
+        return Err(ApiError(IggyBenchDashboardServerError::NotFound(format!(
+            "Benchmark '{}' did not run on git ref '{}'",
+            params_identifier, gitref
+        ))));
+    }
+    Ok(HttpResponse::Ok().json(comparison))
+}
+
+/// Query parameters of the performance index
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PerformanceIndexQuery {
+    /// Git ref compared against, the oldest commit benchmarked on the hardware if unset
+    pub baseline: Option<String>,
+}
+
+/// Performance index of every commit benchmarked on the hardware against a baseline, oldest first
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        PerformanceIndexQuery
+    ),
+    responses(
+        (status = 200, description = "Index of every commit with benchmarks in common with the baseline", body = Vec<PerformanceIndex>),
+        (status = 404, description = "Hardware or baseline has no benchmarks", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/index/{hardware}")]
+pub async fn get_performance_index_trend(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    query: web::Query<PerformanceIndexQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting performance index trend for hardware '{}' (v2)",
+        client_addr, hardware
+    );
+
+    ensure_ready(&data.cache)?;
+    ensure_benchmarked(&data.cache, &hardware, query.baseline.as_deref())?;
+    let trend = data
+        .cache
+        .get_performance_index_trend(&hardware, query.baseline.as_deref());
+    Ok(HttpResponse::Ok().json(trend))
+}
+
+/// Performance index of a git ref against a baseline on the hardware
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        ("gitref" = String, Path, description = "Git ref"),
+        PerformanceIndexQuery
+    ),
+    responses(
+        (status = 200, description = "Index of the git ref", body = PerformanceIndex),
+        (status = 404, description = "No benchmarks in common with the baseline", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/index/{hardware}/{gitref}")]
+pub async fn get_performance_index(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<PerformanceIndexQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let (hardware, gitref) = path.into_inner();
+    info!(
+        "{}: Requesting performance index of '{}' on hardware '{}' (v2)",
+        client_addr, gitref, hardware
+    );
+
+    ensure_ready(&data.cache)?;
+    ensure_benchmarked(&data.cache, &hardware, query.baseline.as_deref())?;
+    let baseline = query
+        .baseline
+        .clone()
+        .or_else(|| data.cache.get_default_index_baseline(&hardware))
+        .unwrap_or_default();
+    let index = data
+        .cache
+        .get_performance_index(&hardware, &gitref, &baseline)
+        .ok_or_else(|| {
+            ApiError(IggyBenchDashboardServerError::NotFound(format!(
+                "Git ref '{}' has no benchmarks in common with '{}' on hardware '{}'",
+                gitref, baseline, hardware
+            )))
+        })?;
+    Ok(HttpResponse::Ok().json(index))
+}
+
+/// Runs of every benchmark at every commit benchmarked on the hardware
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("hardware" = String, Path, description = "Hardware identifier")),
+    responses(
+        (status = 200, description = "Benchmark by git ref matrix of run counts, git refs oldest first", body = BenchmarkCoverage),
+        (status = 404, description = "Hardware has no benchmarks", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/coverage/{hardware}")]
+pub async fn get_coverage(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting benchmark coverage for hardware '{}' (v2)",
+        client_addr, hardware
+    );
+
+    ensure_ready(&data.cache)?;
+    ensure_benchmarked(&data.cache, &hardware, None)?;
+    Ok(HttpResponse::Ok().json(data.cache.get_coverage(&hardware)))
+}
+
+/// Git refs shown by the change grid if the query does not say
+const DEFAULT_GRID_GITREFS: usize = 10;
+
+/// Most git refs the change grid shows
+const MAX_GRID_GITREFS: usize = 100;
+
+/// Query parameters of the change grid
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChangeGridQuery {
+    /// Metric compared between git refs, defaults to P99 latency
+    pub metric: Option<GridMetric>,
+    /// Number of most recent git refs, defaults to 10 and is capped at 100
+    pub gitrefs: Option<usize>,
+}
+
+/// Percent change of a metric of every benchmark over the last git refs benchmarked on the hardware
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(
+        ("hardware" = String, Path, description = "Hardware identifier"),
+        ChangeGridQuery
+    ),
+    responses(
+        (status = 200, description = "Benchmark by git ref grid of changes against the previous git ref, git refs oldest first", body = ChangeGrid),
+        (status = 404, description = "Hardware has no benchmarks", body = ErrorEnvelope),
+        (status = 503, description = "Cache is loading", body = ErrorEnvelope)
+    )
+)]
+#[get("/change-grid/{hardware}")]
+pub async fn get_change_grid(
+    data: web::Data<AppState>,
+    hardware: web::Path<String>,
+    query: web::Query<ChangeGridQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!(
+        "{}: Requesting change grid for hardware '{}' (v2)",
+        client_addr, hardware
+    );
+
+    ensure_ready(&data.cache)?;
+    ensure_benchmarked(&data.cache, &hardware, None)?;
+    let gitrefs = query
+        .gitrefs
+        .unwrap_or(DEFAULT_GRID_GITREFS)
+        .clamp(1, MAX_GRID_GITREFS);
+    let grid = data
+        .cache
+        .get_change_grid(&hardware, query.metric.unwrap_or_default(), gitrefs);
+    Ok(HttpResponse::Ok().json(grid))
+}
+
+/// List ingested workflow runs, most recently ingested first
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(IngestHistoryQuery),
+    responses(
+        (status = 200, description = "Page of ingest records", body = Page<IngestRecord>),
+        (status = 400, description = "Invalid query", body = ErrorEnvelope)
+    )
+)]
+#[get("/ingest/history")]
+pub async fn get_ingest_history(
+    data: web::Data<AppState>,
+    query: web::Query<IngestHistoryQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    info!("{}: Requesting ingest history (v2)", client_addr);
+
+    let page = ingest_history_page(&data.cache, &query)?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Query parameters of a re-import request
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReimportQuery {
+    /// `owner/repo/workflow` source of the run, needed when several sources ingested a run with this ID
+    pub source: Option<String>,
+}
+
+/// Import a workflow run again, replacing its benchmarks. Requires the admin token.
+#[utoipa::path(
+    context_path = "/api/v2",
+    tag = "v2",
+    params(("run_id" = u64, Path, description = "GitHub workflow run ID"), ReimportQuery),
+    security(("admin_token" = [])),
+    responses(
+        (status = 202, description = "Re-import requested, picked up by the poller", body = IngestRecord),
+        (status = 400, description = "Invalid run ID", body = ErrorEnvelope),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorEnvelope),
+        (status = 404, description = "Run was never ingested", body = ErrorEnvelope),
+        (status = 409, description = "Run came from a source that can't import it again, e.g. an inbox, S3 or HTTP index source, or a GitHub source that is no longer polled, or several sources ingested a run with this ID and no source was given", body = ErrorEnvelope)
+    )
+)]
+#[post("/ingest/{run_id}/reimport")]
+pub async fn reimport_workflow_run(
+    data: web::Data<AppState>,
+    run_id: web::Path<u64>,
+    query: web::Query<ReimportQuery>,
+    req: HttpRequest,
+) -> Result<HttpResponse> {
+    let client_addr = get_client_addr(&req);
+    let run_id = run_id.into_inner();
+    info!(
+        "{}: Requesting re-import of workflow run {} (v2)",
+        client_addr, run_id
+    );
+
+    ensure_admin(&data, &req)?;
+    let record = data
+        .cache
+        .manifest()
+        .request_reimport(run_id, query.source.as_deref())
+        .await?;
+    Ok(HttpResponse::Accepted().json(record))
+}
+
+/// Checks the `Authorization: Bearer` header against the configured admin token
+fn ensure_admin(data: &AppState, req: &HttpRequest) -> Result<()> {
+    let Some(admin_token) = &data.admin_token else {
+        return Err(ApiError(IggyBenchDashboardServerError::Unauthorized(
+            "Admin endpoints are disabled, no admin token is configured".into(),
+        )));
+    };
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        Some(token) if constant_time_eq(token.as_bytes(), admin_token.as_bytes()) => Ok(()),
+        _ => {
+            warn!(
+                "{}: Rejected admin request with missing or wrong token",
+                get_client_addr(req)
+            );
+            Err(ApiError(IggyBenchDashboardServerError::Unauthorized(
+                "Missing or wrong admin token".into(),
+            )))
+        }
+    }
+}
+
+/// Compares without returning early, so the token can't be guessed from response times
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn ensure_ready(cache: &BenchmarkCache) -> Result<()> {
+    if cache.is_loading() {
+        return Err(ApiError(IggyBenchDashboardServerError::Unavailable(
+            "Benchmark cache is loading, retry shortly".into(),
+        )));
+    }
+    Ok(())
+}
+
+/// Fails with 404 unless the hardware, and the baseline on it if given, have benchmarks
+fn ensure_benchmarked(
+    cache: &BenchmarkCache,
+    hardware: &str,
+    baseline: Option<&str>,
+) -> Result<()> {
+    let gitrefs = cache.get_gitrefs_for_hardware(hardware);
+    if gitrefs.is_empty() {
+        return Err(ApiError(IggyBenchDashboardServerError::NotFound(format!(
+            "No benchmarks on hardware '{}'",
+            hardware
+        ))));
+    }
+    if let Some(baseline) = baseline {
+        if cache
+            .get_benchmarks_for_hardware_and_gitref(hardware, baseline)
+            .is_empty()
+        {
+            return Err(ApiError(IggyBenchDashboardServerError::NotFound(format!(
+                "Baseline '{}' has no benchmarks on hardware '{}'",
+                baseline, hardware
+            ))));
+        }
+    }
+    Ok(())
+}
+
+fn parse_uuid(uuid_str: &str) -> Result<Uuid> {
+    Uuid::parse_str(uuid_str).map_err(|_| {
+        ApiError(IggyBenchDashboardServerError::InvalidUuid(format!(
+            "Invalid UUID format: '{}'",
+            uuid_str
+        )))
+    })
+}
+
+fn benchmark_not_found(uuid: &Uuid) -> ApiError {
+    ApiError(IggyBenchDashboardServerError::NotFound(format!(
+        "Benchmark '{}' not found",
+        uuid
+    )))
+}
@@ -0,0 +1,250 @@
+use crate::error::IggyBenchDashboardServerError;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tempfile::{NamedTempFile, TempDir};
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+use zip::ZipArchive;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+/// Directory holding the benchmark directories inside GitHub artifacts
+const PERFORMANCE_RESULTS_DIR: &str = "performance_results";
+
+/// Bounds of an artifact, checked while downloading and unpacking it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArtifactLimits {
+    /// Size of the archive, and of its unpacked content
+    pub max_total_bytes: u64,
+    /// Files and directories in the archive
+    pub max_entries: usize,
+    /// Unpacked size of a single file
+    pub max_file_bytes: u64,
+}
+
+impl Default for ArtifactLimits {
+    fn default() -> Self {
+        Self {
+            max_total_bytes: 512 * 1024 * 1024,
+            max_entries: 10_000,
+            max_file_bytes: 128 * 1024 * 1024,
+        }
+    }
+}
+
+/// Streams a ZIP archive served over HTTP into a temporary file, the download
+/// is abandoned as soon as it exceeds the size limit
+pub async fn download_zip(
+    mut response: reqwest::Response,
+    limits: &ArtifactLimits,
+) -> Result<NamedTempFile> {
+    if let Some(length) = response.content_length() {
+        check_archive_size(length, limits)?;
+    }
+
+    let archive = NamedTempFile::new()?;
+    let mut file = tokio::fs::File::from_std(archive.as_file().try_clone()?);
+    let mut size = 0;
+    while let Some(chunk) = response.chunk().await? {
+        size += chunk.len() as u64;
+        check_archive_size(size, limits)?;
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+
+    info!("Downloaded {} bytes to {:?}", size, archive.path());
+    Ok(archive)
+}
+
+/// Rejects an archive of `size` bytes if it is over the limit
+pub fn check_archive_size(size: u64, limits: &ArtifactLimits) -> Result<()> {
+    if size > limits.max_total_bytes {
+        return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+            "Archive of {} bytes exceeds the limit of {} bytes",
+            size, limits.max_total_bytes
+        )));
+    }
+    Ok(())
+}
+
+/// Unpacks a ZIP archive into a new temporary directory. Archives over the
+/// limits, holding symbolic links or paths leaving the directory are rejected.
+pub fn unpack_zip(archive: &Path, limits: &ArtifactLimits) -> Result<TempDir> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    if zip.len() > limits.max_entries {
+        return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+            "Archive has {} entries, the limit is {}",
+            zip.len(),
+            limits.max_entries
+        )));
+    }
+
+    let temp_dir = TempDir::new()?;
+    let output_dir = temp_dir.path();
+    info!("Unzipping to directory: {:?}", output_dir);
+
+    let mut total_size = 0;
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i)?;
+        if file.is_symlink() {
+            return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+                "Symbolic link {}",
+                file.name()
+            )));
+        }
+        let Some(relative_path) = file.enclosed_name() else {
+            return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+                "Unexpected path {}",
+                file.name()
+            )));
+        };
+        let outpath = output_dir.join(relative_path);
+
+        if file.is_dir() {
+            std::fs::create_dir_all(&outpath)?;
+            continue;
+        }
+        if file.size() > limits.max_file_bytes {
+            return Err(file_too_large(file.name(), limits));
+        }
+        if let Some(parent) = outpath.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // The declared size may lie, so the copy itself is bounded too
+        let mut outfile = File::create(&outpath)?;
+        let written = std::io::copy(
+            &mut (&mut file).take(limits.max_file_bytes + 1),
+            &mut outfile,
+        )?;
+        if written > limits.max_file_bytes {
+            return Err(file_too_large(file.name(), limits));
+        }
+        total_size += written;
+        if total_size > limits.max_total_bytes {
+            return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+                "Unpacked archive exceeds the limit of {} bytes",
+                limits.max_total_bytes
+            )));
+        }
+    }
+
+    info!("Archive unzipped to: {:?}", output_dir);
+    Ok(temp_dir)
+}
+
+/// Directory whose subdirectories are benchmarks, `performance_results/` if the
+/// unpacked artifact has one, the artifact itself otherwise
+pub fn benchmarks_dir(unpacked: &Path) -> PathBuf {
+    let performance_results = unpacked.join(PERFORMANCE_RESULTS_DIR);
+    if performance_results.is_dir() {
+        performance_results
+    } else {
+        unpacked.to_path_buf()
+    }
+}
+
+fn file_too_large(name: &str, limits: &ArtifactLimits) -> IggyBenchDashboardServerError {
+    IggyBenchDashboardServerError::InvalidArtifact(format!(
+        "File {} exceeds the limit of {} bytes",
+        name, limits.max_file_bytes
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    enum Entry<'a> {
+        File(&'a str, &'a [u8]),
+        Symlink(&'a str, &'a str),
+    }
+
+    fn archive(entries: &[Entry]) -> NamedTempFile {
+        let archive = NamedTempFile::new().unwrap();
+        let mut zip = ZipWriter::new(archive.reopen().unwrap());
+        for entry in entries {
+            match entry {
+                Entry::File(name, content) => {
+                    zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+                    zip.write_all(content).unwrap();
+                }
+                Entry::Symlink(name, target) => {
+                    zip.add_symlink(*name, *target, SimpleFileOptions::default())
+                        .unwrap();
+                }
+            }
+        }
+        zip.finish().unwrap();
+        archive
+    }
+
+    fn limits(max_total_bytes: u64, max_entries: usize, max_file_bytes: u64) -> ArtifactLimits {
+        ArtifactLimits {
+            max_total_bytes,
+            max_entries,
+            max_file_bytes,
+        }
+    }
+
+    fn rejected(archive: &NamedTempFile, limits: &ArtifactLimits) -> bool {
+        matches!(
+            unpack_zip(archive.path(), limits),
+            Err(IggyBenchDashboardServerError::InvalidArtifact(_))
+        )
+    }
+
+    #[test]
+    fn unpacks_within_the_limits() {
+        let archive = archive(&[
+            Entry::File("bench/report.json", b"{}"),
+            Entry::File("bench/data.csv", b"1,2"),
+        ]);
+        let unpacked = unpack_zip(archive.path(), &limits(5, 2, 3)).unwrap();
+        let report = std::fs::read(unpacked.path().join("bench/report.json")).unwrap();
+        assert_eq!(report, b"{}");
+    }
+
+    #[test]
+    fn rejects_too_many_entries() {
+        let archive = archive(&[Entry::File("a", b"1"), Entry::File("b", b"2")]);
+        assert!(rejected(&archive, &limits(1024, 1, 1024)));
+    }
+
+    #[test]
+    fn rejects_too_large_files() {
+        let archive = archive(&[Entry::File("a", b"1234")]);
+        assert!(rejected(&archive, &limits(1024, 10, 3)));
+    }
+
+    #[test]
+    fn rejects_too_large_content() {
+        let archive = archive(&[Entry::File("a", b"123"), Entry::File("b", b"456")]);
+        assert!(rejected(&archive, &limits(5, 10, 1024)));
+    }
+
+    #[test]
+    fn rejects_symbolic_links() {
+        let archive = archive(&[Entry::Symlink("link", "/etc/passwd")]);
+        assert!(rejected(&archive, &ArtifactLimits::default()));
+    }
+
+    #[test]
+    fn rejects_paths_leaving_the_directory() {
+        for name in ["../escaped", "bench/../../escaped", "/absolute"] {
+            let archive = archive(&[Entry::File(name, b"1")]);
+            assert!(rejected(&archive, &ArtifactLimits::default()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn archive_size_is_checked_against_the_total_limit() {
+        assert!(check_archive_size(5, &limits(5, 1, 1)).is_ok());
+        assert!(check_archive_size(6, &limits(5, 1, 1)).is_err());
+    }
+}
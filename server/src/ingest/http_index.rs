@@ -0,0 +1,159 @@
+use super::archive::download_zip;
+use super::{ArtifactItem, ArtifactLimits, ArtifactRecord, ArtifactSource, FetchedArtifact};
+use crate::error::IggyBenchDashboardServerError;
+use serde::Deserialize;
+use url::Url;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpIndexConfig {
+    /// URL of a JSON array of ZIP URLs
+    pub url: String,
+    /// Sent as a bearer token with requests to the origin of `url`, never to other hosts
+    pub token: Option<String>,
+}
+
+/// Entry of the index, a bare URL or a URL with the commit it was built from.
+/// Relative URLs are resolved against the index URL.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum IndexEntry {
+    Url(String),
+    Artifact {
+        url: String,
+        gitref: Option<String>,
+        sha: Option<String>,
+    },
+}
+
+/// ZIP archives listed by a JSON index served over HTTP. URLs are assumed
+/// immutable, a URL is imported once.
+#[derive(Debug)]
+pub struct HttpIndexSource {
+    client: reqwest::Client,
+    url: Url,
+    token: Option<String>,
+}
+
+impl HttpIndexSource {
+    pub fn new(config: HttpIndexConfig) -> Result<Self> {
+        let url = Url::parse(&config.url).map_err(|e| {
+            IggyBenchDashboardServerError::InvalidConfig(format!(
+                "Invalid index URL {}: {}",
+                config.url, e
+            ))
+        })?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url,
+            token: config.token,
+        })
+    }
+
+    /// Request carrying the token only if `url` shares the scheme, host and port
+    /// of the index, so an index listing other hosts can't collect it
+    fn get(&self, url: &Url) -> reqwest::RequestBuilder {
+        let request = self.client.get(url.clone());
+        match &self.token {
+            Some(token) if url.origin() == self.url.origin() => request.bearer_auth(token),
+            _ => request,
+        }
+    }
+}
+
+impl ArtifactSource for HttpIndexSource {
+    fn name(&self) -> String {
+        format!("http:{}", self.url)
+    }
+
+    async fn list_new(&self) -> Result<Vec<ArtifactItem>> {
+        let entries: Vec<IndexEntry> = self
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        entries
+            .into_iter()
+            .map(|entry| {
+                let (url, gitref, sha) = match entry {
+                    IndexEntry::Url(url) => (url, None, None),
+                    IndexEntry::Artifact { url, gitref, sha } => (url, gitref, sha),
+                };
+                let url = self.url.join(&url).map_err(|e| {
+                    IggyBenchDashboardServerError::InvalidJson(format!(
+                        "Invalid URL {} in index {}: {}",
+                        url, self.url, e
+                    ))
+                })?;
+
+                let mut item = ArtifactItem::keyed(&self.name(), url.to_string(), "");
+                item.gitref = gitref;
+                item.head_sha = sha;
+                Ok(item)
+            })
+            .collect()
+    }
+
+    async fn fetch(
+        &self,
+        item: &ArtifactItem,
+        limits: &ArtifactLimits,
+    ) -> Result<Vec<FetchedArtifact>> {
+        let url = Url::parse(&item.key).map_err(|e| {
+            IggyBenchDashboardServerError::InvalidJson(format!("Invalid URL {}: {}", item.key, e))
+        })?;
+        let response = self.get(&url).send().await?.error_for_status()?;
+        let archive = download_zip(response, limits).await?;
+        let name = item.key.rsplit('/').next().unwrap_or(&item.key).to_owned();
+
+        Ok(vec![FetchedArtifact {
+            record: ArtifactRecord {
+                id: item.id,
+                name,
+                size_bytes: archive.as_file().metadata()?.len(),
+            },
+            archive,
+        }])
+    }
+
+    async fn acknowledge(&self, _item: &ArtifactItem) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::AUTHORIZATION;
+
+    fn source() -> HttpIndexSource {
+        HttpIndexSource::new(HttpIndexConfig {
+            url: "https://benchmarks.example.com/index.json".to_owned(),
+            token: Some("secret".to_owned()),
+        })
+        .unwrap()
+    }
+
+    fn sends_token(url: &str) -> bool {
+        let request = source().get(&Url::parse(url).unwrap()).build().unwrap();
+        request.headers().contains_key(AUTHORIZATION)
+    }
+
+    #[test]
+    fn token_is_sent_to_the_index_origin() {
+        assert!(sends_token("https://benchmarks.example.com/index.json"));
+        assert!(sends_token("https://benchmarks.example.com/runs/1.zip"));
+    }
+
+    #[test]
+    fn token_is_not_sent_to_other_origins() {
+        assert!(!sends_token("https://attacker.example.com/1.zip"));
+        assert!(!sends_token("http://benchmarks.example.com/1.zip"));
+        assert!(!sends_token("https://benchmarks.example.com:8443/1.zip"));
+    }
+}
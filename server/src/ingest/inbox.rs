@@ -0,0 +1,97 @@
+use super::archive::check_archive_size;
+use super::{ArtifactItem, ArtifactLimits, ArtifactRecord, ArtifactSource, FetchedArtifact};
+use crate::error::IggyBenchDashboardServerError;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+use tempfile::NamedTempFile;
+use tokio::fs;
+use tracing::info;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+/// Imported archives are moved here, inside the inbox directory
+const PROCESSED_DIR: &str = "processed";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InboxConfig {
+    /// Directory ZIP archives are dropped into
+    pub dir: PathBuf,
+}
+
+/// Local directory receiving benchmark ZIP archives. Archives should be written
+/// under a hidden name and renamed once complete, hidden files are ignored.
+#[derive(Debug)]
+pub struct InboxSource {
+    dir: PathBuf,
+}
+
+impl InboxSource {
+    pub fn new(config: InboxConfig) -> Self {
+        Self { dir: config.dir }
+    }
+}
+
+impl ArtifactSource for InboxSource {
+    fn name(&self) -> String {
+        format!("inbox:{}", self.dir.display())
+    }
+
+    async fn list_new(&self) -> Result<Vec<ArtifactItem>> {
+        let mut items = Vec::new();
+        let mut entries = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() || file_name.starts_with('.') || !file_name.ends_with(".zip") {
+                continue;
+            }
+
+            // A new archive with the name of an imported one is a different item
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|modified| modified.as_nanos())
+                .unwrap_or_default();
+            items.push(ArtifactItem::keyed(
+                &self.name(),
+                file_name,
+                &modified.to_string(),
+            ));
+        }
+        items.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(items)
+    }
+
+    async fn fetch(
+        &self,
+        item: &ArtifactItem,
+        limits: &ArtifactLimits,
+    ) -> Result<Vec<FetchedArtifact>> {
+        let path = self.dir.join(&item.key);
+        let size = fs::metadata(&path).await?.len();
+        check_archive_size(size, limits)?;
+
+        // Copied so that the inbox file can be moved away while importing
+        let archive = NamedTempFile::new()?;
+        fs::copy(&path, archive.path()).await?;
+        Ok(vec![FetchedArtifact {
+            archive,
+            record: ArtifactRecord {
+                id: item.id,
+                name: item.key.clone(),
+                size_bytes: size,
+            },
+        }])
+    }
+
+    async fn acknowledge(&self, item: &ArtifactItem) -> Result<()> {
+        let processed_dir = self.dir.join(PROCESSED_DIR);
+        fs::create_dir_all(&processed_dir).await?;
+        fs::rename(self.dir.join(&item.key), processed_dir.join(&item.key)).await?;
+        info!("Moved {} to {}", item.key, processed_dir.display());
+        Ok(())
+    }
+}
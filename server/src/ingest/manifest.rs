@@ -0,0 +1,445 @@
+use crate::error::IggyBenchDashboardServerError;
+use chrono::{DateTime, Utc};
+use dashmap::{DashMap, DashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, Notify};
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+const MANIFEST_FILE: &str = "ingest_manifest.jsonl";
+
+/// Bare list of workflow run IDs written by earlier versions, migrated on startup
+const LEGACY_WORKFLOW_FILE: &str = "gh_workflows.txt";
+
+/// Workflow earlier versions polled, the source of migrated runs when no
+/// GitHub source is configured
+pub const LEGACY_SOURCE: &str = "iggy-rs/iggy/performance.yml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestStatus {
+    Imported,
+    Failed,
+    /// Waiting for the poller of its source to import the run again
+    ReimportRequested,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArtifactRecord {
+    pub id: u64,
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+/// Latest ingestion attempt of a workflow run
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IngestRecord {
+    /// `owner/repo/workflow` the run belongs to, or the name of another artifact source
+    pub source: String,
+    /// Workflow run ID, or the ID derived from the item key for other sources
+    pub run_id: u64,
+    /// Item within its source, e.g. an object key or URL
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Set for pull request runs, which are ingested as candidates
+    #[serde(default)]
+    pub pull_request: Option<u64>,
+    pub head_sha: Option<String>,
+    /// Git ref the benchmarks are filed under, the tag of the commit or its SHA
+    pub gitref: Option<String>,
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub artifacts: Vec<ArtifactRecord>,
+    /// Benchmarks contained in the run's artifacts
+    #[serde(default)]
+    pub benchmark_uuids: Vec<Uuid>,
+    pub run_created_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub status: IngestStatus,
+    pub error: Option<String>,
+    /// Failed attempts since the last successful import
+    #[serde(default)]
+    pub failed_attempts: u32,
+}
+
+impl IngestRecord {
+    fn legacy(source: &str, run_id: u64) -> Self {
+        Self {
+            source: source.to_owned(),
+            run_id,
+            key: None,
+            pull_request: None,
+            head_sha: None,
+            gitref: None,
+            branch: None,
+            artifacts: Vec::new(),
+            benchmark_uuids: Vec::new(),
+            run_created_at: None,
+            started_at: None,
+            finished_at: None,
+            status: IngestStatus::Imported,
+            error: None,
+            failed_attempts: 0,
+        }
+    }
+}
+
+/// Identifies a record: IDs are only unique within their source
+type RecordKey = (String, u64);
+
+/// Record of every workflow run the poller ingested, stored in the results
+/// directory as JSON lines. Records are only appended, the last line of a run
+/// wins. Unreadable lines are skipped instead of failing startup.
+#[derive(Debug)]
+pub struct IngestManifest {
+    records: DashMap<RecordKey, IngestRecord>,
+    file: Mutex<File>,
+    reimport_requested: Notify,
+    /// Sources whose poller imports runs again on request
+    reimport_sources: DashSet<String>,
+}
+
+impl IngestManifest {
+    /// Files the manifest writes to in the results directory
+    pub fn files(results_dir: &Path) -> [PathBuf; 2] {
+        let path = results_dir.join(MANIFEST_FILE);
+        [tmp_path(&path), path]
+    }
+
+    /// Opens the manifest of `results_dir`. Runs listed by earlier versions are
+    /// migrated as runs of `legacy_source`, which should be the configured
+    /// source polling the same workflow so that they aren't ingested again and
+    /// can be re-imported.
+    pub async fn open(results_dir: &Path, legacy_source: &str) -> Result<Self> {
+        let path = results_dir.join(MANIFEST_FILE);
+        info!("Ingest manifest path: {}", path.display());
+
+        let legacy_path = results_dir.join(LEGACY_WORKFLOW_FILE);
+        let records = if path.exists() {
+            let (records, needs_compaction) = read_manifest(&path)?;
+            if needs_compaction {
+                write_manifest(&path, records.values()).await?;
+            }
+            records
+        } else if legacy_path.exists() {
+            let records = read_legacy_workflows(&legacy_path, legacy_source)?;
+            write_manifest(&path, records.values()).await?;
+            let mut migrated_path = legacy_path.clone().into_os_string();
+            migrated_path.push(".migrated");
+            tokio::fs::rename(&legacy_path, migrated_path).await?;
+            records
+        } else {
+            HashMap::new()
+        };
+
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&path)
+            .await?;
+        info!("Loaded {} ingest records", records.len());
+
+        Ok(Self {
+            records: records.into_iter().collect(),
+            file: Mutex::new(file),
+            reimport_requested: Notify::new(),
+            reimport_sources: DashSet::new(),
+        })
+    }
+
+    pub fn is_imported(&self, source: &str, run_id: u64) -> bool {
+        self.records
+            .get(&(source.to_owned(), run_id))
+            .is_some_and(|record| record.status == IngestStatus::Imported)
+    }
+
+    pub fn get(&self, source: &str, run_id: u64) -> Option<IngestRecord> {
+        self.records
+            .get(&(source.to_owned(), run_id))
+            .map(|record| record.clone())
+    }
+
+    /// Stores `record` as the latest state of its run
+    pub async fn record(&self, record: IngestRecord) {
+        let mut file = self.file.lock().await;
+        match serde_json::to_string(&record) {
+            Ok(line) => {
+                if let Err(e) = file.write_all(format!("{}\n", line).as_bytes()).await {
+                    error!(
+                        "Failed to write ingest record of run {}: {}",
+                        record.run_id, e
+                    );
+                }
+            }
+            Err(e) => error!(
+                "Failed to serialize ingest record of run {}: {}",
+                record.run_id, e
+            ),
+        }
+        self.records
+            .insert((record.source.clone(), record.run_id), record);
+    }
+
+    /// Every run, most recently ingested first
+    pub fn history(&self) -> Vec<IngestRecord> {
+        let mut records: Vec<IngestRecord> = self
+            .records
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        records.sort_by(|a, b| {
+            b.finished_at
+                .cmp(&a.finished_at)
+                .then_with(|| b.run_id.cmp(&a.run_id))
+                .then_with(|| a.source.cmp(&b.source))
+        });
+        records
+    }
+
+    /// Registers a poller that picks up [`Self::pending_reimports`] of `source`
+    pub fn accept_reimports(&self, source: &str) {
+        self.reimport_sources.insert(source.to_owned());
+    }
+
+    /// Marks a known run for import and wakes up the pollers. `source` is only
+    /// needed when runs of several sources share the ID. Fails for runs of
+    /// sources no running poller imports again, where the request would never
+    /// be served.
+    pub async fn request_reimport(
+        &self,
+        run_id: u64,
+        source: Option<&str>,
+    ) -> Result<IngestRecord> {
+        let mut matching: Vec<IngestRecord> = self
+            .records
+            .iter()
+            .filter(|entry| {
+                entry.run_id == run_id && source.is_none_or(|source| entry.source == source)
+            })
+            .map(|entry| entry.value().clone())
+            .collect();
+        if matching.len() > 1 {
+            return Err(IggyBenchDashboardServerError::Conflict(format!(
+                "Workflow run {} was ingested from several sources, pick one with the source parameter",
+                run_id
+            )));
+        }
+        let mut record = matching.pop().ok_or_else(|| {
+            IggyBenchDashboardServerError::NotFound(format!(
+                "Workflow run {} was never ingested",
+                run_id
+            ))
+        })?;
+        if !self.reimport_sources.contains(&record.source) {
+            return Err(IggyBenchDashboardServerError::Conflict(format!(
+                "Workflow run {} was ingested from {}, which does not support re-imports",
+                run_id, record.source
+            )));
+        }
+        record.status = IngestStatus::ReimportRequested;
+        record.error = None;
+        record.failed_attempts = 0;
+        self.record(record.clone()).await;
+        self.reimport_requested.notify_waiters();
+        Ok(record)
+    }
+
+    /// Runs of `source` waiting to be imported again
+    pub fn pending_reimports(&self, source: &str) -> Vec<u64> {
+        self.records
+            .iter()
+            .filter(|entry| {
+                entry.status == IngestStatus::ReimportRequested && entry.source == source
+            })
+            .map(|entry| entry.run_id)
+            .collect()
+    }
+
+    /// Completes once a re-import is requested
+    pub async fn reimport_requested(&self) {
+        self.reimport_requested.notified().await;
+    }
+
+    /// Makes sure every record reached the disk
+    pub async fn flush(&self) {
+        let mut file = self.file.lock().await;
+        if let Err(e) = file.flush().await {
+            error!("Failed to flush ingest manifest: {}", e);
+        }
+        if let Err(e) = file.sync_all().await {
+            error!("Failed to flush ingest manifest: {}", e);
+        }
+    }
+}
+
+/// Reads the manifest, returning whether it should be compacted because it
+/// contains superseded or unreadable lines
+fn read_manifest(path: &Path) -> Result<(HashMap<RecordKey, IngestRecord>, bool)> {
+    let data = std::fs::read_to_string(path)?;
+    let mut records = HashMap::new();
+    let mut lines = 0;
+    for (number, line) in data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        lines += 1;
+        match serde_json::from_str::<IngestRecord>(line) {
+            Ok(record) => {
+                records.insert((record.source.clone(), record.run_id), record);
+            }
+            Err(e) => warn!(
+                "Skipping invalid line {} of {}: {}",
+                number + 1,
+                path.display(),
+                e
+            ),
+        }
+    }
+    let needs_compaction = lines != records.len();
+    Ok((records, needs_compaction))
+}
+
+fn read_legacy_workflows(
+    legacy_path: &Path,
+    source: &str,
+) -> Result<HashMap<RecordKey, IngestRecord>> {
+    info!(
+        "Migrating {} to the ingest manifest as runs of {}",
+        legacy_path.display(),
+        source
+    );
+    let data = std::fs::read_to_string(legacy_path)?;
+    let mut records = HashMap::new();
+    for (number, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match line.parse::<u64>() {
+            Ok(run_id) => {
+                records.insert(
+                    (source.to_owned(), run_id),
+                    IngestRecord::legacy(source, run_id),
+                );
+            }
+            Err(e) => warn!(
+                "Skipping invalid workflow ID '{}' on line {} of {}: {}",
+                line,
+                number + 1,
+                legacy_path.display(),
+                e
+            ),
+        }
+    }
+    Ok(records)
+}
+
+/// Replaces the manifest with one line per record
+async fn write_manifest(path: &Path, records: impl Iterator<Item = &IngestRecord>) -> Result<()> {
+    let mut content = String::new();
+    for record in records {
+        let line = serde_json::to_string(record)
+            .map_err(|e| IggyBenchDashboardServerError::InternalError(e.to_string()))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    let tmp_path = tmp_path(path);
+    tokio::fs::write(&tmp_path, content).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    PathBuf::from(tmp_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(source: &str, run_id: u64, status: IngestStatus) -> IngestRecord {
+        IngestRecord {
+            status,
+            ..IngestRecord::legacy(source, run_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_of_different_sources_are_kept_apart() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let manifest = IngestManifest::open(results_dir.path(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+        manifest
+            .record(record("a/iggy/performance.yml", 1, IngestStatus::Imported))
+            .await;
+        manifest
+            .record(record("b/iggy/performance.yml", 1, IngestStatus::Failed))
+            .await;
+
+        assert!(manifest.is_imported("a/iggy/performance.yml", 1));
+        assert!(!manifest.is_imported("b/iggy/performance.yml", 1));
+        assert_eq!(manifest.history().len(), 2);
+
+        manifest.flush().await;
+        let reopened = IngestManifest::open(results_dir.path(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+        assert!(reopened.is_imported("a/iggy/performance.yml", 1));
+        assert!(!reopened.is_imported("b/iggy/performance.yml", 1));
+    }
+
+    #[tokio::test]
+    async fn reimport_of_a_shared_run_id_needs_the_source() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let manifest = IngestManifest::open(results_dir.path(), LEGACY_SOURCE)
+            .await
+            .unwrap();
+        for source in ["a/iggy/performance.yml", "b/iggy/performance.yml"] {
+            manifest.accept_reimports(source);
+            manifest
+                .record(record(source, 1, IngestStatus::Imported))
+                .await;
+        }
+
+        assert!(matches!(
+            manifest.request_reimport(1, None).await,
+            Err(IggyBenchDashboardServerError::Conflict(_))
+        ));
+        let requested = manifest
+            .request_reimport(1, Some("b/iggy/performance.yml"))
+            .await
+            .unwrap();
+        assert_eq!(requested.source, "b/iggy/performance.yml");
+        assert!(manifest
+            .pending_reimports("a/iggy/performance.yml")
+            .is_empty());
+        assert_eq!(manifest.pending_reimports("b/iggy/performance.yml"), [1]);
+    }
+
+    #[tokio::test]
+    async fn legacy_runs_are_migrated_to_the_given_source() {
+        let results_dir = tempfile::tempdir().unwrap();
+        std::fs::write(results_dir.path().join(LEGACY_WORKFLOW_FILE), "7\n8\n").unwrap();
+
+        let manifest = IngestManifest::open(results_dir.path(), "apache/iggy/performance.yml")
+            .await
+            .unwrap();
+
+        assert!(manifest.is_imported("apache/iggy/performance.yml", 7));
+        assert!(manifest.is_imported("apache/iggy/performance.yml", 8));
+        assert!(!manifest.is_imported(LEGACY_SOURCE, 7));
+        assert!(!results_dir.path().join(LEGACY_WORKFLOW_FILE).exists());
+    }
+}
@@ -0,0 +1,18 @@
+mod archive;
+mod http_index;
+mod inbox;
+mod manifest;
+mod pipeline;
+mod quarantine;
+mod s3;
+mod source;
+mod staging;
+mod worker;
+
+pub use archive::{download_zip, ArtifactLimits};
+pub use manifest::{ArtifactRecord, IngestManifest, IngestRecord, IngestStatus, LEGACY_SOURCE};
+pub use pipeline::{ImportOutcome, ImportPipeline, MAX_IMPORT_ATTEMPTS};
+pub use quarantine::QUARANTINE_DIR;
+pub use source::{ArtifactItem, ArtifactSource, FetchedArtifact};
+pub use staging::{clean_staging, copy_into, staging_dir, REPLACED_DIR, STAGING_DIR};
+pub use worker::{ArtifactSourceConfig, IngestConfig, IngestWorkers};
@@ -0,0 +1,395 @@
+use super::archive::{benchmarks_dir, unpack_zip};
+use super::quarantine::quarantine;
+use super::staging::{copy_into, staging_dir, REPLACED_DIR};
+use super::{
+    ArtifactItem, ArtifactLimits, ArtifactSource, FetchedArtifact, IngestRecord, IngestStatus,
+};
+use crate::cache::BenchmarkCache;
+use crate::error::IggyBenchDashboardServerError;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::{fs, sync::watch};
+use tracing::{error, info, trace, warn};
+use uuid::Uuid;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+/// Failed import attempts after which an item is skipped until restart
+pub const MAX_IMPORT_ATTEMPTS: u32 = 5;
+
+/// How an import that didn't fail ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportOutcome {
+    /// The benchmarks are installed and recorded in the manifest
+    Imported,
+    /// Shutdown was requested before anything was written, the item is still pending
+    Abandoned,
+}
+
+/// Validation and import shared by every [`ArtifactSource`]
+#[derive(Debug)]
+pub struct ImportPipeline {
+    results_dir: PathBuf,
+    cache: Arc<BenchmarkCache>,
+    limits: ArtifactLimits,
+}
+
+/// Artifacts of an item that passed validation, unpacked in temporary
+/// directories removed when dropped
+pub struct AcceptedArtifacts {
+    _unpacked: Vec<TempDir>,
+    pub benchmarks_dirs: Vec<PathBuf>,
+    pub benchmark_uuids: Vec<Uuid>,
+}
+
+impl ImportPipeline {
+    pub fn new(results_dir: PathBuf, cache: Arc<BenchmarkCache>, limits: ArtifactLimits) -> Self {
+        Self {
+            results_dir,
+            cache,
+            limits,
+        }
+    }
+
+    pub fn limits(&self) -> &ArtifactLimits {
+        &self.limits
+    }
+
+    /// Whether `item` is neither imported yet nor failed too often
+    pub fn is_pending(&self, source_name: &str, item: &ArtifactItem, failed_attempts: u32) -> bool {
+        !self.cache.manifest().is_imported(source_name, item.id)
+            && failed_attempts < MAX_IMPORT_ATTEMPTS
+    }
+
+    /// Fetches `item`, validates its benchmarks and installs them in the results
+    /// directory, then acknowledges it to the source. Nothing is written to the
+    /// results directory while fetching, so a fetch is abandoned on shutdown.
+    pub async fn import<S: ArtifactSource>(
+        &self,
+        source: &S,
+        item: &ArtifactItem,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<ImportOutcome> {
+        let mut record = self.record(&source.name(), item);
+        // A requested re-import replaces benchmarks that are already present
+        let replace = self
+            .cache
+            .manifest()
+            .get(&source.name(), item.id)
+            .is_some_and(|previous| previous.status == IngestStatus::ReimportRequested);
+
+        let artifacts = tokio::select! {
+            artifacts = source.fetch(item, &self.limits) => self.fetched(&source.name(), item, artifacts).await?,
+            _ = shutdown_rx.changed() => {
+                info!("Shutdown signal received during fetch of {} from {}, abandoning it", item.key, source.name());
+                return Ok(ImportOutcome::Abandoned);
+            }
+        };
+        let accepted = self.accept(&source.name(), item, &artifacts).await?;
+        info!(
+            "Artifacts of {} from {} are in {:?}",
+            item.key,
+            source.name(),
+            accepted.benchmarks_dirs
+        );
+        record.benchmark_uuids = accepted.benchmark_uuids.clone();
+
+        // Once installing started the item is finished even when shutdown is
+        // requested, and rolled back if installing fails
+        self.install(&accepted.benchmarks_dirs, replace).await?;
+
+        // The benchmarks are in place, a source that keeps offering the item
+        // is skipped thanks to the manifest
+        if let Err(e) = source.acknowledge(item).await {
+            warn!(
+                "Failed to acknowledge {} to {}: {}",
+                item.key,
+                source.name(),
+                e
+            );
+        }
+
+        record.artifacts = artifacts
+            .into_iter()
+            .map(|artifact| artifact.record)
+            .collect();
+        record.status = IngestStatus::Imported;
+        record.finished_at = Some(Utc::now());
+        self.cache.manifest().record(record).await;
+
+        Ok(ImportOutcome::Imported)
+    }
+
+    /// Passes the result of fetching `item` through, quarantining the item if
+    /// its download was rejected
+    pub async fn fetched(
+        &self,
+        source_name: &str,
+        item: &ArtifactItem,
+        artifacts: Result<Vec<FetchedArtifact>>,
+    ) -> Result<Vec<FetchedArtifact>> {
+        if let Err(e @ IggyBenchDashboardServerError::InvalidArtifact(_)) = &artifacts {
+            self.quarantine(source_name, item, &[], e).await;
+        }
+        artifacts
+    }
+
+    /// Unpacks the fetched artifacts of `item` within the limits and validates
+    /// their benchmarks. A rejected item is quarantined.
+    pub async fn accept(
+        &self,
+        source_name: &str,
+        item: &ArtifactItem,
+        artifacts: &[FetchedArtifact],
+    ) -> Result<AcceptedArtifacts> {
+        let accepted = self.unpack(artifacts).and_then(|unpacked| {
+            let benchmarks_dirs: Vec<PathBuf> = unpacked
+                .iter()
+                .map(|dir| benchmarks_dir(dir.path()))
+                .collect();
+            Ok(AcceptedArtifacts {
+                benchmark_uuids: self.validate(&benchmarks_dirs)?,
+                benchmarks_dirs,
+                _unpacked: unpacked,
+            })
+        });
+        if let Err(e @ IggyBenchDashboardServerError::InvalidArtifact(_)) = &accepted {
+            self.quarantine(source_name, item, artifacts, e).await;
+        }
+        accepted
+    }
+
+    fn unpack(&self, artifacts: &[FetchedArtifact]) -> Result<Vec<TempDir>> {
+        artifacts
+            .iter()
+            .map(|artifact| {
+                unpack_zip(artifact.archive.path(), &self.limits).map_err(|e| match e {
+                    IggyBenchDashboardServerError::InvalidArtifact(reason) => {
+                        IggyBenchDashboardServerError::InvalidArtifact(format!(
+                            "{}: {}",
+                            artifact.record.name, reason
+                        ))
+                    }
+                    e => e,
+                })
+            })
+            .collect()
+    }
+
+    async fn quarantine(
+        &self,
+        source_name: &str,
+        item: &ArtifactItem,
+        artifacts: &[FetchedArtifact],
+        reason: &IggyBenchDashboardServerError,
+    ) {
+        if let Err(e) = quarantine(&self.results_dir, source_name, item, artifacts, reason).await {
+            error!(
+                "Failed to quarantine {} from {}: {}",
+                item.key, source_name, e
+            );
+        }
+    }
+
+    /// Checks that every benchmark directory holds a report with a hardware
+    /// identifier and a gitref, and returns the benchmark UUIDs. An artifact
+    /// without any benchmark is rejected.
+    pub fn validate(&self, benchmarks_dirs: &[PathBuf]) -> Result<Vec<Uuid>> {
+        let mut uuids = Vec::new();
+        let mut seen = HashSet::new();
+        for benchmarks_dir in benchmarks_dirs {
+            for entry in std::fs::read_dir(benchmarks_dir)?.filter_map(|r| r.ok()) {
+                let path = entry.path();
+                if !path.is_dir() {
+                    continue;
+                }
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with('.') {
+                    return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+                        "Hidden benchmark directory {}",
+                        name
+                    )));
+                }
+
+                let report = self
+                    .cache
+                    .load_light_report(&path.join("report.json"))
+                    .map_err(|e| {
+                        IggyBenchDashboardServerError::InvalidArtifact(format!(
+                            "Benchmark {}: {}",
+                            name, e
+                        ))
+                    })?;
+                if report
+                    .hardware
+                    .identifier
+                    .as_deref()
+                    .is_none_or(str::is_empty)
+                {
+                    return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+                        "Benchmark {} has no hardware identifier",
+                        name
+                    )));
+                }
+                if report.params.gitref.as_deref().is_none_or(str::is_empty) {
+                    return Err(IggyBenchDashboardServerError::InvalidArtifact(format!(
+                        "Benchmark {} has no gitref",
+                        name
+                    )));
+                }
+                if seen.insert(report.uuid) {
+                    uuids.push(report.uuid);
+                }
+            }
+        }
+
+        if uuids.is_empty() {
+            return Err(IggyBenchDashboardServerError::InvalidArtifact(
+                "No benchmarks found".into(),
+            ));
+        }
+        Ok(uuids)
+    }
+
+    /// Moves the benchmarks of the unpacked artifacts into the results directory.
+    /// They are copied into the staging area and validated first, then renamed
+    /// into place, so the results directory never holds a partial benchmark.
+    /// Benchmarks that are already present are skipped, or replaced if `replace`
+    /// is set. On failure, benchmarks moved so far are taken out again.
+    async fn install(&self, benchmarks_dirs: &[PathBuf], replace: bool) -> Result<()> {
+        let staging = staging_dir(&self.results_dir).await?;
+        let staged = copy_into(benchmarks_dirs, staging.path()).await?;
+        let staged: Vec<PathBuf> = staged
+            .into_iter()
+            .filter(|staged| {
+                let present = self.results_dir.join(staged.file_name().unwrap()).exists();
+                if present && !replace {
+                    trace!("{:?} is already present, skipping", staged.file_name());
+                }
+                !present || replace
+            })
+            .collect();
+
+        // Copying may have been cut short by a full disk or a broken artifact
+        for staged in &staged {
+            self.cache
+                .load_light_report(&staged.join("report.json"))
+                .map_err(|e| {
+                    IggyBenchDashboardServerError::InvalidArtifact(format!(
+                        "Staged benchmark {}: {}",
+                        staged.display(),
+                        e
+                    ))
+                })?;
+        }
+
+        let replaced_dir = staging.path().join(REPLACED_DIR);
+        fs::create_dir_all(&replaced_dir).await?;
+        let mut installed: Vec<(PathBuf, Option<PathBuf>)> = Vec::new();
+        let mut result = Ok(());
+        for staged in &staged {
+            let name = staged.file_name().unwrap();
+            let destination = self.results_dir.join(name);
+            let replaced = replaced_dir.join(name);
+            result = async {
+                let backup = if destination.exists() {
+                    fs::rename(&destination, &replaced).await?;
+                    Some(replaced)
+                } else {
+                    None
+                };
+                if let Err(e) = fs::rename(staged, &destination).await {
+                    if let Some(backup) = &backup {
+                        fs::rename(backup, &destination).await?;
+                    }
+                    return Err(e);
+                }
+                info!("Installed benchmark {:?}", destination);
+                installed.push((destination, backup));
+                Ok(())
+            }
+            .await;
+            if result.is_err() {
+                break;
+            }
+        }
+
+        if let Err(e) = result {
+            for (destination, backup) in installed.into_iter().rev() {
+                warn!(
+                    "Rolling back partially installed item, removing {:?}",
+                    destination
+                );
+                if let Err(e) = fs::remove_dir_all(&destination).await {
+                    error!("Failed to remove {:?}: {}", destination, e);
+                    continue;
+                }
+                if let Some(backup) = backup {
+                    if let Err(e) = fs::rename(&backup, &destination).await {
+                        error!("Failed to restore {:?}: {}", destination, e);
+                    }
+                }
+            }
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Manifest record of an import of `item` starting now
+    pub fn record(&self, source_name: &str, item: &ArtifactItem) -> IngestRecord {
+        IngestRecord {
+            source: source_name.to_owned(),
+            run_id: item.id,
+            key: Some(item.key.clone()),
+            pull_request: None,
+            head_sha: item.head_sha.clone(),
+            gitref: item.gitref.clone(),
+            branch: item.branch.clone(),
+            artifacts: Vec::new(),
+            benchmark_uuids: Vec::new(),
+            run_created_at: item.created_at,
+            started_at: Some(Utc::now()),
+            finished_at: None,
+            status: IngestStatus::Failed,
+            error: None,
+            failed_attempts: 0,
+        }
+    }
+
+    /// Failed attempts of an item after another failure with `e`. A rejected
+    /// artifact is given up on at once, retrying won't make it valid.
+    pub fn failed_attempts_after(attempts: u32, e: &IggyBenchDashboardServerError) -> u32 {
+        match e {
+            IggyBenchDashboardServerError::InvalidArtifact(_) => MAX_IMPORT_ATTEMPTS,
+            _ => attempts + 1,
+        }
+    }
+
+    /// Records the failed import of `item` in the manifest
+    pub async fn record_failure(
+        &self,
+        source_name: &str,
+        item: &ArtifactItem,
+        pull_request: Option<u64>,
+        attempts: u32,
+        e: &IggyBenchDashboardServerError,
+    ) {
+        error!(
+            "Failed to import {} from {} (attempt {}/{}): {}",
+            item.key, source_name, attempts, MAX_IMPORT_ATTEMPTS, e
+        );
+        if attempts >= MAX_IMPORT_ATTEMPTS {
+            warn!("Giving up on {} from {}", item.key, source_name);
+        }
+
+        let mut record = self.record(source_name, item);
+        record.pull_request = pull_request;
+        record.error = Some(e.to_string());
+        record.failed_attempts = attempts;
+        record.finished_at = Some(Utc::now());
+        self.cache.manifest().record(record).await;
+    }
+}
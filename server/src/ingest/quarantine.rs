@@ -0,0 +1,58 @@
+use super::{ArtifactItem, FetchedArtifact};
+use crate::error::IggyBenchDashboardServerError;
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::warn;
+
+/// Hidden directory inside the results directory receiving rejected artifacts,
+/// one directory per rejected item. The loader and watcher ignore it.
+pub const QUARANTINE_DIR: &str = ".quarantine";
+
+/// Name of the file explaining why an item was quarantined
+const REASON_FILE: &str = "reason.txt";
+
+/// Keeps the archives of a rejected item for inspection, next to a file with
+/// the reason. Nothing is kept but the reason if the download itself was rejected.
+pub async fn quarantine(
+    results_dir: &Path,
+    source_name: &str,
+    item: &ArtifactItem,
+    artifacts: &[FetchedArtifact],
+    reason: &IggyBenchDashboardServerError,
+) -> Result<PathBuf, IggyBenchDashboardServerError> {
+    let rejected_at = Utc::now();
+    let dir = results_dir.join(QUARANTINE_DIR).join(format!(
+        "{}-{}",
+        rejected_at.format("%Y%m%dT%H%M%S"),
+        item.id
+    ));
+    fs::create_dir_all(&dir).await?;
+
+    for artifact in artifacts {
+        let name = artifact.record.name.replace(['/', '\\'], "_");
+        let name = if name.ends_with(".zip") {
+            name
+        } else {
+            format!("{}.zip", name)
+        };
+        fs::copy(artifact.archive.path(), dir.join(name)).await?;
+    }
+
+    let reason = format!(
+        "source: {}\nitem: {}\nrejected_at: {}\nreason: {}\n",
+        source_name,
+        item.key,
+        rejected_at.to_rfc3339(),
+        reason
+    );
+    fs::write(dir.join(REASON_FILE), reason).await?;
+
+    warn!(
+        "Quarantined {} from {} in {}",
+        item.key,
+        source_name,
+        dir.display()
+    );
+    Ok(dir)
+}
@@ -0,0 +1,299 @@
+use super::archive::check_archive_size;
+use super::{ArtifactItem, ArtifactLimits, ArtifactRecord, ArtifactSource, FetchedArtifact};
+use crate::error::IggyBenchDashboardServerError;
+use s3::{bucket::Bucket, creds::Credentials, region::Region};
+use serde::Deserialize;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tempfile::NamedTempFile;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tracing::info;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3Config {
+    pub bucket: String,
+    /// Endpoint of an S3-compatible service, e.g. `http://localhost:9000` for MinIO,
+    /// AWS S3 if unset
+    pub endpoint: Option<String>,
+    #[serde(default = "default_region")]
+    pub region: String,
+    /// Only ZIP objects whose key starts with this are imported
+    #[serde(default)]
+    pub prefix: String,
+    /// Read from `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` if unset
+    pub access_key: Option<String>,
+    pub secret_key: Option<String>,
+    /// Imported objects are deleted from the bucket, kept otherwise
+    #[serde(default)]
+    pub delete_after_import: bool,
+}
+
+fn default_region() -> String {
+    "us-east-1".to_owned()
+}
+
+/// ZIP archives stored in an S3-compatible bucket
+#[derive(Debug)]
+pub struct S3Source {
+    bucket: Box<Bucket>,
+    prefix: String,
+    delete_after_import: bool,
+}
+
+impl S3Source {
+    pub fn new(config: S3Config) -> Result<Self> {
+        let region = match config.endpoint {
+            Some(endpoint) => Region::Custom {
+                region: config.region,
+                endpoint,
+            },
+            None => config.region.parse().map_err(|e| {
+                IggyBenchDashboardServerError::InvalidConfig(format!("Invalid S3 region: {}", e))
+            })?,
+        };
+        let credentials = Credentials::new(
+            config.access_key.as_deref(),
+            config.secret_key.as_deref(),
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| {
+            IggyBenchDashboardServerError::InvalidConfig(format!("Invalid S3 credentials: {}", e))
+        })?;
+        // Path-style addressing is what S3-compatible services support universally
+        let bucket = Bucket::new(&config.bucket, region, credentials)?.with_path_style();
+
+        Ok(Self {
+            bucket,
+            prefix: config.prefix,
+            delete_after_import: config.delete_after_import,
+        })
+    }
+}
+
+impl ArtifactSource for S3Source {
+    fn name(&self) -> String {
+        format!("s3:{}/{}", self.bucket.name(), self.prefix)
+    }
+
+    async fn list_new(&self) -> Result<Vec<ArtifactItem>> {
+        let mut items = Vec::new();
+        for page in self.bucket.list(self.prefix.clone(), None).await? {
+            for object in page.contents {
+                if !object.key.ends_with(".zip") {
+                    continue;
+                }
+                // An overwritten object is a new item
+                let revision = object.e_tag.unwrap_or(object.last_modified);
+                items.push(ArtifactItem::keyed(&self.name(), object.key, &revision));
+            }
+        }
+        Ok(items)
+    }
+
+    async fn fetch(
+        &self,
+        item: &ArtifactItem,
+        limits: &ArtifactLimits,
+    ) -> Result<Vec<FetchedArtifact>> {
+        let (head, _) = self.bucket.head_object(&item.key).await?;
+        if let Some(length) = head.content_length {
+            check_archive_size(length.max(0) as u64, limits)?;
+        }
+
+        let archive = NamedTempFile::new()?;
+        let mut file = LimitedWriter {
+            inner: tokio::fs::File::from_std(archive.as_file().try_clone()?),
+            written: 0,
+            max_bytes: limits.max_total_bytes,
+            exceeded_at: None,
+        };
+        let status_code = self.bucket.get_object_to_writer(&item.key, &mut file).await;
+        // The object may have been replaced by a larger one since the HEAD request
+        if let Some(size) = file.exceeded_at {
+            check_archive_size(size, limits)?;
+        }
+        let status_code = status_code?;
+        if status_code != 200 {
+            return Err(IggyBenchDashboardServerError::InternalError(format!(
+                "S3 returned {} for {}",
+                status_code, item.key
+            )));
+        }
+        file.flush().await?;
+
+        let size = file.written;
+        Ok(vec![FetchedArtifact {
+            archive,
+            record: ArtifactRecord {
+                id: item.id,
+                name: item.key.clone(),
+                size_bytes: size,
+            },
+        }])
+    }
+
+    async fn acknowledge(&self, item: &ArtifactItem) -> Result<()> {
+        if self.delete_after_import {
+            self.bucket.delete_object(&item.key).await?;
+            info!("Deleted {} from bucket {}", item.key, self.bucket.name());
+        }
+        Ok(())
+    }
+}
+
+/// Fails writes once more than `max_bytes` would have been written, so that
+/// an oversized object is abandoned mid-download
+struct LimitedWriter<W> {
+    inner: W,
+    written: u64,
+    max_bytes: u64,
+    /// Size the object reached when the limit was exceeded
+    exceeded_at: Option<u64>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for LimitedWriter<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let size = self.written + buf.len() as u64;
+        if size > self.max_bytes {
+            self.exceeded_at = Some(size);
+            return Poll::Ready(Err(io::Error::other("archive size limit exceeded")));
+        }
+        let poll = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = poll {
+            self.written += written as u64;
+        }
+        poll
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s3::bucket_ops::BucketConfiguration;
+    use std::io::{Cursor, Write};
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    fn writer(max_bytes: u64) -> LimitedWriter<Vec<u8>> {
+        LimitedWriter {
+            inner: Vec::new(),
+            written: 0,
+            max_bytes,
+            exceeded_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_up_to_the_limit() {
+        let mut writer = writer(8);
+        writer.write_all(b"1234").await.unwrap();
+        writer.write_all(b"5678").await.unwrap();
+        assert_eq!(writer.written, 8);
+        assert_eq!(writer.exceeded_at, None);
+        assert_eq!(writer.inner, b"12345678");
+    }
+
+    #[tokio::test]
+    async fn rejects_writes_over_the_limit() {
+        let mut writer = writer(8);
+        writer.write_all(b"12345").await.unwrap();
+        assert!(writer.write_all(b"6789").await.is_err());
+        assert_eq!(writer.exceeded_at, Some(9));
+        assert_eq!(writer.inner, b"12345");
+    }
+
+    fn zip_bytes() -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("benchmark/report.json", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    /// Runs against a local MinIO, e.g. `docker run -p 9000:9000 minio/minio server /data`,
+    /// listening on `IGGY_DASHBOARD_TEST_S3_ENDPOINT` or `http://localhost:9000`
+    #[tokio::test]
+    #[ignore = "needs a MinIO server"]
+    async fn imports_zip_objects_from_minio() {
+        let endpoint = std::env::var("IGGY_DASHBOARD_TEST_S3_ENDPOINT")
+            .unwrap_or_else(|_| "http://localhost:9000".to_owned());
+        let config = S3Config {
+            bucket: format!("iggy-dashboard-test-{}", std::process::id()),
+            endpoint: Some(endpoint.clone()),
+            region: default_region(),
+            prefix: "runs/".to_owned(),
+            access_key: Some("minioadmin".to_owned()),
+            secret_key: Some("minioadmin".to_owned()),
+            delete_after_import: true,
+        };
+        let region = Region::Custom {
+            region: config.region.clone(),
+            endpoint,
+        };
+        let credentials =
+            Credentials::new(Some("minioadmin"), Some("minioadmin"), None, None, None).unwrap();
+        Bucket::create_with_path_style(
+            &config.bucket,
+            region,
+            credentials,
+            BucketConfiguration::default(),
+        )
+        .await
+        .unwrap();
+        let source = S3Source::new(config).unwrap();
+        let archive = zip_bytes();
+        for (key, content) in [
+            ("runs/1.zip", archive.as_slice()),
+            ("runs/notes.txt", b"not an archive".as_slice()),
+            ("other/2.zip", archive.as_slice()),
+        ] {
+            source.bucket.put_object(key, content).await.unwrap();
+        }
+
+        let items = source.list_new().await.unwrap();
+        let keys: Vec<&str> = items.iter().map(|item| item.key.as_str()).collect();
+        assert_eq!(keys, ["runs/1.zip"]);
+
+        let tiny = ArtifactLimits {
+            max_total_bytes: 1,
+            ..ArtifactLimits::default()
+        };
+        assert!(matches!(
+            source.fetch(&items[0], &tiny).await,
+            Err(IggyBenchDashboardServerError::InvalidArtifact(_))
+        ));
+
+        let fetched = source
+            .fetch(&items[0], &ArtifactLimits::default())
+            .await
+            .unwrap();
+        assert_eq!(fetched.len(), 1);
+        assert_eq!(fetched[0].record.size_bytes, archive.len() as u64);
+        assert_eq!(std::fs::read(fetched[0].archive.path()).unwrap(), archive);
+
+        source.acknowledge(&items[0]).await.unwrap();
+        assert!(source.list_new().await.unwrap().is_empty());
+
+        for key in ["runs/notes.txt", "other/2.zip"] {
+            source.bucket.delete_object(key).await.unwrap();
+        }
+        source.bucket.delete().await.unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+use super::{ArtifactLimits, ArtifactRecord};
+use crate::error::IggyBenchDashboardServerError;
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use tempfile::NamedTempFile;
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+/// Unit of ingestion offered by an [`ArtifactSource`], e.g. a workflow run or a ZIP file
+#[derive(Debug, Clone)]
+pub struct ArtifactItem {
+    /// Identifies the item in the ingest manifest
+    pub id: u64,
+    /// Identifies the item within its source, e.g. an object key or a URL
+    pub key: String,
+    pub head_sha: Option<String>,
+    pub gitref: Option<String>,
+    pub branch: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl ArtifactItem {
+    /// Item without a numeric identifier of its own, identified by a stable
+    /// hash of the source name, `key` and `revision`, e.g. an ETag
+    pub fn keyed(source_name: &str, key: String, revision: &str) -> Self {
+        Self {
+            id: item_id(source_name, &key, revision),
+            key,
+            head_sha: None,
+            gitref: None,
+            branch: None,
+            created_at: None,
+        }
+    }
+}
+
+/// Downloaded ZIP archive of an item, removed from disk when dropped. The
+/// pipeline unpacks it.
+pub struct FetchedArtifact {
+    pub record: ArtifactRecord,
+    pub archive: NamedTempFile,
+}
+
+/// Backend benchmark results are ingested from. Items are listed, fetched and
+/// acknowledged by the source, everything in between is done by the shared
+/// [`ImportPipeline`](super::ImportPipeline).
+pub trait ArtifactSource: Send + Sync {
+    /// Name shown in logs, the poller status and the ingest manifest
+    fn name(&self) -> String;
+
+    /// Items currently offered, the pipeline skips those imported already
+    fn list_new(&self) -> impl Future<Output = Result<Vec<ArtifactItem>>> + Send;
+
+    /// Downloads the artifacts of an item, rejecting archives over the size limit
+    fn fetch(
+        &self,
+        item: &ArtifactItem,
+        limits: &ArtifactLimits,
+    ) -> impl Future<Output = Result<Vec<FetchedArtifact>>> + Send;
+
+    /// Called once the item is imported, e.g. to remove it from the source
+    fn acknowledge(&self, item: &ArtifactItem) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// FNV-1a hash of the source name, item key and revision, limited to 53 bits
+/// so that the ID survives JSON consumers using floating point numbers
+fn item_id(source_name: &str, key: &str, revision: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    let bytes = source_name
+        .bytes()
+        .chain([0])
+        .chain(key.bytes())
+        .chain([0])
+        .chain(revision.bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash & ((1 << 53) - 1)
+}
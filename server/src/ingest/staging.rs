@@ -0,0 +1,153 @@
+use crate::cache::CANDIDATES_DIR;
+use crate::error::IggyBenchDashboardServerError;
+use file_operation::async_copy_dir_files;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tokio::fs;
+use tracing::{info, trace, warn};
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+/// Hidden directory inside the results directory that ingestion writes into.
+/// Being on the same filesystem as the results, staged benchmarks are moved
+/// into place with an atomic rename. The loader and watcher ignore it.
+pub const STAGING_DIR: &str = ".staging";
+
+/// Directory inside a staging directory holding the benchmarks and candidates
+/// being replaced, at their path relative to the results directory
+pub const REPLACED_DIR: &str = ".replaced";
+
+/// New directory in the staging area, removed with its content when dropped
+pub async fn staging_dir(results_dir: &Path) -> Result<TempDir> {
+    let staging_root = results_dir.join(STAGING_DIR);
+    fs::create_dir_all(&staging_root).await?;
+    Ok(tempfile::Builder::new()
+        .prefix("ingest-")
+        .tempdir_in(staging_root)?)
+}
+
+/// Removes what an interrupted ingestion left behind: the staging area and
+/// hidden candidate directories written by earlier versions. A replaced
+/// benchmark or candidate whose replacement never made it into place is
+/// moved back first.
+pub async fn clean_staging(results_dir: &Path) -> Result<()> {
+    let staging_root = results_dir.join(STAGING_DIR);
+    if staging_root.exists() {
+        let mut entries = fs::read_dir(&staging_root).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let replaced_dir = entry.path().join(REPLACED_DIR);
+            if replaced_dir.is_dir() {
+                restore_replaced(&replaced_dir, results_dir).await?;
+                restore_replaced(
+                    &replaced_dir.join(CANDIDATES_DIR),
+                    &results_dir.join(CANDIDATES_DIR),
+                )
+                .await?;
+            }
+        }
+
+        warn!(
+            "Removing leftovers of interrupted ingestion in {}",
+            staging_root.display()
+        );
+        fs::remove_dir_all(&staging_root).await?;
+    }
+
+    let candidates_dir = results_dir.join(CANDIDATES_DIR);
+    if candidates_dir.exists() {
+        let mut entries = fs::read_dir(&candidates_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                warn!("Removing leftover candidate {}", entry.path().display());
+                fs::remove_dir_all(entry.path()).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Moves the directories of `replaced_dir` back into `destination`, unless
+/// something is already there
+async fn restore_replaced(replaced_dir: &Path, destination: &Path) -> Result<()> {
+    if !replaced_dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(replaced_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name();
+        if name == CANDIDATES_DIR {
+            continue;
+        }
+        let target = destination.join(&name);
+        if target.exists() {
+            continue;
+        }
+        warn!(
+            "Restoring {} replaced by an interrupted ingestion",
+            target.display()
+        );
+        fs::create_dir_all(destination).await?;
+        fs::rename(entry.path(), &target).await?;
+    }
+    Ok(())
+}
+
+/// Copies the benchmark directories found in `benchmarks_dirs` into `destination`,
+/// returning the copied directories. A benchmark present in several artifacts is
+/// copied once.
+pub async fn copy_into(benchmarks_dirs: &[PathBuf], destination: &Path) -> Result<Vec<PathBuf>> {
+    let mut copied = Vec::new();
+    for benchmarks_dir in benchmarks_dirs {
+        let mut entries = fs::read_dir(benchmarks_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let staged = destination.join(entry.file_name());
+            if staged.exists() {
+                trace!("{:?} is already staged, skipping", staged);
+                continue;
+            }
+
+            let source = path.to_str().ok_or_else(|| {
+                IggyBenchDashboardServerError::InvalidPath("Invalid source path".into())
+            })?;
+            let target = staged.to_str().ok_or_else(|| {
+                IggyBenchDashboardServerError::InvalidPath("Invalid destination path".into())
+            })?;
+            info!("Staging {} in {:?}", path.display(), staged);
+            async_copy_dir_files(source, target).await?;
+            copied.push(staged);
+        }
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replaced_directories_without_replacement_are_restored() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let results = results_dir.path();
+        let replaced = results
+            .join(STAGING_DIR)
+            .join("ingest-1")
+            .join(REPLACED_DIR);
+        std::fs::create_dir_all(replaced.join("lost")).unwrap();
+        std::fs::create_dir_all(replaced.join("installed")).unwrap();
+        std::fs::create_dir_all(replaced.join(CANDIDATES_DIR).join("lost-pr")).unwrap();
+        std::fs::create_dir_all(results.join("installed")).unwrap();
+        std::fs::write(results.join("installed").join("new"), "").unwrap();
+
+        clean_staging(results).await.unwrap();
+
+        assert!(results.join("lost").is_dir());
+        assert!(results.join(CANDIDATES_DIR).join("lost-pr").is_dir());
+        assert!(results.join("installed").join("new").exists());
+        assert!(!results.join(CANDIDATES_DIR).join(CANDIDATES_DIR).exists());
+        assert!(!results.join(STAGING_DIR).exists());
+    }
+}
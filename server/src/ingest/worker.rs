@@ -0,0 +1,207 @@
+use super::http_index::{HttpIndexConfig, HttpIndexSource};
+use super::inbox::{InboxConfig, InboxSource};
+use super::s3::{S3Config, S3Source};
+use super::{ArtifactSource, ImportOutcome, ImportPipeline};
+use crate::error::IggyBenchDashboardServerError;
+use crate::github::PollerStatus;
+use chrono::Utc;
+use serde::Deserialize;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::{
+    sync::watch,
+    task::JoinHandle,
+    time::{sleep, timeout_at, Instant},
+};
+use tracing::{error, info, trace, warn};
+
+/// Artifact source other than GitHub, configured as `[[ingest.sources]]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArtifactSourceConfig {
+    Inbox(InboxConfig),
+    S3(S3Config),
+    HttpIndex(HttpIndexConfig),
+}
+
+#[derive(Debug, Clone)]
+pub struct IngestConfig {
+    pub interval: Duration,
+    pub sources: Vec<ArtifactSourceConfig>,
+}
+
+/// Background tasks, one per configured source, importing new items through
+/// the shared pipeline
+pub struct IngestWorkers {
+    shutdown_tx: watch::Sender<bool>,
+    join_handles: Vec<(String, JoinHandle<()>)>,
+}
+
+impl IngestWorkers {
+    pub fn start(
+        config: IngestConfig,
+        pipeline: Arc<ImportPipeline>,
+        status: Arc<PollerStatus>,
+    ) -> Self {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let mut workers = Self {
+            shutdown_tx,
+            join_handles: Vec::new(),
+        };
+
+        for source_config in config.sources {
+            let spawned = match source_config {
+                ArtifactSourceConfig::Inbox(inbox) => {
+                    workers.spawn(
+                        InboxSource::new(inbox),
+                        config.interval,
+                        &pipeline,
+                        &status,
+                        &shutdown_rx,
+                    );
+                    Ok(())
+                }
+                ArtifactSourceConfig::S3(s3) => S3Source::new(s3).map(|source| {
+                    workers.spawn(source, config.interval, &pipeline, &status, &shutdown_rx)
+                }),
+                ArtifactSourceConfig::HttpIndex(http_index) => HttpIndexSource::new(http_index)
+                    .map(|source| {
+                        workers.spawn(source, config.interval, &pipeline, &status, &shutdown_rx)
+                    }),
+            };
+            if let Err(e) = spawned {
+                error!("Failed to start artifact source: {}", e);
+            }
+        }
+        workers
+    }
+
+    fn spawn<S: ArtifactSource + 'static>(
+        &mut self,
+        source: S,
+        interval: Duration,
+        pipeline: &Arc<ImportPipeline>,
+        status: &Arc<PollerStatus>,
+        shutdown_rx: &watch::Receiver<bool>,
+    ) {
+        let name = source.name();
+        info!("Starting ingestion from {} every {:?}", name, interval);
+        status.update(&name, |_| {});
+
+        let worker = SourceWorker {
+            source,
+            pipeline: Arc::clone(pipeline),
+            status: Arc::clone(status),
+            failed_attempts: HashMap::new(),
+        };
+        let shutdown_rx = shutdown_rx.clone();
+        let join_handle = tokio::spawn(worker.run(interval, shutdown_rx));
+        self.join_handles.push((name, join_handle));
+    }
+
+    /// Signals the workers to stop and waits up to `deadline` for them to
+    /// finish the items they are copying
+    pub async fn shutdown(self, deadline: Duration) {
+        if let Err(err) = self.shutdown_tx.send(true) {
+            error!("Error sending shutdown signal: {:?}", err);
+        }
+
+        let deadline = Instant::now() + deadline;
+        for (name, mut join_handle) in self.join_handles {
+            match timeout_at(deadline, &mut join_handle).await {
+                Ok(Ok(())) => info!("Ingestion from {} stopped", name),
+                Ok(Err(e)) => error!("Ingestion task join error for {}: {:?}", name, e),
+                Err(_) => {
+                    warn!("Ingestion from {} did not stop in time, aborting", name);
+                    join_handle.abort();
+                }
+            }
+        }
+    }
+}
+
+struct SourceWorker<S> {
+    source: S,
+    pipeline: Arc<ImportPipeline>,
+    status: Arc<PollerStatus>,
+    /// Failed import attempts per item
+    failed_attempts: HashMap<u64, u32>,
+}
+
+impl<S: ArtifactSource> SourceWorker<S> {
+    async fn run(mut self, interval: Duration, mut shutdown_rx: watch::Receiver<bool>) {
+        let name = self.source.name();
+        loop {
+            if *shutdown_rx.borrow() {
+                info!("Shutdown signal received, stopping ingestion from {}", name);
+                break;
+            }
+
+            self.status.update(&name, |status| {
+                status.last_poll_at = Some(Utc::now());
+            });
+            match self.poll_once(&mut shutdown_rx).await {
+                Ok(()) => self.status.update(&name, |status| {
+                    status.last_success_at = Some(Utc::now());
+                    status.consecutive_failures = 0;
+                }),
+                Err(e) => {
+                    error!("Failed to list new items of {}: {}", name, e);
+                    self.status.record_error(&name, e.to_string());
+                    self.status.update(&name, |status| {
+                        status.consecutive_failures += 1;
+                    });
+                }
+            }
+            self.status.update(&name, |status| {
+                status.next_poll_at = chrono::Duration::from_std(interval)
+                    .ok()
+                    .map(|interval| Utc::now() + interval);
+            });
+
+            tokio::select! {
+                _ = shutdown_rx.changed() => {}
+                _ = sleep(interval) => {}
+            }
+        }
+    }
+
+    async fn poll_once(
+        &mut self,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<(), IggyBenchDashboardServerError> {
+        let name = self.source.name();
+        let mut items = self.source.list_new().await?;
+        items.retain(|item| {
+            let attempts = self.failed_attempts.get(&item.id).copied().unwrap_or(0);
+            self.pipeline.is_pending(&name, item, attempts)
+        });
+        if items.is_empty() {
+            trace!("No new items in {}", name);
+            return Ok(());
+        }
+
+        for item in items {
+            if *shutdown_rx.borrow() {
+                return Ok(());
+            }
+
+            match self.pipeline.import(&self.source, &item, shutdown_rx).await {
+                Ok(ImportOutcome::Imported) => {
+                    self.failed_attempts.remove(&item.id);
+                }
+                Ok(ImportOutcome::Abandoned) => return Ok(()),
+                // A broken item must not stop the others from being imported
+                Err(e) => {
+                    let attempts = self.failed_attempts.entry(item.id).or_default();
+                    *attempts = ImportPipeline::failed_attempts_after(*attempts, &e);
+                    self.pipeline
+                        .record_failure(&name, &item, None, *attempts, &e)
+                        .await;
+                    self.status
+                        .record_error(&name, format!("{}: {}", item.key, e));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,15 @@
 mod args;
 mod cache;
+mod config;
 mod error;
+mod git;
 mod github;
 mod handlers;
+mod ingest;
+mod openapi;
+mod pagination;
+mod shutdown;
+mod tls;
 
 use crate::cache::CacheWatcher;
 use actix_cors::Cors;
@@ -12,23 +19,32 @@ use actix_web::{
     middleware::{Compress, Logger},
     web, App, HttpServer,
 };
-use args::{IggyBenchDashboardServerArgs, PollGithub};
+use args::IggyBenchDashboardServerArgs;
 use cache::BenchmarkCache;
-use github::IggyBenchDashboardGithubPoller;
+use config::{ReloadableSettings, ServerConfig};
+use git::GitRepository;
+use github::{IggyBenchDashboardGithubPoller, PollerStatus};
 use handlers::AppState;
+use ingest::{ImportPipeline, IngestWorkers};
+use openapi::ApiDoc;
+use shutdown::ShutdownController;
 use std::sync::Arc;
 use tracing::{error, info};
 use tracing_subscriber::{
     fmt::{self, format::Format},
     layer::SubscriberExt,
+    reload,
     util::SubscriberInitExt,
     EnvFilter,
 };
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(Clone)]
 struct ServerState {
     cache: Arc<BenchmarkCache>,
-    _watcher: Arc<CacheWatcher>,
+    poller_status: Arc<PollerStatus>,
+    admin_token: Option<String>,
 }
 
 async fn index() -> actix_web::Result<NamedFile> {
@@ -38,22 +54,56 @@ async fn index() -> actix_web::Result<NamedFile> {
 #[actix_web::main]
 async fn main() -> Result<(), std::io::Error> {
     let args = IggyBenchDashboardServerArgs::parse();
-    args.validate();
+    let config = match ServerConfig::load(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load configuration: {}", e);
+            std::process::exit(1);
+        }
+    };
+    config.validate();
 
     let env_filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&args.log_level));
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.log_level));
+    let (env_filter, log_filter) = reload::Layer::new(env_filter);
 
     tracing_subscriber::registry()
-        .with(fmt::layer().event_format(Format::default().with_thread_ids(true)))
         .with(env_filter)
+        .with(fmt::layer().event_format(Format::default().with_thread_ids(true)))
         .try_init()
         .unwrap();
 
-    let results_dir = args.results_dir.clone();
-    let addr = args.server_addr();
-    let cors_origins = args.cors_origins_list();
+    let results_dir = config.results_dir.clone();
+    let addr = config.server_addr();
+    let settings = Arc::new(ReloadableSettings::new(&config));
+    let config_reloader = tokio::spawn(config::reload_on_sighup(
+        args.clone(),
+        Arc::clone(&settings),
+        log_filter,
+    ));
+
+    if let Err(e) = ingest::clean_staging(&results_dir).await {
+        error!("Failed to clean up the ingest staging area: {}", e);
+        std::process::exit(1);
+    }
 
-    let cache = Arc::new(BenchmarkCache::new(results_dir.clone()).await);
+    // Runs ingested by earlier versions belong to the workflow polled now
+    let legacy_source = config
+        .poller
+        .as_ref()
+        .and_then(|poller| poller.sources.first())
+        .map_or_else(|| ingest::LEGACY_SOURCE.to_owned(), ToString::to_string);
+    let cache = match BenchmarkCache::new(results_dir.clone(), &legacy_source).await {
+        Ok(cache) => Arc::new(
+            cache
+                .with_git(config.git.clone().map(GitRepository::new))
+                .with_index_weights(config.performance_index.weights.clone()),
+        ),
+        Err(e) => {
+            error!("Failed to open ingest manifest: {}", e);
+            std::process::exit(1);
+        }
+    };
     info!("Starting cache load...");
     let start = std::time::Instant::now();
     if let Err(e) = cache.load().await {
@@ -64,63 +114,72 @@ async fn main() -> Result<(), std::io::Error> {
     info!("Cache loaded in {:.2?}", duration);
 
     let watcher = match CacheWatcher::new(Arc::clone(&cache), results_dir.clone()) {
-        Ok(w) => Arc::new(w),
+        Ok(w) => w,
         Err(e) => {
             error!("Failed to initialize file watcher: {}", e);
             std::process::exit(1);
         }
     };
 
-    let poller = if let Some(poller) = args.github {
-        match poller {
-            PollGithub::PollGithub(args) => {
-                info!("Starting GithubPoller for branch {}", args.branch);
-
-                Some(IggyBenchDashboardGithubPoller::start(
-                    results_dir.clone(),
-                    args.branch,
-                    args.interval_seconds,
-                    cache.clone(),
-                ))
+    let poller_status = Arc::new(PollerStatus::default());
+    let pipeline = Arc::new(ImportPipeline::new(
+        results_dir.clone(),
+        Arc::clone(&cache),
+        config.artifact_limits.clone(),
+    ));
+    let poller = match (config.poller.clone(), config.github_token.clone()) {
+        (Some(poller_config), Some(github_token)) => {
+            for source in &poller_config.sources {
+                info!(
+                    "Starting GithubPoller for {} on branches {:?}",
+                    source, source.branches
+                );
             }
+
+            Some(IggyBenchDashboardGithubPoller::start(
+                results_dir.clone(),
+                poller_config,
+                github_token,
+                Arc::clone(&settings),
+                Arc::clone(&pipeline),
+                cache.clone(),
+                Arc::clone(&poller_status),
+            ))
         }
-    } else {
-        None
+        _ => None,
     };
 
+    let ingest_workers = config.ingest.clone().map(|ingest_config| {
+        IngestWorkers::start(
+            ingest_config,
+            Arc::clone(&pipeline),
+            Arc::clone(&poller_status),
+        )
+    });
+
     let state = ServerState {
         cache: Arc::clone(&cache),
-        _watcher: Arc::clone(&watcher),
+        poller_status,
+        admin_token: config.admin_token.clone(),
     };
 
     info!("Starting server on {}", addr);
     info!("Results directory: {}", results_dir.display());
-    info!("Log level: {}", args.log_level);
-    info!("CORS origins: {}", args.cors_origins);
+    info!("Log level: {}", config.log_level);
+    info!("CORS origins: {:?}", config.cors_origins);
 
     let server = HttpServer::new(move || {
         let state = state.clone();
 
-        let cors = if cors_origins.contains(&"*".to_string()) {
-            Cors::default()
-                .allow_any_origin()
-                .allowed_methods(vec!["GET"])
-                .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
-                .allowed_header(header::CONTENT_TYPE)
-                .max_age(3600)
-        } else {
-            let origins = cors_origins.clone();
-            Cors::default()
-                .allowed_origin_fn(move |origin, _req_head| {
-                    origins
-                        .iter()
-                        .any(|allowed| origin.as_bytes().ends_with(allowed.as_bytes()))
-                })
-                .allowed_methods(vec!["GET"])
-                .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
-                .allowed_header(header::CONTENT_TYPE)
-                .max_age(3600)
-        };
+        let settings = Arc::clone(&settings);
+        let cors = Cors::default()
+            .allowed_origin_fn(move |origin, _req_head| {
+                settings.is_origin_allowed(origin.as_bytes())
+            })
+            .allowed_methods(vec!["GET", "POST"])
+            .allowed_headers(vec![header::AUTHORIZATION, header::ACCEPT])
+            .allowed_header(header::CONTENT_TYPE)
+            .max_age(3600);
 
         App::new()
             .wrap(cors)
@@ -130,16 +189,14 @@ async fn main() -> Result<(), std::io::Error> {
             .wrap(Compress::default())
             .app_data(web::Data::new(AppState {
                 cache: Arc::clone(&state.cache),
+                poller_status: Arc::clone(&state.poller_status),
+                admin_token: state.admin_token.clone(),
             }))
-            .service(handlers::health_check)
-            .service(handlers::list_hardware)
-            .service(handlers::list_gitrefs_for_hardware)
-            .service(handlers::list_benchmarks_for_gitref)
-            .service(handlers::list_benchmarks_for_hardware_and_gitref)
-            .service(handlers::get_benchmark_report_full)
-            .service(handlers::get_benchmark_report_light)
-            .service(handlers::get_benchmark_trend)
-            .service(handlers::get_test_artifacts_zip)
+            .configure(handlers::configure)
+            .service(
+                SwaggerUi::new(openapi::EXPLORER_PATH)
+                    .url(openapi::OPENAPI_JSON_PATH, ApiDoc::openapi()),
+            )
             .service(
                 fs::Files::new("/", "frontend/dist")
                     .index_file("index.html")
@@ -147,14 +204,82 @@ async fn main() -> Result<(), std::io::Error> {
             )
             .default_service(web::route().to(index))
     })
-    .bind(&addr)?
-    .run();
+    .disable_signals()
+    .shutdown_timeout(config.shutdown_timeout.as_secs());
+
+    let shutdown = ShutdownController::new(config.shutdown_timeout);
+
+    // Told to stop together with the HTTP servers, within the same deadline
+    let background_shutdown = tokio::spawn({
+        let shutdown = Arc::clone(&shutdown);
+        async move {
+            shutdown.requested().await;
+            let poller = async {
+                if let Some(poller) = poller {
+                    poller.shutdown(shutdown.remaining()).await;
+                }
+            };
+            let ingest_workers = async {
+                if let Some(ingest_workers) = ingest_workers {
+                    ingest_workers.shutdown(shutdown.remaining()).await;
+                }
+            };
+            tokio::join!(poller, ingest_workers);
+        }
+    });
+
+    match &config.tls {
+        Some(tls_config) => {
+            // Kept alive while serving so that certificate changes are picked up
+            let (rustls_config, _certificate_watcher) = match tls::load(tls_config) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    error!("Failed to load TLS certificate: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            info!(
+                "Serving HTTPS with certificate {}",
+                tls_config.cert.display()
+            );
+
+            let server = server.bind_rustls_0_23(&addr, rustls_config)?.run();
+            match tls_config.redirect_port {
+                Some(redirect_port) => {
+                    let redirect_server = tls::redirect_server(
+                        &config.host,
+                        redirect_port,
+                        config.port,
+                        shutdown.timeout(),
+                    )?;
+                    shutdown
+                        .stop_servers_on_signal(vec![server.handle(), redirect_server.handle()]);
+                    tokio::try_join!(server, redirect_server)?;
+                }
+                None => {
+                    shutdown.stop_servers_on_signal(vec![server.handle()]);
+                    server.await?;
+                }
+            }
+        }
+        None => {
+            let server = server.bind(&addr)?.run();
+            shutdown.stop_servers_on_signal(vec![server.handle()]);
+            server.await?;
+        }
+    }
 
-    server.await?;
+    info!("HTTP server stopped");
+    config_reloader.abort();
 
-    if let Some(poller) = poller {
-        poller.shutdown().await;
+    // Also ends the background tasks when the servers stopped without a signal
+    shutdown.request();
+    if let Err(e) = background_shutdown.await {
+        error!("Background task shutdown failed: {}", e);
     }
+    cache.manifest().flush().await;
+    watcher.stop();
 
+    info!("Shutdown complete");
     Ok(())
 }
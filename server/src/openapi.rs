@@ -0,0 +1,305 @@
+use crate::{
+    error::{ErrorDetail, ErrorEnvelope, ErrorResponse},
+    github::SourceStatus,
+    handlers,
+    ingest::{ArtifactRecord, IngestRecord, IngestStatus},
+};
+use iggy_bench_dashboard_shared::{
+    candidate::{
+        BenchmarkComparison, CandidateComparison, HardwareComparison, PullRequestCandidate,
+    },
+    change_grid::{ChangeGrid, ChangeGridRow, GridMetric},
+    commit::{CommitChange, CommitIdentity},
+    coverage::{BenchmarkCoverage, CoverageRow},
+    hardware::{HardwareBenchmark, HardwareEntry, HardwareSpecDifference, HardwareSpecValue},
+    pagination::{BenchmarkSort, SortOrder},
+    performance_index::PerformanceIndex,
+    BenchmarkGroupMetricsLight, BenchmarkIndividualMetricsLight, BenchmarkReportLight,
+};
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi, ToSchema,
+};
+
+/// Path of the generated OpenAPI document
+pub const OPENAPI_JSON_PATH: &str = "/api/openapi.json";
+
+/// Path pattern of the bundled API explorer
+pub const EXPLORER_PATH: &str = "/api/explorer/{_:.*}";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Apache Iggy Benchmarks Dashboard API",
+        description = "Benchmark results of Apache Iggy across hardware and git refs"
+    ),
+    paths(
+        handlers::v1::health_check,
+        handlers::v1::list_hardware,
+        handlers::v1::list_gitrefs_for_hardware,
+        handlers::v1::list_benchmarks_for_gitref,
+        handlers::v1::list_benchmarks_for_hardware_and_gitref,
+        handlers::v1::get_benchmark_report_full,
+        handlers::v1::get_benchmark_report_light,
+        handlers::v1::get_benchmark_trend,
+        handlers::v1::get_test_artifacts_zip,
+        handlers::v1::list_changes,
+        handlers::v1::compare_hardware,
+        handlers::v1::get_coverage,
+        handlers::v1::get_ingest_history,
+        handlers::v2::list_hardware,
+        handlers::v2::list_hardware_registry,
+        handlers::v2::get_hardware_registry_entry,
+        handlers::v2::list_gitrefs_for_hardware,
+        handlers::v2::list_commits_for_hardware,
+        handlers::v2::get_commit,
+        handlers::v2::list_changes,
+        handlers::v2::list_benchmarks_for_gitref,
+        handlers::v2::list_benchmarks_for_hardware_and_gitref,
+        handlers::v2::get_benchmark_report_full,
+        handlers::v2::get_benchmark_report_light,
+        handlers::v2::get_benchmark_trend,
+        handlers::v2::get_test_artifacts_zip,
+        handlers::v2::get_poller_status,
+        handlers::v2::list_candidates,
+        handlers::v2::get_candidate_comparison,
+        handlers::v2::compare_hardware,
+        handlers::v2::get_performance_index_trend,
+        handlers::v2::get_performance_index,
+        handlers::v2::get_coverage,
+        handlers::v2::get_change_grid,
+        handlers::v2::get_ingest_history,
+        handlers::v2::reimport_workflow_run,
+    ),
+    components(schemas(
+        BenchmarkReportLight,
+        BenchmarkGroupMetricsLight,
+        BenchmarkIndividualMetricsLight,
+        BenchmarkHardwareSchema,
+        BenchmarkSort,
+        SortOrder,
+        ErrorResponse,
+        ErrorEnvelope,
+        ErrorDetail,
+        handlers::HealthStatus,
+        handlers::v2::PollerStatusResponse,
+        SourceStatus,
+        PullRequestCandidate,
+        CandidateComparison,
+        HardwareComparison,
+        BenchmarkComparison,
+        CommitIdentity,
+        CommitChange,
+        HardwareEntry,
+        HardwareSpecDifference,
+        HardwareSpecValue,
+        HardwareBenchmark,
+        PerformanceIndex,
+        BenchmarkCoverage,
+        CoverageRow,
+        ChangeGrid,
+        ChangeGridRow,
+        GridMetric,
+        IngestRecord,
+        IngestStatus,
+        ArtifactRecord,
+    )),
+    modifiers(&AdminTokenSecurity)
+)]
+pub struct ApiDoc;
+
+/// Declares the bearer token required by the admin endpoints
+struct AdminTokenSecurity;
+
+impl Modify for AdminTokenSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "admin_token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+    }
+}
+
+/// Schema of `iggy_bench_report::hardware::BenchmarkHardware`, which lives
+/// outside of this workspace and can't derive `ToSchema` itself
+#[allow(dead_code)]
+#[derive(ToSchema)]
+#[schema(as = BenchmarkHardware)]
+pub struct BenchmarkHardwareSchema {
+    identifier: Option<String>,
+    cpu_name: String,
+    cpu_cores: usize,
+    total_memory_mb: u64,
+    os_name: String,
+    os_version: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cache::BenchmarkCache, handlers::AppState, ingest::LEGACY_SOURCE};
+    use actix_web::{
+        http::{Method, StatusCode},
+        test::{call_service, init_service, read_body, TestRequest},
+        web, App, HttpResponse,
+    };
+    use std::{collections::BTreeSet, sync::Arc};
+
+    /// Scope prefix and source of every file registering handlers in `handlers::configure`
+    const HANDLER_SOURCES: &[(&str, &str)] = &[
+        ("", include_str!("handlers/v1.rs")),
+        (handlers::v2::API_V2_PREFIX, include_str!("handlers/v2.rs")),
+    ];
+
+    fn documented_paths() -> BTreeSet<String> {
+        ApiDoc::openapi().paths.paths.keys().cloned().collect()
+    }
+
+    fn routed_paths() -> BTreeSet<String> {
+        HANDLER_SOURCES
+            .iter()
+            .flat_map(|(prefix, source)| {
+                source.lines().filter_map(move |line| {
+                    let line = line.trim();
+                    let route = line
+                        .strip_prefix("#[get(\"")
+                        .or_else(|| line.strip_prefix("#[post(\""))?;
+                    route
+                        .strip_suffix("\")]")
+                        .map(|route| format!("{}{}", prefix, route))
+                })
+            })
+            .collect()
+    }
+
+    fn example_path(path: &str) -> String {
+        path.split('/')
+            .map(|segment| {
+                if segment.starts_with('{') && segment.ends_with('}') {
+                    "example"
+                } else {
+                    segment
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Route and query struct of every handler taking query parameters
+    fn routed_queries() -> Vec<(String, String)> {
+        HANDLER_SOURCES
+            .iter()
+            .flat_map(|(prefix, source)| {
+                source
+                    .split("#[utoipa::path(")
+                    .skip(1)
+                    .filter_map(move |handler| {
+                        let route = handler.lines().find_map(|line| {
+                            let line = line.trim();
+                            line.strip_prefix("#[get(\"")
+                                .or_else(|| line.strip_prefix("#[post(\""))?
+                                .strip_suffix("\")]")
+                        })?;
+                        let signature = handler.split(") -> ").next()?;
+                        let query = signature.split("web::Query<").nth(1)?.split('>').next()?;
+                        Some((format!("{}{}", prefix, route), query.to_string()))
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spec_documents_every_route() {
+        assert_eq!(documented_paths(), routed_paths());
+    }
+
+    #[test]
+    fn spec_documents_pagination_and_sort_parameters() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        for (path, query) in routed_queries() {
+            let expected: &[&str] = match query.as_str() {
+                "PageQuery" => &["limit", "cursor"],
+                "BenchmarkListQuery" => &["limit", "cursor", "sort", "order"],
+                _ => continue,
+            };
+            let documented: Vec<&str> = spec["paths"][&path]["get"]["parameters"]
+                .as_array()
+                .map(|parameters| {
+                    parameters
+                        .iter()
+                        .filter_map(|parameter| parameter["name"].as_str())
+                        .collect()
+                })
+                .unwrap_or_default();
+            for name in expected {
+                assert!(
+                    documented.contains(name),
+                    "{} does not document the '{}' query parameter",
+                    path,
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hardware_entry_refers_to_the_hardware_schema() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = &spec["components"]["schemas"];
+        assert_eq!(
+            schemas["HardwareEntry"]["properties"]["hardware"]["$ref"],
+            "#/components/schemas/BenchmarkHardware"
+        );
+        assert!(schemas["BenchmarkHardware"]["properties"]["cpu_name"].is_object());
+    }
+
+    #[actix_web::test]
+    async fn every_documented_path_is_routed() {
+        let results_dir = tempfile::tempdir().unwrap();
+        let cache = Arc::new(
+            BenchmarkCache::new(results_dir.path().to_path_buf(), LEGACY_SOURCE)
+                .await
+                .unwrap(),
+        );
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(AppState {
+                    cache,
+                    poller_status: Default::default(),
+                    admin_token: None,
+                }))
+                .configure(handlers::configure)
+                .default_service(web::to(|| async { HttpResponse::ImATeapot().finish() })),
+        )
+        .await;
+
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        for (path, operations) in spec["paths"].as_object().unwrap() {
+            for method in operations.as_object().unwrap().keys() {
+                let method = Method::from_bytes(method.to_uppercase().as_bytes()).unwrap();
+                let request = TestRequest::default()
+                    .method(method.clone())
+                    .uri(&example_path(path))
+                    .to_request();
+                let response = call_service(&app, request).await;
+                let status = response.status();
+                let body: serde_json::Value =
+                    serde_json::from_slice(&read_body(response).await).unwrap_or_default();
+                let unmatched = status == StatusCode::IM_A_TEAPOT
+                    || status == StatusCode::METHOD_NOT_ALLOWED
+                    || (body["error"]["code"] == "not_found"
+                        && body["error"]["message"].as_str().is_some_and(|message| {
+                            message.starts_with(handlers::v2::UNMATCHED_ENDPOINT)
+                        }));
+                assert!(
+                    !unmatched,
+                    "{} {} is documented but not routed",
+                    method, path
+                );
+            }
+        }
+    }
+}
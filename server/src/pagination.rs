@@ -0,0 +1,211 @@
+use crate::error::IggyBenchDashboardServerError;
+use chrono::{DateTime, FixedOffset};
+use iggy_bench_dashboard_shared::{
+    pagination::{BenchmarkSort, Page, SortOrder},
+    BenchmarkReportLight,
+};
+use serde::Deserialize;
+use std::cmp::Ordering;
+use utoipa::IntoParams;
+
+/// Page size used when the client does not pass `limit`
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Upper bound for `limit`, larger values are clamped
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// Query parameters accepted by list endpoints that don't support sorting
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Page size, defaults to 100 and is capped at 1000
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+/// Query parameters accepted by list endpoints returning benchmark reports
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BenchmarkListQuery {
+    /// Page size, defaults to 100 and is capped at 1000
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Sort key
+    pub sort: Option<BenchmarkSort>,
+    /// Sort direction
+    pub order: Option<SortOrder>,
+}
+
+impl BenchmarkListQuery {
+    pub fn page(&self) -> PageQuery {
+        PageQuery {
+            limit: self.limit,
+            cursor: self.cursor.clone(),
+        }
+    }
+}
+
+/// Cuts a single page out of an already sorted list.
+///
+/// The cursor is the key of the last item of the previous page, so pages stay
+/// consistent when new items are added to the archive between requests.
+pub fn paginate<T>(
+    items: Vec<T>,
+    query: &PageQuery,
+    key: impl Fn(&T) -> String,
+) -> Result<Page<T>, IggyBenchDashboardServerError> {
+    let total = items.len();
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT);
+
+    let start = match &query.cursor {
+        Some(cursor) => items
+            .iter()
+            .position(|item| key(item) == *cursor)
+            .map(|position| position + 1)
+            .ok_or_else(|| {
+                IggyBenchDashboardServerError::InvalidQuery(format!("Unknown cursor '{}'", cursor))
+            })?,
+        None => 0,
+    };
+
+    let page: Vec<T> = items.into_iter().skip(start).take(limit).collect();
+    let next_cursor = if start + page.len() < total {
+        page.last().map(&key)
+    } else {
+        None
+    };
+
+    Ok(Page {
+        items: page,
+        total,
+        next_cursor,
+    })
+}
+
+/// Paginates benchmark reports, using the report UUID as the cursor
+pub fn paginate_benchmarks(
+    reports: Vec<BenchmarkReportLight>,
+    query: &BenchmarkListQuery,
+) -> Result<Page<BenchmarkReportLight>, IggyBenchDashboardServerError> {
+    paginate(reports, &query.page(), |report| report.uuid.to_string())
+}
+
+/// Sorts benchmark reports by the given key. Ties are broken by UUID so that
+/// cursors always resolve to the same position.
+pub fn sort_benchmarks(
+    reports: &mut [BenchmarkReportLight],
+    sort: BenchmarkSort,
+    order: SortOrder,
+) {
+    reports.sort_by(|a, b| {
+        let ordering = match sort {
+            BenchmarkSort::PrettyName => {
+                apply_order(a.params.pretty_name.cmp(&b.params.pretty_name), order)
+            }
+            BenchmarkSort::Timestamp => compare_optional(
+                parse_timestamp(&a.timestamp),
+                parse_timestamp(&b.timestamp),
+                order,
+            ),
+            BenchmarkSort::Throughput => compare_optional(
+                a.throughput_messages_per_second(),
+                b.throughput_messages_per_second(),
+                order,
+            ),
+            BenchmarkSort::P99 => compare_optional(a.p99_latency_ms(), b.p99_latency_ms(), order),
+        };
+
+        ordering.then_with(|| a.uuid.cmp(&b.uuid))
+    });
+}
+
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(timestamp).ok()
+}
+
+fn apply_order(ordering: Ordering, order: SortOrder) -> Ordering {
+    match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    }
+}
+
+// Reports without a value for the sort key go last regardless of the order
+fn compare_optional<T: PartialOrd>(a: Option<T>, b: Option<T>, order: SortOrder) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => apply_order(a.partial_cmp(&b).unwrap_or(Ordering::Equal), order),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(items: usize, limit: Option<usize>, cursor: Option<&str>) -> Page<usize> {
+        let query = PageQuery {
+            limit,
+            cursor: cursor.map(str::to_owned),
+        };
+        paginate((0..items).collect(), &query, usize::to_string).unwrap()
+    }
+
+    #[test]
+    fn next_cursor_resumes_after_the_last_item() {
+        let first = page(5, Some(2), None);
+        assert_eq!(first.items, [0, 1]);
+        assert_eq!(first.total, 5);
+        assert_eq!(first.next_cursor.as_deref(), Some("1"));
+
+        let second = page(5, Some(2), first.next_cursor.as_deref());
+        assert_eq!(second.items, [2, 3]);
+        assert_eq!(second.next_cursor.as_deref(), Some("3"));
+
+        let last = page(5, Some(2), second.next_cursor.as_deref());
+        assert_eq!(last.items, [4]);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn exactly_full_last_page_has_no_cursor() {
+        let last = page(4, Some(2), Some("1"));
+        assert_eq!(last.items, [2, 3]);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn unknown_cursor_is_rejected() {
+        let query = PageQuery {
+            limit: None,
+            cursor: Some("missing".to_owned()),
+        };
+        let result = paginate((0..5).collect(), &query, usize::to_string);
+        assert!(matches!(
+            result,
+            Err(IggyBenchDashboardServerError::InvalidQuery(_))
+        ));
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        assert_eq!(page(5, Some(0), None).items, [0]);
+        assert_eq!(page(5, None, None).items.len(), 5);
+        assert_eq!(
+            page(MAX_PAGE_LIMIT + 10, Some(MAX_PAGE_LIMIT + 1), None)
+                .items
+                .len(),
+            MAX_PAGE_LIMIT
+        );
+        assert_eq!(
+            page(DEFAULT_PAGE_LIMIT + 1, None, None).items.len(),
+            DEFAULT_PAGE_LIMIT
+        );
+    }
+}
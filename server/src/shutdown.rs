@@ -0,0 +1,102 @@
+use actix_web::dev::ServerHandle;
+use std::{
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::watch,
+    task::JoinSet,
+    time::timeout,
+};
+use tracing::{error, info, warn};
+
+/// Coordinates shutdown of the HTTP servers and background tasks.
+///
+/// On SIGTERM or SIGINT the HTTP servers stop accepting connections and finish
+/// in-flight requests, while the background tasks waiting in
+/// [`ShutdownController::requested`] are told to stop at the same moment.
+/// Everything shares the same deadline, see [`ShutdownController::remaining`].
+#[derive(Debug)]
+pub struct ShutdownController {
+    timeout: Duration,
+    requested_at: OnceLock<Instant>,
+    requested_tx: watch::Sender<bool>,
+}
+
+impl ShutdownController {
+    pub fn new(timeout: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            timeout,
+            requested_at: OnceLock::new(),
+            requested_tx: watch::Sender::new(false),
+        })
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Starts the shutdown deadline and wakes everything waiting in
+    /// [`ShutdownController::requested`]. Later calls keep the first deadline.
+    pub fn request(&self) {
+        self.requested_at.get_or_init(Instant::now);
+        self.requested_tx.send_replace(true);
+    }
+
+    /// Resolves once shutdown is requested
+    pub async fn requested(&self) {
+        let mut requested_rx = self.requested_tx.subscribe();
+        // The sender lives as long as `self`, so this can't fail
+        let _ = requested_rx.wait_for(|requested| *requested).await;
+    }
+
+    /// Waits for SIGTERM or SIGINT in the background, then stops all `servers`
+    /// gracefully at once
+    pub fn stop_servers_on_signal(self: &Arc<Self>, servers: Vec<ServerHandle>) {
+        let controller = Arc::clone(self);
+        tokio::spawn(async move {
+            let (mut terminate, mut interrupt) = match (
+                signal(SignalKind::terminate()),
+                signal(SignalKind::interrupt()),
+            ) {
+                (Ok(terminate), Ok(interrupt)) => (terminate, interrupt),
+                (Err(e), _) | (_, Err(e)) => {
+                    error!("Failed to install shutdown signal handlers: {}", e);
+                    return;
+                }
+            };
+
+            let signal_name = tokio::select! {
+                _ = terminate.recv() => "SIGTERM",
+                _ = interrupt.recv() => "SIGINT",
+            };
+            info!(
+                "{} received, shutting down within {:?}...",
+                signal_name, controller.timeout
+            );
+            controller.request();
+
+            let mut stopping = JoinSet::new();
+            for server in servers {
+                stopping.spawn(async move { server.stop(true).await });
+            }
+            if timeout(controller.remaining(), stopping.join_all())
+                .await
+                .is_err()
+            {
+                warn!("HTTP servers did not stop in time");
+            }
+        });
+    }
+
+    /// Time left until the shutdown deadline. The full timeout is available
+    /// when the servers stopped without a signal.
+    pub fn remaining(&self) -> Duration {
+        self.requested_at
+            .get()
+            .map_or(self.timeout, |requested_at| {
+                self.timeout.saturating_sub(requested_at.elapsed())
+            })
+    }
+}
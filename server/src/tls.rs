@@ -0,0 +1,202 @@
+use crate::error::IggyBenchDashboardServerError;
+use actix_web::{
+    http::{header, uri::Authority},
+    web, App, HttpRequest, HttpResponse, HttpServer,
+};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rustls::{
+    crypto::ring::{default_provider, sign::any_supported_type},
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+    ServerConfig,
+};
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tracing::{error, info};
+
+type Result<T> = std::result::Result<T, IggyBenchDashboardServerError>;
+
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// PEM file with the certificate chain
+    pub cert: PathBuf,
+    /// PEM file with the private key
+    pub key: PathBuf,
+    /// Port serving plain HTTP redirects to HTTPS, disabled if unset
+    pub redirect_port: Option<u16>,
+}
+
+/// Serves the certificate currently on disk, swapped in place by [`CertificateWatcher`]
+#[derive(Debug)]
+struct ReloadingCertResolver {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    certified_key: RwLock<Arc<CertifiedKey>>,
+}
+
+impl ReloadingCertResolver {
+    fn new(config: &TlsConfig) -> Result<Self> {
+        let certified_key = load_certified_key(&config.cert, &config.key)?;
+        Ok(Self {
+            cert_path: config.cert.clone(),
+            key_path: config.key.clone(),
+            certified_key: RwLock::new(Arc::new(certified_key)),
+        })
+    }
+
+    fn reload(&self) {
+        match load_certified_key(&self.cert_path, &self.key_path) {
+            Ok(certified_key) => {
+                *self.certified_key.write().unwrap() = Arc::new(certified_key);
+                info!("Reloaded TLS certificate {}", self.cert_path.display());
+            }
+            Err(e) => error!(
+                "Failed to reload TLS certificate, keeping the current one: {}",
+                e
+            ),
+        }
+    }
+}
+
+impl ResolvesServerCert for ReloadingCertResolver {
+    fn resolve(&self, _client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        Some(Arc::clone(&self.certified_key.read().unwrap()))
+    }
+}
+
+/// Reloads the certificate when the certificate or key file changes on disk.
+/// Parent directories are watched, so replacing the files by renaming works too.
+pub struct CertificateWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Builds the rustls configuration for `config` together with the watcher that
+/// keeps its certificate up to date. The watcher must be kept alive.
+pub fn load(config: &TlsConfig) -> Result<(ServerConfig, CertificateWatcher)> {
+    let resolver = Arc::new(ReloadingCertResolver::new(config)?);
+
+    let server_config = ServerConfig::builder_with_provider(Arc::new(default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| IggyBenchDashboardServerError::InvalidConfig(e.to_string()))?
+        .with_no_client_auth()
+        .with_cert_resolver(Arc::clone(&resolver) as Arc<dyn ResolvesServerCert>);
+
+    let watched_files = [watched_path(&config.cert)?, watched_path(&config.key)?];
+    let watched_dirs: BTreeSet<PathBuf> = watched_files
+        .iter()
+        .filter_map(|file| file.parent().map(Path::to_path_buf))
+        .collect();
+    let watcher_resolver = Arc::clone(&resolver);
+    let mut watcher =
+        notify::recommended_watcher(move |res: std::result::Result<Event, _>| match res {
+            Ok(event) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                    && event.paths.iter().any(|path| watched_files.contains(path))
+                {
+                    watcher_resolver.reload();
+                }
+            }
+            Err(e) => error!("TLS certificate watch error: {:?}", e),
+        })
+        .map_err(|e| IggyBenchDashboardServerError::InvalidPath(e.to_string()))?;
+
+    for dir in &watched_dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| IggyBenchDashboardServerError::InvalidPath(e.to_string()))?;
+    }
+
+    Ok((server_config, CertificateWatcher { _watcher: watcher }))
+}
+
+/// Absolute path of `file` as reported by the watcher. Only the parent directory
+/// is resolved, so a file replaced by renaming keeps the same path.
+fn watched_path(file: &Path) -> Result<PathBuf> {
+    let parent = match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = file.file_name().ok_or_else(|| {
+        IggyBenchDashboardServerError::InvalidPath(format!("{} is not a file", file.display()))
+    })?;
+    Ok(parent.canonicalize()?.join(file_name))
+}
+
+/// Plain HTTP server answering every request with a permanent redirect to `https_port`
+pub fn redirect_server(
+    host: &str,
+    redirect_port: u16,
+    https_port: u16,
+    shutdown_timeout: Duration,
+) -> std::io::Result<actix_web::dev::Server> {
+    info!(
+        "Redirecting HTTP on {}:{} to HTTPS port {}",
+        host, redirect_port, https_port
+    );
+
+    Ok(HttpServer::new(move || {
+        App::new().default_service(web::to(move |req: HttpRequest| async move {
+            let host = req.connection_info().host().to_owned();
+            let host = host
+                .parse::<Authority>()
+                .map_or(host.clone(), |authority| authority.host().to_owned());
+            let authority = if https_port == 443 {
+                host
+            } else {
+                format!("{}:{}", host, https_port)
+            };
+            let path = req
+                .uri()
+                .path_and_query()
+                .map_or("/", |path_and_query| path_and_query.as_str());
+
+            HttpResponse::PermanentRedirect()
+                .append_header((header::LOCATION, format!("https://{}{}", authority, path)))
+                .finish()
+        }))
+    })
+    .disable_signals()
+    .shutdown_timeout(shutdown_timeout.as_secs())
+    .bind((host, redirect_port))?
+    .run())
+}
+
+fn load_certified_key(cert_path: &Path, key_path: &Path) -> Result<CertifiedKey> {
+    let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(cert_path)?))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if certs.is_empty() {
+        return Err(IggyBenchDashboardServerError::InvalidConfig(format!(
+            "No certificates found in {}",
+            cert_path.display()
+        )));
+    }
+
+    let key = rustls_pemfile::private_key(&mut BufReader::new(File::open(key_path)?))?.ok_or_else(
+        || {
+            IggyBenchDashboardServerError::InvalidConfig(format!(
+                "No private key found in {}",
+                key_path.display()
+            ))
+        },
+    )?;
+    let signing_key = any_supported_type(&key)
+        .map_err(|e| IggyBenchDashboardServerError::InvalidConfig(e.to_string()))?;
+
+    // A rotation caught between writing the certificate and the key pairs them wrongly
+    let certified_key = CertifiedKey::new(certs, signing_key);
+    certified_key.keys_match().map_err(|e| {
+        IggyBenchDashboardServerError::InvalidConfig(format!(
+            "Private key {} does not match certificate {}: {}",
+            key_path.display(),
+            cert_path.display(),
+            e
+        ))
+    })?;
+    Ok(certified_key)
+}
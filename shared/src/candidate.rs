@@ -0,0 +1,68 @@
+use crate::BenchmarkReportLight;
+use serde::{Deserialize, Serialize};
+
+/// Pull request whose benchmark run is kept apart from mainline results
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PullRequestCandidate {
+    pub pr_number: u64,
+    pub title: Option<String>,
+    /// `owner/repo/workflow` the run was ingested from
+    pub source: String,
+    pub run_id: u64,
+    pub head_sha: String,
+    pub head_ref: String,
+    pub base_sha: String,
+    pub base_ref: String,
+    /// Mainline git ref of the base commit, the tag of the commit or its SHA
+    pub base_gitref: String,
+    /// Commit date of the base commit, RFC 3339
+    pub base_committed_at: Option<String>,
+    /// When the run was ingested, RFC 3339
+    pub ingested_at: String,
+}
+
+/// Pull request benchmarks next to the closest mainline benchmarks on each hardware
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CandidateComparison {
+    pub candidate: PullRequestCandidate,
+    pub hardware: Vec<HardwareComparison>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HardwareComparison {
+    pub hardware: String,
+    /// Mainline git ref compared against, `None` if the hardware has no mainline results
+    pub baseline_gitref: Option<String>,
+    pub benchmarks: Vec<BenchmarkComparison>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BenchmarkComparison {
+    pub candidate: BenchmarkReportLight,
+    /// Same benchmark on the baseline git ref, if it was run there
+    pub baseline: Option<BenchmarkReportLight>,
+    /// Throughput change against the baseline in percent, positive is better
+    pub throughput_change_percent: Option<f64>,
+    /// P99 latency change against the baseline in percent, negative is better
+    pub p99_change_percent: Option<f64>,
+}
+
+impl BenchmarkComparison {
+    pub fn new(candidate: BenchmarkReportLight, baseline: Option<BenchmarkReportLight>) -> Self {
+        let change = |metric: fn(&BenchmarkReportLight) -> Option<f64>| {
+            let baseline = metric(baseline.as_ref()?)?;
+            let candidate = metric(&candidate)?;
+            (baseline != 0.0).then(|| (candidate - baseline) / baseline * 100.0)
+        };
+        Self {
+            throughput_change_percent: change(BenchmarkReportLight::throughput_messages_per_second),
+            p99_change_percent: change(BenchmarkReportLight::p99_latency_ms),
+            candidate,
+            baseline,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::BenchmarkReportLight;
+use serde::{Deserialize, Serialize};
+
+/// Metric whose change between git refs a change grid shows
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GridMetric {
+    #[default]
+    P99,
+    Throughput,
+}
+
+impl GridMetric {
+    pub fn value(&self, report: &BenchmarkReportLight) -> Option<f64> {
+        match self {
+            GridMetric::P99 => report.p99_latency_ms(),
+            GridMetric::Throughput => report.throughput_messages_per_second(),
+        }
+    }
+
+    /// Whether an increase of the metric is an improvement
+    pub fn higher_is_better(&self) -> bool {
+        matches!(self, GridMetric::Throughput)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GridMetric::P99 => "p99",
+            GridMetric::Throughput => "throughput",
+        }
+    }
+}
+
+/// Change of one metric of every benchmark over the last git refs of one hardware
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChangeGrid {
+    pub hardware: String,
+    pub metric: GridMetric,
+    /// Last git refs benchmarked on the hardware, oldest first
+    pub gitrefs: Vec<String>,
+    /// One row per benchmark that ran at one of the `gitrefs`, sorted by name
+    pub benchmarks: Vec<ChangeGridRow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChangeGridRow {
+    pub params_identifier: String,
+    pub pretty_name: String,
+    /// Metric of the latest run at each of the `gitrefs`, `None` where it did not run
+    pub values: Vec<Option<f64>>,
+    /// Change in percent against the previous git ref the benchmark ran at,
+    /// `None` where it did not run or has nothing to compare against
+    pub change_percent: Vec<Option<f64>>,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Commit benchmarks were run on, known under several git refs
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CommitIdentity {
+    /// Git ref the commit is listed under, its tag if it has one, otherwise
+    /// its short SHA, otherwise the git ref its benchmarks were reported with
+    pub name: String,
+    /// Full SHA, if any benchmark or ingested run revealed it
+    pub sha: Option<String>,
+    /// Every git ref resolving to the commit, sorted, `name` included
+    pub aliases: Vec<String>,
+    /// Index in the first-parent history of the mainline, oldest first, if a
+    /// git clone is configured and the commit is part of it
+    #[serde(default)]
+    pub mainline_position: Option<usize>,
+    /// Set if a git clone is configured and the commit is not reachable from its mainline
+    #[serde(default)]
+    pub off_mainline: bool,
+    /// Position in the commit order of the server, oldest first, the same for
+    /// every hardware. Unset for commits without any date.
+    #[serde(default)]
+    pub rank: Option<usize>,
+}
+
+/// Commit listed in the changelog between two git refs
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CommitChange {
+    pub sha: String,
+    /// First line of the commit message
+    pub subject: String,
+    pub author: String,
+    /// Commit date, RFC 3339
+    pub date: String,
+    /// Pull request the commit was merged with, parsed from its message
+    pub pr_number: Option<u64>,
+}
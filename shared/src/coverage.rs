@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Which benchmarks ran at which git refs on one hardware
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BenchmarkCoverage {
+    pub hardware: String,
+    /// Git refs benchmarked on the hardware, oldest first
+    pub gitrefs: Vec<String>,
+    /// One row per benchmark that ran at least once, sorted by name
+    pub benchmarks: Vec<CoverageRow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CoverageRow {
+    pub params_identifier: String,
+    pub pretty_name: String,
+    /// Number of runs at each of the `gitrefs`, 0 where the benchmark did not run
+    pub counts: Vec<u32>,
+}
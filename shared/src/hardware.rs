@@ -0,0 +1,63 @@
+use crate::BenchmarkReportLight;
+use iggy_bench_report::hardware::BenchmarkHardware;
+use serde::{Deserialize, Serialize};
+
+/// Machine benchmarks were run on, possibly reported under several identifiers
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HardwareEntry {
+    /// Identifier the benchmarks of the machine are listed under
+    pub identifier: String,
+    /// Name to show instead of the identifier, from the hardware registry
+    pub display_name: Option<String>,
+    /// From the hardware registry
+    pub description: Option<String>,
+    /// Other identifiers merged into this one, e.g. former names of the machine
+    pub aliases: Vec<String>,
+    /// Retired hardware is hidden from hardware lists by default
+    pub retired: bool,
+    /// Specification reported by the most recent benchmark
+    #[cfg_attr(feature = "openapi", schema(schema_with = benchmark_hardware_schema))]
+    pub hardware: BenchmarkHardware,
+    /// Specification fields the benchmarks disagree on, empty if all reported the same
+    pub spec_differences: Vec<HardwareSpecDifference>,
+}
+
+/// Refers to the `BenchmarkHardware` schema the server documents, as the
+/// report crate's type has none
+#[cfg(feature = "openapi")]
+fn benchmark_hardware_schema() -> utoipa::openapi::Ref {
+    utoipa::openapi::Ref::from_schema_name("BenchmarkHardware")
+}
+
+/// Field of the hardware specification reported with different values
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HardwareSpecDifference {
+    /// Name of the field, e.g. `cpu_name`
+    pub field: String,
+    pub values: Vec<HardwareSpecValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HardwareSpecValue {
+    pub value: String,
+    /// Identifiers the value was reported under
+    pub identifiers: Vec<String>,
+    pub benchmark_count: usize,
+    /// Timestamps of the first and last benchmark reporting the value, RFC 3339
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+/// Benchmark of one hardware, compared with the same benchmark on other hardware
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HardwareBenchmark {
+    /// Hardware identifier, the same as the report's
+    pub identifier: String,
+    /// From the hardware registry
+    pub display_name: Option<String>,
+    pub report: BenchmarkReportLight,
+}
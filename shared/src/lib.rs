@@ -1,3 +1,11 @@
+pub mod candidate;
+pub mod change_grid;
+pub mod commit;
+pub mod coverage;
+pub mod hardware;
+pub mod metrics;
+pub mod pagination;
+pub mod performance_index;
 pub mod subtext;
 pub mod title;
 
@@ -11,11 +19,15 @@ use uuid::Uuid;
 
 /// A light version of the benchmark report that doesn't include the time series
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BenchmarkReportLight {
     pub timestamp: String,
     pub uuid: Uuid,
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub server_stats: BenchmarkServerStats,
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub params: BenchmarkParams,
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub hardware: BenchmarkHardware,
     pub group_metrics: Vec<BenchmarkGroupMetricsLight>,
     pub individual_metrics: Vec<BenchmarkIndividualMetricsLight>,
@@ -23,12 +35,16 @@ pub struct BenchmarkReportLight {
 
 /// Same as BenchmarkGroupMetrics, but without the time series
 #[derive(Debug, Serialize, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BenchmarkGroupMetricsLight {
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub summary: BenchmarkGroupMetricsSummary,
 }
 
 /// Same as BenchmarkIndividualMetrics, but without the time series
 #[derive(Debug, Clone, Serialize, PartialEq, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BenchmarkIndividualMetricsLight {
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub summary: BenchmarkIndividualMetricsSummary,
 }
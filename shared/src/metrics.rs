@@ -0,0 +1,37 @@
+use crate::BenchmarkReportLight;
+use iggy_bench_report::group_metrics_kind::GroupMetricsKind;
+
+/// Headline metrics used to rank and compare benchmark reports
+impl BenchmarkReportLight {
+    /// Total throughput in messages per second. Uses the combined producers and consumers
+    /// summary when present, otherwise the sum of all group totals.
+    pub fn throughput_messages_per_second(&self) -> Option<f64> {
+        if self.group_metrics.is_empty() {
+            return None;
+        }
+
+        if let Some(total) = self.group_metrics.iter().find(|g| {
+            g.summary.kind == GroupMetricsKind::ProducersAndConsumers
+                || g.summary.kind == GroupMetricsKind::ProducingConsumers
+        }) {
+            return Some(total.summary.total_throughput_messages_per_second);
+        }
+
+        Some(
+            self.group_metrics
+                .iter()
+                .map(|g| g.summary.total_throughput_messages_per_second)
+                .sum(),
+        )
+    }
+
+    /// Worst average P99 latency in milliseconds across all actor groups, skipping the
+    /// combined summary.
+    pub fn p99_latency_ms(&self) -> Option<f64> {
+        self.group_metrics
+            .iter()
+            .filter(|g| g.summary.kind != GroupMetricsKind::ProducersAndConsumers)
+            .map(|g| g.summary.average_p99_latency_ms)
+            .reduce(f64::max)
+    }
+}
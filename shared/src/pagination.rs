@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// A single page of a list endpoint response
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Page<T> {
+    /// Items on this page
+    pub items: Vec<T>,
+    /// Total number of items across all pages
+    pub total: usize,
+    /// Cursor to pass back to get the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Keys by which benchmark lists can be sorted
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkSort {
+    #[default]
+    PrettyName,
+    Timestamp,
+    Throughput,
+    P99,
+}
+
+/// Sort direction
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// How much faster a git ref is than a baseline on one hardware, over the
+/// benchmarks both ran. 1.0 is as fast as the baseline, 1.1 is 10% faster.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PerformanceIndex {
+    pub hardware: String,
+    pub gitref: String,
+    pub baseline_gitref: String,
+    /// Weighted geometric mean of `throughput_index` and `latency_index`
+    pub index: f64,
+    /// Weighted geometric mean of the throughput relative to the baseline
+    pub throughput_index: f64,
+    /// Weighted geometric mean of the baseline P99 latency relative to this one
+    pub latency_index: f64,
+    /// Benchmarks present in both git refs that were taken into account
+    pub benchmark_count: usize,
+}